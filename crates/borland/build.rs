@@ -1,23 +1,51 @@
 use std::{
+    collections::HashSet,
     fs::{self, OpenOptions},
     io::Write,
     path::PathBuf,
 };
 
-#[derive(Debug, Copy, Clone)]
-struct PackedPoint {
-    pub x: i8,
-    pub y: i8,
-    pub pen: bool,
-}
+use vector_text_build::{
+    StrokeData, check_font, glyph_to_rust, load_link_section, load_subset, metrics_to_rust,
+    place_statics,
+};
+use vector_text_core::{FontMetrics, PackedPoint, Strokes};
+
+#[allow(dead_code)]
+#[path = "src/codepage.rs"]
+mod codepage;
+
+use codepage::Codepage;
 
 const NUM_GLYPHS: usize = 256; // ASCII only, sorry
+/// Length of the font header (starting with `+`), before the offset and width tables
+const FONT_HEADER_LEN: usize = 16;
+/// Distance between the lines used to fill shapes, with the `hatch-fill` feature
+const HATCH_SPACING: i16 = 3;
 type FontFile = [Option<Glyph>; NUM_GLYPHS];
 
 /// Generate the output Rust definitions for the font data.
-fn generate_rust(font: &[Option<Glyph>], name: &str) -> String {
+///
+/// With a `subset`, only the glyphs for its characters (in either [Codepage]) are
+/// included. The metrics are still measured from every glyph, apart from those given
+/// by the `header`.
+fn generate_rust(
+    font: &[Option<Glyph>],
+    header: &Header,
+    name: &str,
+    subset: Option<&HashSet<char>>,
+    strokes: &mut StrokeData,
+) -> String {
     let mut out = String::new();
 
+    let keep = |byte: u8| {
+        subset.is_none_or(|subset| {
+            [Codepage::Cp437, Codepage::Latin1]
+                .iter()
+                .any(|codepage| subset.contains(&codepage.decode(byte)))
+        })
+    };
+
     // Write the symbol table
     out.push_str(&format!(
         "static {}_FONT: [Option<Glyph>; {}] = [\n",
@@ -25,29 +53,37 @@ fn generate_rust(font: &[Option<Glyph>], name: &str) -> String {
         font.len()
     ));
 
-    for glyph in font {
+    for (byte, glyph) in font.iter().enumerate() {
         match glyph {
-            None => out.push_str("    None,\n"),
-            Some(g) => {
-                out.push_str("    Some(Glyph {\n");
-                out.push_str(&format!("        left: {},\n", g.left));
-                out.push_str(&format!("        right: {},\n", g.right));
-                out.push_str("        strokes: &[\n");
-
-                for p in &g.strokes {
-                    out.push_str(&format!(
-                        "            PackedPoint {{ x: {}, y: {}, pen: {} }},\n",
-                        p.x, p.y, p.pen
-                    ));
-                }
-
-                out.push_str("        ],\n    }),\n");
-            }
+            Some(g) if keep(byte as u8) => out.push_str(&format!(
+                "    Some({}),\n",
+                glyph_to_rust(&g.as_glyph(), strokes)
+            )),
+            _ => out.push_str("    None,\n"),
         }
     }
 
     out.push_str("];\n");
 
+    let lookup = |c: char| Some(font.get(c as usize)?.as_ref()?.as_glyph());
+    let measured = FontMetrics::measure(lookup);
+    let metrics = FontMetrics {
+        // Y coordinates are flipped from the file, so that they increase downward
+        baseline: -(header.origin_to_baseline as i16),
+        ascender: header.origin_to_top as i16 - header.origin_to_baseline as i16,
+        descender: header.origin_to_baseline as i16 - header.origin_to_descender as i16,
+        ..measured
+    };
+    // Glyphs are looked up by their character code in the file
+    let codes = (0..=u8::MAX).map(char::from);
+    check_font(name, codes, lookup, &metrics);
+
+    out.push_str(&format!(
+        "const {}_METRICS: FontMetrics = {};\n",
+        name,
+        metrics_to_rust(&metrics)
+    ));
+
     out
 }
 
@@ -58,6 +94,27 @@ struct Glyph {
     pub strokes: Vec<PackedPoint>,
 }
 
+impl Glyph {
+    /// Borrow this glyph's data as a [vector_text_core::Glyph].
+    fn as_glyph(&self) -> vector_text_core::Glyph<'_> {
+        vector_text_core::Glyph {
+            left: self.left,
+            right: self.right,
+            strokes: Strokes::Points(&self.strokes),
+            anchors: &[],
+        }
+    }
+}
+
+/// Vertical metrics from the font header, as distances up from the origin
+struct Header {
+    /// Distance to the top of capital letters, and of accents above them
+    origin_to_top: i8,
+    origin_to_baseline: i8,
+    /// Distance to the bottom of descenders, which is negative
+    origin_to_descender: i8,
+}
+
 /// Represents a position that may be advanced within a buffer.
 struct Cursor<'a> {
     buf: &'a [u8],
@@ -151,11 +208,108 @@ impl<'a> Cursor<'a> {
     }
 }
 
+/// Handle the "do scan" command, which fills the shapes drawn since `start`.
+///
+/// This closes the current polygon, and if `hatch` is set, fills the shapes with
+/// horizontal lines (treating each polygon as closed, and using the even-odd rule).
+fn do_scan(path: &mut Vec<PackedPoint>, start: usize, hatch: bool) {
+    // Close the current polygon
+    let first = path.iter().rfind(|p| !p.pen).copied();
+
+    if let (Some(first), Some(last)) = (first, path.last())
+        && last.pen
+        && (first.x, first.y) != (last.x, last.y)
+    {
+        path.push(PackedPoint {
+            x: first.x,
+            y: first.y,
+            pen: true,
+        });
+    }
+
+    if !hatch {
+        return;
+    }
+
+    // Find the edges of each polygon drawn since the last scan
+    let mut edges = Vec::new();
+    let mut first = None;
+    let mut prev = None;
+
+    for &point in &path[start..] {
+        if point.pen {
+            edges.extend(prev.map(|prev| (prev, point)));
+        } else {
+            edges.extend(first.zip(prev).map(|(first, last)| (last, first)));
+            first = Some(point);
+        }
+
+        prev = Some(point);
+    }
+
+    edges.extend(first.zip(prev).map(|(first, last)| (last, first)));
+
+    let (Some(top), Some(bottom)) = (
+        edges.iter().map(|(a, b)| a.y.min(b.y) as i16).min(),
+        edges.iter().map(|(a, b)| a.y.max(b.y) as i16).max(),
+    ) else {
+        return;
+    };
+
+    // Draw every other line backwards, to save moving back across the shape
+    let mut forwards = true;
+    let mut y = top + HATCH_SPACING / 2;
+
+    while y < bottom {
+        let mut crossings: Vec<f32> = edges
+            .iter()
+            .filter(|(a, b)| (a.y.min(b.y) as i16) <= y && y < (a.y.max(b.y) as i16))
+            .map(|(a, b)| {
+                let t = (y - a.y as i16) as f32 / (b.y as i16 - a.y as i16) as f32;
+                a.x as f32 + t * (b.x as f32 - a.x as f32)
+            })
+            .collect();
+        crossings.sort_by(f32::total_cmp);
+
+        let mut spans: Vec<(i8, i8)> = crossings
+            .chunks_exact(2)
+            .map(|pair| (pair[0].round() as i8, pair[1].round() as i8))
+            .filter(|(left, right)| left != right)
+            .collect();
+
+        if !forwards {
+            spans.reverse();
+            spans = spans
+                .into_iter()
+                .map(|(left, right)| (right, left))
+                .collect();
+        }
+
+        for (from, to) in spans {
+            path.push(PackedPoint {
+                x: from,
+                y: y as i8,
+                pen: false,
+            });
+            path.push(PackedPoint {
+                x: to,
+                y: y as i8,
+                pen: true,
+            });
+        }
+
+        forwards = !forwards;
+        y += HATCH_SPACING;
+    }
+}
+
 /// Parse a .CHR format font file.
 ///
 /// Based on this specification:
 /// https://www.fileformat.info/format/borland-chr/corion.htm
-fn parse_chrfile(input: &[u8]) -> FontFile {
+///
+/// If `hatch` is set, shapes which the font fills are filled with hatching.
+fn parse_chrfile(input: &[u8], hatch: bool) -> (FontFile, Header) {
     let mut cur = Cursor::new(input);
 
     // Read file magic
@@ -195,9 +349,9 @@ fn parse_chrfile(input: &[u8]) -> FontFile {
     let _driver_major_version = cur.read_u8();
     let _driver_minor_version = cur.read_u8();
 
-    let header_end = cur.read_u16_le();
     // docs list this as 0x0100 but i think that's an endianness oops
-    assert_eq!(header_end, 0x0001);
+    // Either way, nothing depends on it, so don't insist on a particular value
+    let _header_end = cur.read_u16_le();
 
     // Seek to end of header
     eprintln!("Skipping to {}", header_len);
@@ -214,27 +368,25 @@ fn parse_chrfile(input: &[u8]) -> FontFile {
     let start_char = cur.read_u8();
     eprintln!("Starting at character {}", start_char);
 
-    // TODO: what does this mean?
-    let _stroke_offset = cur.read_u16_le();
+    // Offset from the start of the font header to the stroke definitions
+    let stroke_offset = cur.read_u16_le();
 
     let _scan_flag = cur.read_u8(); // docs say "??" so idk what this is
 
     // Font metric time!
-    // Distance from origin to top of capital letter
-    let _origin_to_top = cur.read_u8();
-    // Distance from origin to baseline
-    let _origin_to_baseline = cur.read_u8();
-    // Distance from origin to bottom of descender
-    let _origin_to_descender = cur.read_u8();
+    let header = Header {
+        origin_to_top: cur.read_u8() as i8,
+        origin_to_baseline: cur.read_u8() as i8,
+        origin_to_descender: cur.read_u8() as i8,
+    };
 
     // Docs specify that this is the short font name, repeated
     // Nope -- null bytes! At least in my file
     cur.skip(4);
 
     // there is an extra byte here that they forgot about in the spec
-    cur.skip(1);
-
-    assert_eq!(cur.pos, 0x0090);
+    // Rather than trusting the spec's layout, find the tables from the header length
+    cur.skip_to(header_len as usize + FONT_HEADER_LEN);
 
     // Offsets to stroke data for each character
     // TODO there is surely a faster way lol
@@ -254,7 +406,9 @@ fn parse_chrfile(input: &[u8]) -> FontFile {
 
     // The rest of the file is character definitions!
 
-    let data_section_start = cur.pos;
+    // This usually follows the width table directly, but use the header's offset to it
+    // in case a file has padding in between
+    let data_section_start = header_len as usize + stroke_offset as usize;
 
     let mut file: FontFile = std::array::from_fn(|_| None);
 
@@ -263,11 +417,23 @@ fn parse_chrfile(input: &[u8]) -> FontFile {
         let offset = chr_offsets[i] as usize + data_section_start;
         let width = chr_widths[i];
 
+        if ascii_value >= NUM_GLYPHS || offset >= input.len() {
+            eprintln!("Skipping character {}, which is out of range", ascii_value);
+            continue;
+        }
+
         cur.skip_to(offset);
 
         let mut path = Vec::new();
+        // Where the shapes filled by the next "do scan" command start
+        let mut scan_start = 0;
 
         loop {
+            if cur.pos + 2 > input.len() {
+                eprintln!("Character {} is truncated", ascii_value);
+                break;
+            }
+
             let coord = cur.read_coord();
 
             match coord.opcode {
@@ -276,8 +442,9 @@ fn parse_chrfile(input: &[u8]) -> FontFile {
                     break;
                 }
                 0b01 => {
-                    // "Do scan"
-                    panic!("Unknown scan command");
+                    // "Do scan", which fills the shapes drawn so far (e.g. in BOLD.CHR)
+                    do_scan(&mut path, scan_start, hatch);
+                    scan_start = path.len();
                 }
                 0b10 => {
                     // Move the pointer to X, Y
@@ -301,14 +468,25 @@ fn parse_chrfile(input: &[u8]) -> FontFile {
 
         let glyph = Glyph {
             left: 0,
-            right: (width as i8),
+            right: width.min(i8::MAX as u8) as i8,
             strokes: path,
         };
 
         file[ascii_value] = Some(glyph);
     }
 
-    file
+    (file, header)
+}
+
+/// Convert a font's file name to the name of its enum variant.
+fn variant_name(font: &str) -> String {
+    font.chars()
+        .enumerate()
+        .map(|(i, c)| match i {
+            0 => c.to_ascii_uppercase(),
+            _ => c.to_ascii_lowercase(),
+        })
+        .collect()
 }
 
 /// Generate an enum and implementation mapping font names to glyph tables.
@@ -317,17 +495,12 @@ fn generate_enum(variants: &[&str]) -> String {
 
     // Generate the enum definition
     out.push_str("/// A specific Borland font instance (i.e., `.CHR` file).\n");
+    out.push_str("#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]\n");
+    out.push_str("#[non_exhaustive]\n");
     out.push_str("pub enum BorlandFont {\n");
 
     for font in variants {
-        let name: String = font
-            .chars()
-            .enumerate()
-            .map(|(i, c)| match i {
-                0 => c.to_ascii_uppercase(),
-                _ => c.to_ascii_lowercase(),
-            })
-            .collect();
+        let name = variant_name(font);
 
         out.push_str(&format!("    {},\n", name));
     }
@@ -336,24 +509,60 @@ fn generate_enum(variants: &[&str]) -> String {
 
     // Generate implementation mapping to values
     out.push_str("impl BorlandFont {\n");
+    out.push_str("    /// Every Borland font, in alphabetical order.\n");
+    out.push_str(&format!(
+        "    pub const ALL: [Self; {}] = [\n",
+        variants.len()
+    ));
+
+    for font in variants {
+        out.push_str(&format!("        Self::{},\n", variant_name(font)));
+    }
+
+    out.push_str("    ];\n\n");
+
+    out.push_str(
+        "    /// The name of this font, which is the name of its `.CHR` file in lowercase.\n",
+    );
+    out.push_str("    pub fn name(&self) -> &'static str {\n");
+    out.push_str("        match self {\n");
+
+    for font in variants {
+        out.push_str(&format!(
+            "            Self::{} => \"{}\",\n",
+            variant_name(font),
+            font.to_ascii_lowercase()
+        ));
+    }
+
+    out.push_str("        }\n");
+    out.push_str("    }\n\n");
+
     out.push_str(&format!(
-        "    fn table(self) -> &'static [Option<Glyph>; {}] {{\n",
+        "    fn table(&self) -> &'static [Option<Glyph<'static>>; {}] {{\n",
         NUM_GLYPHS
     ));
     out.push_str("        match self {\n");
 
     for font in variants {
-        let name: String = font
-            .chars()
-            .enumerate()
-            .map(|(i, c)| match i {
-                0 => c.to_ascii_uppercase(),
-                _ => c.to_ascii_lowercase(),
-            })
-            .collect();
+        let name = variant_name(font);
         out.push_str(&format!("            Self::{} => &{}_FONT,\n", name, font));
     }
 
+    out.push_str("        }\n");
+    out.push_str("    }\n\n");
+
+    out.push_str("    fn metrics(&self) -> FontMetrics {\n");
+    out.push_str("        match self {\n");
+
+    for font in variants {
+        let name = variant_name(font);
+        out.push_str(&format!(
+            "            Self::{} => {}_METRICS,\n",
+            name, font
+        ));
+    }
+
     out.push_str("        }\n");
     out.push_str("    }\n");
     out.push_str("}\n");
@@ -362,11 +571,24 @@ fn generate_enum(variants: &[&str]) -> String {
 }
 
 fn main() {
-    // TODO: "BOLD.CHR" does not parse properly
-    let fonts = [
-        // "BOLD",
-        "EURO", "GOTH", "LCOM", "LITT", "SANS", "SCRI", "SIMP", "TRIP", "TSCR",
-    ];
+    // Only the fonts whose features are enabled, e.g. `litt` for LITT.CHR
+    let fonts: Vec<&str> = [
+        "BOLD", "EURO", "GOTH", "LCOM", "LITT", "SANS", "SCRI", "SIMP", "TRIP", "TSCR",
+    ]
+    .into_iter()
+    .filter(|font| std::env::var_os(format!("CARGO_FEATURE_{}", font)).is_some())
+    .collect();
+
+    assert!(
+        !fonts.is_empty(),
+        "enable at least one font feature (such as `litt`, or `all-fonts`; or through \
+         vector-text, `borland-litt` or `borland-all`)"
+    );
+
+    // Fill shapes with hatching, with the `hatch-fill` feature
+    let hatch = std::env::var_os("CARGO_FEATURE_HATCH_FILL").is_some();
+    let subset = load_subset();
+    let section = load_link_section();
 
     let out_dir = PathBuf::from(std::env::var("OUT_DIR").unwrap());
     let out_path = out_dir.join("chr_font.rs");
@@ -380,11 +602,22 @@ fn main() {
 
     output.write_all(generate_enum(&fonts).as_bytes()).unwrap();
 
+    let mut strokes = StrokeData::new();
+
     for font in fonts {
-        let glyphs = parse_chrfile(&fs::read(format!("data/{}.CHR", font)).unwrap());
+        let (glyphs, header) =
+            parse_chrfile(&fs::read(format!("data/{}.CHR", font)).unwrap(), hatch);
+        let code = generate_rust(&glyphs, &header, font, subset.as_ref(), &mut strokes);
         output
-            .write_all(generate_rust(&glyphs, font).as_bytes())
+            .write_all(place_statics(&code, section.as_deref()).as_bytes())
             .unwrap();
         println!("cargo:rerun-if-changed=data/{}.CHR", font);
     }
+
+    output
+        .write_all(
+            place_statics(&strokes.to_rust("chr_strokes.bin"), section.as_deref()).as_bytes(),
+        )
+        .unwrap();
+    strokes.write(&out_dir, "chr_strokes.bin");
 }
@@ -0,0 +1,74 @@
+/// The character set used to index the glyphs of a Borland font.
+///
+/// BGI fonts store their glyphs by byte value, in whatever codepage the program drawing
+/// them used. Text is mapped from Unicode to bytes in this codepage before looking up
+/// glyphs.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Hash)]
+pub enum Codepage {
+    /// The original IBM PC character set, used by DOS and by the bundled fonts.
+    ///
+    /// Bytes 1 to 31 are drawn as their symbols (such as `☺` and `♥`), and can be
+    /// selected by either the symbol or the control character.
+    #[default]
+    Cp437,
+    /// ISO 8859-1, where each byte is the Unicode code point of the same value.
+    Latin1,
+}
+
+/// The characters of CP437 bytes 0x01 to 0x1F
+const CP437_LOW: [char; 31] = [
+    '☺', '☻', '♥', '♦', '♣', '♠', '•', '◘', '○', '◙', '♂', '♀', '♪', '♫', '☼', '►', '◄', '↕', '‼',
+    '¶', '§', '▬', '↨', '↑', '↓', '→', '←', '∟', '↔', '▲', '▼',
+];
+
+/// The character of CP437 byte 0x7F
+const CP437_DELETE: char = '⌂';
+
+/// The characters of CP437 bytes 0x80 to 0xFF
+#[rustfmt::skip]
+const CP437_HIGH: [char; 128] = [
+    'Ç', 'ü', 'é', 'â', 'ä', 'à', 'å', 'ç', 'ê', 'ë', 'è', 'ï', 'î', 'ì', 'Ä', 'Å',
+    'É', 'æ', 'Æ', 'ô', 'ö', 'ò', 'û', 'ù', 'ÿ', 'Ö', 'Ü', '¢', '£', '¥', '₧', 'ƒ',
+    'á', 'í', 'ó', 'ú', 'ñ', 'Ñ', 'ª', 'º', '¿', '⌐', '¬', '½', '¼', '¡', '«', '»',
+    '░', '▒', '▓', '│', '┤', '╡', '╢', '╖', '╕', '╣', '║', '╗', '╝', '╜', '╛', '┐',
+    '└', '┴', '┬', '├', '─', '┼', '╞', '╟', '╚', '╔', '╩', '╦', '╠', '═', '╬', '╧',
+    '╨', '╤', '╥', '╙', '╘', '╒', '╓', '╫', '╪', '┘', '┌', '█', '▄', '▌', '▐', '▀',
+    'α', 'ß', 'Γ', 'π', 'Σ', 'σ', 'µ', 'τ', 'Φ', 'Θ', 'Ω', 'δ', '∞', 'φ', 'ε', '∩',
+    '≡', '±', '≥', '≤', '⌠', '⌡', '÷', '≈', '°', '∙', '·', '√', 'ⁿ', '²', '■', '\u{a0}',
+];
+
+impl Codepage {
+    /// The byte which represents a character in this codepage, if there is one.
+    pub fn encode(&self, character: char) -> Option<u8> {
+        match self {
+            Self::Latin1 => u8::try_from(character).ok(),
+            Self::Cp437 => match character {
+                '\0'..='\x7e' => Some(character as u8),
+                CP437_DELETE => Some(0x7f),
+                // Characters the codepage's glyphs are commonly used for
+                'β' => Some(0xe1),
+                'μ' => Some(0xe6),
+                _ => CP437_LOW
+                    .iter()
+                    .position(|&c| c == character)
+                    .map(|i| i as u8 + 0x01)
+                    .or_else(|| {
+                        CP437_HIGH
+                            .iter()
+                            .position(|&c| c == character)
+                            .map(|i| i as u8 + 0x80)
+                    }),
+            },
+        }
+    }
+
+    /// The character which a byte represents in this codepage.
+    pub fn decode(&self, byte: u8) -> char {
+        match (self, byte) {
+            (Self::Latin1, _) | (Self::Cp437, 0x00 | 0x20..=0x7e) => byte as char,
+            (Self::Cp437, 0x01..=0x1f) => CP437_LOW[byte as usize - 0x01],
+            (Self::Cp437, 0x7f) => CP437_DELETE,
+            (Self::Cp437, 0x80..=0xff) => CP437_HIGH[byte as usize - 0x80],
+        }
+    }
+}
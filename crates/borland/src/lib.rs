@@ -6,36 +6,194 @@
 //! It includes the standard BGI fonts from GameMaker, which are available
 //! under an MIT license. For more details, see the original source here:
 //! <https://github.com/gandrewstone/GameMaker>
+//!
+//! Each font has a cargo feature named after its file (such as `litt` for `LITT.CHR`), so
+//! that only the fonts a program uses are compiled in. The `all-fonts` feature, enabled
+//! by default, includes every font.
+//!
+//! To save more space, set the `VECTOR_TEXT_SUBSET` environment variable when building to
+//! compile in only the glyphs for some characters: either `ascii`, `latin1`, or the
+//! absolute path of a file containing the characters. The `compress` feature roughly
+//! halves the size of the stroke data again, by delta-encoding the points (see
+//! [vector_text_core::Strokes]). Setting `VECTOR_TEXT_LINK_SECTION` when building puts
+//! the font tables in the linker section of that name, for targets which map their
+//! flash into memory, and the `progmem` feature keeps the glyphs' points in the program
+//! memory of AVR microcontrollers.
+//!
+//! Some fonts (such as [BorlandFont::Bold]) draw the outlines of shapes which BGI would
+//! fill in. Enable the `hatch-fill` feature to fill them with hatching.
+//!
+//! Glyphs are looked up by their byte value in the DOS codepage ([Codepage::Cp437]). To
+//! draw a font made for another codepage, use [BorlandFont::with_codepage].
 
 extern crate alloc;
 
+mod codepage;
+
 use alloc::vec::Vec;
 
-use vector_text_core::{Glyph, PackedPoint, Point, Renderer};
+pub use codepage::Codepage;
+use vector_text_core::{Font, FontMetrics, Glyph, ParseFontError, RenderError, Renderer};
 
 include!(concat!(env!("OUT_DIR"), "/chr_font.rs"));
 
 /// A [Renderer] which draws text using Borland fonts.
+///
+/// A font's characters are found in order, although the codepage puts them in another
+/// order by byte:
+///
+/// ```
+/// use vector_text_borland::{BorlandFont, BorlandRenderer};
+/// use vector_text_core::Renderer;
+///
+/// let ranges: Vec<_> = BorlandRenderer::coverage(&BorlandFont::Simp).collect();
+/// assert!(ranges.windows(2).all(|pair| pair[0].end() < pair[1].start()));
+/// ```
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Hash)]
 pub struct BorlandRenderer;
 
+impl BorlandRenderer {
+    fn lookup(
+        font: BorlandFont,
+        codepage: Codepage,
+        character: char,
+    ) -> Result<Glyph<'static>, RenderError> {
+        let byte = codepage
+            .encode(character)
+            .ok_or(RenderError::UnsupportedCharacter(character))?;
+
+        font.table()
+            .get(byte as usize)
+            .ok_or(RenderError::CodepointOutOfRange(character))?
+            .ok_or(RenderError::UnsupportedCharacter(character))
+    }
+
+    /// The characters of a font's glyph table in increasing order, rather than in the
+    /// order of their bytes in the codepage
+    fn chars(font: BorlandFont, codepage: Codepage) -> impl Iterator<Item = char> {
+        let mut chars: Vec<char> = (0..font.table().len())
+            .map(|byte| codepage.decode(byte as u8))
+            .collect();
+        chars.sort_unstable();
+        chars.dedup();
+        chars.into_iter()
+    }
+}
+
 impl Renderer<BorlandFont> for BorlandRenderer {
-    fn render_text(text: &str, font: BorlandFont) -> Vec<Point> {
-        let mut result = Vec::new();
-        let mut x_idx = 0;
-
-        let table = font.table();
-
-        for character in text.chars() {
-            if let Some(glyph) = table[character as usize] {
-                result.extend(glyph.strokes.iter().map(|point| Point {
-                    x: point.x as i16 - glyph.left as i16 + x_idx,
-                    y: point.y as i16,
-                    pen: point.pen,
-                }));
-                x_idx += glyph.right as i16 - glyph.left as i16;
-            }
+    fn glyph(font: &BorlandFont, character: char) -> Result<Glyph<'_>, RenderError> {
+        Self::lookup(*font, Codepage::default(), character)
+    }
+
+    fn metrics(font: &BorlandFont) -> FontMetrics {
+        font.metrics()
+    }
+
+    fn candidates(font: &BorlandFont) -> impl Iterator<Item = char> {
+        Self::chars(*font, Codepage::default())
+    }
+}
+
+impl Renderer<EncodedBorlandFont> for BorlandRenderer {
+    fn glyph(font: &EncodedBorlandFont, character: char) -> Result<Glyph<'_>, RenderError> {
+        Self::lookup(font.font, font.codepage, character)
+    }
+
+    fn metrics(font: &EncodedBorlandFont) -> FontMetrics {
+        font.font.metrics()
+    }
+
+    fn candidates(font: &EncodedBorlandFont) -> impl Iterator<Item = char> {
+        Self::chars(font.font, font.codepage)
+    }
+}
+
+impl Font for BorlandFont {
+    fn glyph(&self, character: char) -> Option<Glyph<'_>> {
+        BorlandRenderer::glyph(self, character).ok()
+    }
+
+    fn metrics(&self) -> FontMetrics {
+        BorlandRenderer::metrics(self)
+    }
+}
+
+/// A Borland font whose glyphs are indexed by a particular [Codepage].
+///
+/// ```
+/// use vector_text_borland::{BorlandFont, Codepage};
+/// use vector_text_core::Font;
+///
+/// // In CP437, byte 0xE9 is a theta
+/// assert!(BorlandFont::Simp.supports_char('Θ'));
+///
+/// // In Latin-1, it is an e with an acute accent
+/// let font = BorlandFont::Simp.with_codepage(Codepage::Latin1);
+/// assert!(font.supports_char('é'));
+/// assert!(!font.supports_char('Θ'));
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct EncodedBorlandFont {
+    /// The font to draw
+    pub font: BorlandFont,
+    /// The codepage which the font's glyphs are indexed by
+    pub codepage: Codepage,
+}
+
+impl From<BorlandFont> for EncodedBorlandFont {
+    fn from(font: BorlandFont) -> Self {
+        Self {
+            font,
+            codepage: Codepage::default(),
         }
+    }
+}
+
+impl Font for EncodedBorlandFont {
+    fn glyph(&self, character: char) -> Option<Glyph<'_>> {
+        BorlandRenderer::glyph(self, character).ok()
+    }
+
+    fn metrics(&self) -> FontMetrics {
+        BorlandRenderer::metrics(self)
+    }
+}
+
+impl BorlandFont {
+    /// Iterate over every font, in alphabetical order.
+    pub fn iter() -> impl Iterator<Item = Self> {
+        Self::ALL.into_iter()
+    }
+
+    /// The glyphs of this font, indexed by their character code in the `.CHR` file.
+    ///
+    /// To look up the glyph for a character, use [Font::glyph].
+    pub fn glyphs(&self) -> &'static [Option<Glyph<'static>>] {
+        self.table()
+    }
+
+    /// Look up glyphs using a codepage other than the default [Codepage::Cp437].
+    pub fn with_codepage(self, codepage: Codepage) -> EncodedBorlandFont {
+        EncodedBorlandFont {
+            font: self,
+            codepage,
+        }
+    }
+}
+
+impl core::fmt::Display for BorlandFont {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl core::str::FromStr for BorlandFont {
+    type Err = ParseFontError;
 
-        result
+    /// Parse the name of a font (as given by [BorlandFont::name]), ignoring case.
+    fn from_str(name: &str) -> Result<Self, ParseFontError> {
+        Self::iter()
+            .find(|font| font.name().eq_ignore_ascii_case(name))
+            .ok_or(ParseFontError)
     }
 }
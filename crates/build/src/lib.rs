@@ -0,0 +1,206 @@
+//! `vector-text-build` holds the code generation shared by the build scripts of the
+//! `vector-text` font backends.
+//!
+//! Each backend parses its own font format, then uses this crate to turn the glyphs
+//! into Rust code: [StrokeData] collects their points into a binary file, and the
+//! generated tables refer to slices of it. The `VECTOR_TEXT_SUBSET` and
+//! `VECTOR_TEXT_LINK_SECTION` environment variables are read here too, so that every
+//! backend handles them the same way.
+//!
+//! Applications can use it too: [points_to_rust] turns text rendered in a build script
+//! into a static array, for fixed text which is then drawn with no cost at run time.
+
+use std::{collections::HashSet, env, fs};
+
+use vector_text_core::{FontMetrics, FontReport, Glyph, Point, validate};
+
+mod stroke_data;
+
+pub use stroke_data::StrokeData;
+
+/// Environment variable choosing the characters to compile glyphs for
+pub const SUBSET_VAR: &str = "VECTOR_TEXT_SUBSET";
+
+/// Environment variable naming the linker section to put the font tables in
+pub const SECTION_VAR: &str = "VECTOR_TEXT_LINK_SECTION";
+
+/// Load the set of characters to compile glyphs for from `VECTOR_TEXT_SUBSET`, or `None`
+/// to compile every glyph.
+///
+/// The variable is either `ascii`, `latin1`, or the absolute path of a file containing
+/// the characters to keep (line breaks aside).
+pub fn load_subset() -> Option<HashSet<char>> {
+    println!("cargo:rerun-if-env-changed={}", SUBSET_VAR);
+    let subset = env::var(SUBSET_VAR).ok()?;
+
+    let chars = if subset.eq_ignore_ascii_case("ascii") {
+        (' '..='~').collect()
+    } else if subset.eq_ignore_ascii_case("latin1") {
+        (' '..='~').chain('\u{A0}'..='\u{FF}').collect()
+    } else {
+        println!("cargo:rerun-if-changed={}", subset);
+        fs::read_to_string(&subset)
+            .unwrap_or_else(|e| panic!("{} ({}): {}", SUBSET_VAR, subset, e))
+            .chars()
+            .filter(|&c| c != '\n' && c != '\r')
+            .collect()
+    };
+
+    Some(chars)
+}
+
+/// Load the name of the linker section to put the generated tables in from
+/// `VECTOR_TEXT_LINK_SECTION`, or `None` to leave them where the compiler puts them.
+pub fn load_link_section() -> Option<String> {
+    println!("cargo:rerun-if-env-changed={}", SECTION_VAR);
+    env::var(SECTION_VAR).ok()
+}
+
+/// Put every static in some generated code in the given linker section, except those
+/// already placed in one (such as stroke data in AVR program memory).
+pub fn place_statics(code: &str, section: Option<&str>) -> String {
+    let Some(section) = section else {
+        return code.to_string();
+    };
+
+    let mut placed = false;
+    code.split_inclusive('\n')
+        .map(|line| {
+            let line = if line.starts_with("static ") && !placed {
+                format!("#[unsafe(link_section = {:?})]\n{}", section, line)
+            } else {
+                line.to_string()
+            };
+            placed = line.starts_with("#[unsafe(link_section");
+            line
+        })
+        .collect()
+}
+
+/// Check the glyphs of a font with [validate], and print the problems found, which can
+/// be seen with `cargo build -vv`.
+///
+/// Problems in font data are printed rather than failing the build, since fonts such as
+/// the Hershey fonts have a few long-standing quirks which are drawn as they always were.
+pub fn check_font<'a>(
+    name: &str,
+    chars: impl IntoIterator<Item = char>,
+    lookup: impl Fn(char) -> Option<Glyph<'a>>,
+    metrics: &FontMetrics,
+) -> FontReport {
+    let report = validate(chars, lookup, metrics);
+    for line in report.to_string().lines() {
+        eprintln!("{}: {}", name, line);
+    }
+    report
+}
+
+/// Get the Rust expression for some font metrics.
+pub fn metrics_to_rust(metrics: &FontMetrics) -> String {
+    format!(
+        "FontMetrics {{ baseline: {}, cap_height: {}, x_height: {}, ascender: {}, descender: {}, average_advance: {} }}",
+        metrics.baseline,
+        metrics.cap_height,
+        metrics.x_height,
+        metrics.ascender,
+        metrics.descender,
+        metrics.average_advance
+    )
+}
+
+/// Get the Rust expression for a glyph, adding its strokes to `strokes`.
+///
+/// The generated code expects `Glyph`, `Anchor`, and `AnchorKind` to be in scope.
+pub fn glyph_to_rust(glyph: &Glyph, strokes: &mut StrokeData) -> String {
+    let points: Vec<_> = glyph.strokes.iter().collect();
+    let anchors: Vec<String> = glyph
+        .anchors
+        .iter()
+        .map(|a| {
+            format!(
+                "Anchor {{ kind: AnchorKind::{:?}, x: {}, y: {} }}",
+                a.kind, a.x, a.y
+            )
+        })
+        .collect();
+
+    format!(
+        "Glyph {{ left: {}, right: {}, strokes: {}, anchors: &[{}] }}",
+        glyph.left,
+        glyph.right,
+        strokes.push(&points),
+        anchors.join(", ")
+    )
+}
+
+/// Get the Rust code for a static array of rendered points, so that fixed text (such
+/// as the labels of a menu) can be drawn in a build script rather than at run time.
+///
+/// The generated code expects `Point` to be in scope.
+///
+/// ```
+/// use vector_text_build::points_to_rust;
+/// use vector_text_core::Point;
+///
+/// let points = [Point { x: 0, y: 0, pen: false }, Point { x: 4, y: -2, pen: true }];
+/// assert_eq!(
+///     points_to_rust("MENU", &points),
+///     "static MENU: [Point; 2] = [\n    Point { x: 0, y: 0, pen: false },\n    Point { x: 4, y: -2, pen: true },\n];\n"
+/// );
+/// ```
+///
+/// In the build script of a crate with `vector-text` as a build dependency, render the
+/// text (with any of its fonts) and write it to a file in `OUT_DIR`:
+///
+/// ```no_run
+/// use std::{env, fs, path::Path};
+///
+/// use vector_text_build::points_to_rust;
+/// use vector_text_core::{Font, MinimalFont};
+///
+/// let points = MinimalFont.render_text("MENU");
+/// let path = Path::new(&env::var("OUT_DIR").unwrap()).join("labels.rs");
+/// fs::write(path, points_to_rust("MENU", &points)).unwrap();
+/// ```
+///
+/// Then `include!(concat!(env!("OUT_DIR"), "/labels.rs"))` where it is drawn, with
+/// `Point` in scope, to get the points without rendering them or allocating.
+///
+/// # Panics
+///
+/// Panics if `name` isn't a Rust identifier.
+pub fn points_to_rust(name: &str, points: &[Point]) -> String {
+    assert!(is_identifier(name), "{:?} is not a Rust identifier", name);
+
+    let mut out = format!("static {}: [Point; {}] = [\n", name, points.len());
+    for point in points {
+        out.push_str(&format!(
+            "    Point {{ x: {}, y: {}, pen: {} }},\n",
+            point.x, point.y, point.pen
+        ));
+    }
+    out.push_str("];\n");
+    out
+}
+
+/// Check whether a name can be used as an identifier in Rust code, without being a
+/// keyword.
+fn is_identifier(name: &str) -> bool {
+    const KEYWORDS: &[&str] = &[
+        "Self", "abstract", "as", "async", "await", "become", "box", "break", "const", "continue",
+        "crate", "do", "dyn", "else", "enum", "extern", "false", "final", "fn", "for", "gen", "if",
+        "impl", "in", "let", "loop", "macro", "match", "mod", "move", "mut", "override", "priv",
+        "pub", "ref", "return", "self", "static", "struct", "super", "trait", "true", "try",
+        "type", "typeof", "unsafe", "unsized", "use", "virtual", "where", "while", "yield",
+    ];
+
+    let mut chars = name.chars();
+    let Some(first) = chars.next() else {
+        return false;
+    };
+
+    (first.is_alphabetic() || first == '_')
+        && chars.all(|c| c.is_alphanumeric() || c == '_')
+        && name != "_"
+        && !KEYWORDS.contains(&name)
+}
@@ -0,0 +1,132 @@
+use std::{collections::HashMap, env, fs, path::Path};
+
+use vector_text_core::{PackedPoint, Strokes};
+
+/// Stroke data of every glyph, written to a binary file which the generated code
+/// includes, since that compiles much faster than the equivalent Rust code.
+///
+/// With the `compress` feature of the crate being built, the points are delta-encoded,
+/// and with its `progmem` feature, they are put in AVR program memory and read with
+/// `ProgmemBytes`.
+pub struct StrokeData {
+    bytes: Vec<u8>,
+    /// Expression for each run of bytes already in the data
+    interned: HashMap<Vec<u8>, String>,
+    /// Delta-encode the points, with the `compress` feature
+    compress: bool,
+    /// Keep the points in program memory, with the `progmem` feature
+    progmem: bool,
+}
+
+impl StrokeData {
+    /// Create empty stroke data, compressed if the crate being built has its `compress`
+    /// feature enabled, and in program memory if it has its `progmem` feature enabled.
+    pub fn new() -> Self {
+        Self {
+            bytes: Vec::new(),
+            interned: HashMap::new(),
+            compress: env::var_os("CARGO_FEATURE_COMPRESS").is_some(),
+            progmem: env::var_os("CARGO_FEATURE_PROGMEM").is_some(),
+        }
+    }
+
+    /// Append the strokes of a glyph, returning the Rust expression for them.
+    ///
+    /// Glyphs with identical strokes (which are common, such as letters shared between
+    /// fonts) share the same data.
+    pub fn push(&mut self, strokes: &[PackedPoint]) -> String {
+        let bytes = if self.compress {
+            Strokes::encode_delta(strokes)
+        } else {
+            strokes
+                .iter()
+                .flat_map(|p| [p.x as u8, p.y as u8, p.pen as u8])
+                .collect()
+        };
+
+        if let Some(expr) = self.interned.get(&bytes) {
+            return expr.clone();
+        }
+
+        let start = self.bytes.len();
+        self.bytes.extend(&bytes);
+
+        let expr = if self.compress {
+            format!("strokes({}, {})", start, bytes.len())
+        } else {
+            format!("strokes({}, {})", start / PackedPoint::SIZE, strokes.len())
+        };
+
+        self.interned.insert(bytes, expr.clone());
+        expr
+    }
+
+    /// Generate the Rust code which includes the binary file, and the `strokes` function
+    /// used to take slices of it.
+    ///
+    /// These are marked `#[allow(dead_code)]`, since a backend may not have any glyphs to
+    /// compile in. In program memory, the data is put in the `.progmem.data` linker
+    /// section, which [place_statics](crate::place_statics) leaves it in.
+    pub fn to_rust(&self, file: &str) -> String {
+        // Delta-encoded points vary in length, so are sliced by byte instead of by point
+        let (unit, start, len) = match self.compress {
+            true => ("byte", "start", "len"),
+            false => (
+                "point",
+                "start * PackedPoint::SIZE",
+                "len * PackedPoint::SIZE",
+            ),
+        };
+
+        let (section, body) = if self.progmem {
+            let variant = match self.compress {
+                true => "ProgmemDelta",
+                false => "ProgmemPoints",
+            };
+            (
+                "#[unsafe(link_section = \".progmem.data\")]\n",
+                format!(
+                    "// SAFETY: the data is a static in program memory, which is never written to\n    \
+                     let start = unsafe {{ (&raw const STROKE_DATA as *const u8).add({start}) }};\n    \
+                     Strokes::{variant}(unsafe {{ ProgmemBytes::new(start, {len}) }})"
+                ),
+            )
+        } else {
+            let strokes = match self.compress {
+                true => "Strokes::Delta(rest.split_at(len).0)".to_string(),
+                false => format!(
+                    "Strokes::Points(PackedPoint::slice_from_bytes(rest.split_at({len}).0))"
+                ),
+            };
+            (
+                "",
+                format!("let (_, rest) = STROKE_DATA.split_at({start});\n    {strokes}"),
+            )
+        };
+
+        format!(
+            "#[allow(dead_code)]\n\
+             {section}\
+             static STROKE_DATA: [u8; {}] = *include_bytes!(concat!(env!(\"OUT_DIR\"), \"/{}\"));\n\n\
+             /// Get `len` {unit}s of the stroke data, starting with {unit} `start`\n\
+             #[allow(dead_code)]\n\
+             const fn strokes(start: usize, len: usize) -> vector_text_core::Strokes<'static> {{\n    \
+             use vector_text_core::*;\n\n    \
+             {body}\n\
+             }}\n",
+            self.bytes.len(),
+            file
+        )
+    }
+
+    /// Write the binary file to the `out_dir`.
+    pub fn write(&self, out_dir: &Path, file: &str) {
+        fs::write(out_dir.join(file), &self.bytes).unwrap();
+    }
+}
+
+impl Default for StrokeData {
+    fn default() -> Self {
+        Self::new()
+    }
+}
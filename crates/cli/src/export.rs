@@ -0,0 +1,206 @@
+//! Writers for each output format.
+//!
+//! SVG has y increasing downward, and the other formats have y increasing upward, so
+//! each writer expects points rendered with the matching [YAxis](vector_text::YAxis).
+//! Points are grouped by the pen they are drawn with, as by
+//! [Layout::by_pen](vector_text::Layout::by_pen).
+
+use std::fmt::{self, Write as _};
+
+use vector_text::{BBox, GCodeOptions, Point, Raster, ShadedPoint, to_gcode};
+
+/// Colours of the pens in SVG and ILDA, repeating for higher pens. Pen 0 is black in
+/// SVG, but white for lasers, which can't draw black.
+const PALETTE: [(u8, u8, u8); 8] = [
+    (0, 0, 0),
+    (220, 30, 30),
+    (30, 140, 40),
+    (30, 60, 220),
+    (230, 140, 0),
+    (160, 40, 190),
+    (0, 170, 190),
+    (120, 120, 120),
+];
+
+/// Colour of a pen, as red, green, and blue
+fn colour(pen: u8) -> (u8, u8, u8) {
+    PALETTE[pen as usize % PALETTE.len()]
+}
+
+/// Smallest and largest x and y of some points, or zeros if there are none
+fn bounds(points: impl IntoIterator<Item = (i16, i16)>) -> (f32, f32, f32, f32) {
+    let bbox = points
+        .into_iter()
+        .map(BBox::at)
+        .reduce(BBox::union)
+        .unwrap_or(BBox::at((0, 0)));
+    let (min, max) = (bbox.min, bbox.max);
+    (min.0 as f32, min.1 as f32, max.0 as f32, max.1 as f32)
+}
+
+/// Write an SVG document with a path for each pen, `scale` user units per glyph unit.
+pub fn svg(pens: &[(u8, Vec<Point>)], scale: f32) -> String {
+    let margin = 2.0 * scale;
+    let (left, top, right, bottom) = bounds(
+        pens.iter()
+            .flat_map(|(_, points)| points.iter().map(|p| (p.x, p.y))),
+    );
+
+    let mut paths = String::new();
+    for (pen, points) in pens {
+        let mut data = String::new();
+        for point in points {
+            let command = if point.pen { 'L' } else { 'M' };
+            let (x, y) = (
+                (point.x as f32 - left) * scale + margin,
+                (point.y as f32 - top) * scale + margin,
+            );
+            write!(data, "{}{} {} ", command, x, y).unwrap();
+        }
+
+        let (r, g, b) = colour(*pen);
+        writeln!(
+            paths,
+            "<path d=\"{}\" fill=\"none\" stroke=\"#{:02x}{:02x}{:02x}\" stroke-width=\"{}\" stroke-linecap=\"round\" stroke-linejoin=\"round\"/>",
+            data.trim_end(),
+            r,
+            g,
+            b,
+            scale,
+        )
+        .unwrap();
+    }
+
+    format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{w}\" height=\"{h}\" viewBox=\"0 0 {w} {h}\">\n\
+         {}</svg>\n",
+        paths,
+        w = (right - left) * scale + 2.0 * margin,
+        h = (bottom - top) * scale + 2.0 * margin,
+    )
+}
+
+/// Write G-code for a pen plotter, one command per line.
+pub fn gcode(points: &[Point], options: &GCodeOptions) -> String {
+    to_gcode(points, options)
+        .iter()
+        .map(|command| format!("{}\n", command))
+        .collect()
+}
+
+/// Plotter units per millimetre in HPGL
+const HPGL_UNITS: f32 = 40.0;
+
+/// Write HPGL for a pen plotter, `scale` millimetres per glyph unit. HPGL numbers pens
+/// from 1, so pen 0 is selected with `SP1`.
+pub fn hpgl(pens: &[(u8, Vec<Point>)], scale: f32) -> String {
+    let mut out = String::from("IN;");
+
+    for (pen, points) in pens {
+        write!(out, "SP{};", *pen as u16 + 1).unwrap();
+        for point in points {
+            let command = if point.pen { "PD" } else { "PU" };
+            let (x, y) = (
+                (point.x as f32 * scale * HPGL_UNITS).round() as i32,
+                (point.y as f32 * scale * HPGL_UNITS).round() as i32,
+            );
+            write!(out, "{}{},{};", command, x, y).unwrap();
+        }
+    }
+
+    out.push_str("PU;SP0;\n");
+    out
+}
+
+/// Write an ILDA laser show file, with the text as a single 2D frame scaled to fill the
+/// projector's range.
+///
+/// Points are written in true colour, in the colour of their pen dimmed to their
+/// intensity, and blanked points are marked as such too. A frame holds at most 65535
+/// points, so more than that is an error rather than a truncated file.
+pub fn ilda(pens: &[(u8, Vec<ShadedPoint>)]) -> Result<Vec<u8>, TooManyPoints> {
+    let points = || {
+        pens.iter()
+            .flat_map(|(pen, points)| points.iter().map(|p| (*pen, p)))
+    };
+    let (left, top, right, bottom) = bounds(points().map(|(_, p)| (p.x, p.y)));
+    let (cx, cy) = ((left + right) / 2.0, (top + bottom) / 2.0);
+    let extent = (right - left).max(bottom - top).max(1.0);
+    let scale = 65534.0 / extent;
+
+    let len = points().count();
+    let records = u16::try_from(len).map_err(|_| TooManyPoints(len))?;
+    let mut out = ilda_header(records);
+    for (i, (pen, point)) in points().enumerate() {
+        let x = ((point.x as f32 - cx) * scale).round() as i16;
+        let y = ((point.y as f32 - cy) * scale).round() as i16;
+
+        let mut status = 0;
+        if i == len - 1 {
+            status |= 0x80;
+        }
+        if point.is_blanked() {
+            status |= 0x40;
+        }
+
+        let (r, g, b) = match colour(pen) {
+            (0, 0, 0) => (u8::MAX, u8::MAX, u8::MAX),
+            colour => colour,
+        };
+        let dim = |channel: u8| (channel as u16 * point.intensity as u16 / 255) as u8;
+
+        out.extend(x.to_be_bytes());
+        out.extend(y.to_be_bytes());
+        out.extend([status, dim(b), dim(g), dim(r)]);
+    }
+
+    // A header with no points ends the file
+    out.extend(ilda_header(0));
+    Ok(out)
+}
+
+/// Error for text with more points than fit in an ILDA frame
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TooManyPoints(pub usize);
+
+impl fmt::Display for TooManyPoints {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} points is too many for an ILDA frame, which holds at most {}",
+            self.0,
+            u16::MAX
+        )
+    }
+}
+
+impl std::error::Error for TooManyPoints {}
+
+/// Header of an ILDA section of 2D true-colour points
+fn ilda_header(records: u16) -> Vec<u8> {
+    let mut header = Vec::with_capacity(32);
+    header.extend(b"ILDA");
+    header.extend([0, 0, 0, 5]);
+    header.extend(b"vtext\0\0\0");
+    header.extend(b"\0\0\0\0\0\0\0\0");
+    header.extend(records.to_be_bytes());
+    header.extend(0u16.to_be_bytes()); // frame number
+    header.extend(1u16.to_be_bytes()); // total frames
+    header.extend([0, 0]); // projector, reserved
+    header
+}
+
+/// Draw the lines as ASCII art, `#` for each character they pass through and `.` for the
+/// rest, `scale` characters per glyph unit.
+pub fn ascii(points: &[Point], scale: f32) -> String {
+    let scale = |value: i16| (value as f32 * scale).round() as i16;
+    let scaled: Vec<Point> = points
+        .iter()
+        .map(|point| Point {
+            x: scale(point.x),
+            y: scale(point.y),
+            pen: point.pen,
+        })
+        .collect();
+    Raster::from_points(&scaled, 1).to_string()
+}
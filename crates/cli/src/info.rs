@@ -0,0 +1,88 @@
+//! A report on the data of a font, for checking new or converted fonts.
+
+use std::fmt::Write as _;
+
+use vector_text::{Font, PathStats, RenderOptions, VectorFont, render_text_with_options, validate};
+
+/// Describe a font: how many glyphs it has and which characters they are for, its
+/// metrics, the size and strokes of each glyph (or of each character of `text`, if
+/// given), and any problems found in its data.
+pub fn info(font: VectorFont<'_>, text: Option<&str>) -> String {
+    let mut out = String::new();
+    let metrics = Font::metrics(&font);
+    let mut covered: Vec<char> = font.coverage().flatten().collect();
+    covered.sort_unstable();
+    covered.dedup();
+
+    writeln!(out, "font: {}", font).unwrap();
+    writeln!(out, "glyphs: {}", covered.len()).unwrap();
+    writeln!(out, "coverage:").unwrap();
+    // Runs of consecutive codepoints, in order
+    for run in covered.chunk_by(|&a, &b| b as u32 == a as u32 + 1) {
+        let (start, end) = (run[0] as u32, run[run.len() - 1] as u32);
+        match start == end {
+            true => writeln!(out, "  U+{:04X}", start).unwrap(),
+            false => writeln!(out, "  U+{:04X}..U+{:04X}", start, end).unwrap(),
+        }
+    }
+
+    writeln!(out, "metrics:").unwrap();
+    writeln!(out, "  baseline: {}", metrics.baseline).unwrap();
+    writeln!(out, "  cap height: {}", metrics.cap_height).unwrap();
+    writeln!(out, "  x height: {}", metrics.x_height).unwrap();
+    writeln!(out, "  ascender: {}", metrics.ascender).unwrap();
+    writeln!(out, "  descender: {}", metrics.descender).unwrap();
+    writeln!(out, "  average advance: {}", metrics.average_advance).unwrap();
+    writeln!(out, "  line height: {}", metrics.line_height()).unwrap();
+
+    let chars: Vec<char> = match text {
+        Some(text) => text.chars().filter(|c| !c.is_control()).collect(),
+        None => covered,
+    };
+
+    writeln!(out, "glyph stats:").unwrap();
+    writeln!(
+        out,
+        "  {:<8} {:>5} {:>5} {:>7} {:>6} {:>7} {:>8}",
+        "char", "left", "right", "advance", "points", "strokes", "length"
+    )
+    .unwrap();
+    for &c in &chars {
+        let Some(glyph) = Font::glyph(&font, c) else {
+            writeln!(out, "  U+{:04X}   (missing)", c as u32).unwrap();
+            continue;
+        };
+
+        let mut buffer = [0; 4];
+        let points =
+            render_text_with_options(c.encode_utf8(&mut buffer), font, &RenderOptions::default())
+                .unwrap_or_default();
+        let stats = PathStats::from_points(&points);
+
+        writeln!(
+            out,
+            "  U+{:04X}   {:>5} {:>5} {:>7} {:>6} {:>7} {:>8.1}",
+            c as u32,
+            glyph.left,
+            glyph.right,
+            glyph.advance(),
+            stats.points,
+            stats.strokes,
+            stats.pen_down_distance
+        )
+        .unwrap();
+    }
+
+    let report = validate(chars, |c| Font::glyph(&font, c), &metrics);
+    match report.is_ok() {
+        true => writeln!(out, "problems: none").unwrap(),
+        false => {
+            writeln!(out, "problems:").unwrap();
+            for line in report.to_string().lines() {
+                writeln!(out, "  {}", line).unwrap();
+            }
+        }
+    }
+
+    out
+}
@@ -0,0 +1,239 @@
+//! `vtext` renders text with the `vector-text` fonts to files for plotters, laser
+//! projectors, and other vector output, so that the library can be used from shell
+//! scripts and CAM pipelines.
+//!
+//! ```text
+//! vtext "Hello" --font hershey-romans --format gcode -o hello.gcode
+//! ```
+//!
+//! It can also show what is in a font, to check new font data:
+//!
+//! ```text
+//! vtext --info --font borland-bold
+//! vtext "g" --font borland-bold --format ascii
+//! ```
+
+use std::fs;
+use std::io::{self, Write};
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use clap::{Parser, ValueEnum};
+use vector_text::{
+    Font, GCodeOptions, RenderOptions, Scale, TravelOptions, VectorFont, YAxis, optimize_travel,
+    render_text_with_options, shade, specimen, waterfall,
+};
+
+mod export;
+mod info;
+
+/// Render text with vector fonts to SVG, G-code, HPGL, ILDA, or ASCII art.
+#[derive(Parser)]
+#[command(version)]
+struct Args {
+    /// Text to render (line breaks start new lines)
+    #[arg(required_unless_present_any = ["list_fonts", "specimen", "info"])]
+    text: Option<String>,
+
+    /// Font to render with, such as `hershey-romans` or `borland-litt`
+    #[arg(short, long, default_value = "hershey-romans")]
+    font: VectorFont<'static>,
+
+    /// Format to write
+    #[arg(long, value_enum, default_value_t = Format::Svg)]
+    format: Format,
+
+    /// File to write to, instead of standard output
+    #[arg(short, long)]
+    output: Option<PathBuf>,
+
+    /// Size of a font unit: SVG user units, millimetres for G-code and HPGL, or
+    /// characters for ASCII art
+    #[arg(short, long, default_value_t = 1.0)]
+    scale: f32,
+
+    /// Height of capital letters, in SVG user units or millimetres, instead of the size
+    /// of a font unit
+    #[arg(long, conflicts_with = "scale")]
+    cap_height: Option<f32>,
+
+    /// Reorder strokes to reduce the distance moved with the pen up
+    #[arg(long)]
+    optimize: bool,
+
+    /// Height of the pen while moving, for G-code
+    #[arg(long, default_value_t = 5.0, allow_negative_numbers = true)]
+    pen_up: f32,
+
+    /// Height of the pen while drawing, for G-code
+    #[arg(long, default_value_t = 0.0, allow_negative_numbers = true)]
+    pen_down: f32,
+
+    /// Speed to draw at in millimetres per minute, for G-code
+    #[arg(long)]
+    feed_rate: Option<f32>,
+
+    /// Pen to draw with, counting from 0: its number (plus one) for HPGL, or its colour
+    /// for SVG and ILDA
+    #[arg(long, default_value_t = 0)]
+    pen: u8,
+
+    /// Brightness to draw at, from 1 to 255, for ILDA
+    #[arg(long, default_value_t = 255, value_parser = clap::value_parser!(u8).range(1..))]
+    intensity: u8,
+
+    /// Fail if the font can't draw a character, instead of leaving it out
+    #[arg(long)]
+    strict: bool,
+
+    /// List the names of the available fonts, and exit
+    #[arg(long)]
+    list_fonts: bool,
+
+    /// Draw every glyph of the font in a grid, labelled with its codepoint, instead of
+    /// the text
+    #[arg(long, conflicts_with = "text")]
+    specimen: bool,
+
+    /// Draw the text at each of these sizes (relative to the font's own, separated by
+    /// commas) one above another, beside a smoothed copy, to choose a size to plot at
+    #[arg(long, value_delimiter = ',', conflicts_with = "specimen")]
+    waterfall: Vec<f32>,
+
+    /// Print the font's coverage, metrics, and the size and strokes of each glyph (or of
+    /// each character of the text), and any problems found in its data, and exit
+    #[arg(long, conflicts_with_all = ["specimen", "waterfall"])]
+    info: bool,
+}
+
+#[derive(Copy, Clone, ValueEnum)]
+enum Format {
+    Svg,
+    Gcode,
+    Hpgl,
+    Ild,
+    Ascii,
+}
+
+impl Format {
+    /// Which way y increases in the format
+    fn y_axis(self) -> YAxis {
+        match self {
+            Self::Svg | Self::Ascii => YAxis::Down,
+            Self::Gcode | Self::Hpgl | Self::Ild => YAxis::Up,
+        }
+    }
+}
+
+fn main() -> ExitCode {
+    let args = Args::parse();
+
+    if args.list_fonts {
+        let mut stdout = io::stdout().lock();
+        for font in VectorFont::iter() {
+            // Stop quietly if the reader goes away, such as when piped to `head`
+            if writeln!(stdout, "{}", font).is_err() {
+                break;
+            }
+        }
+        return ExitCode::SUCCESS;
+    }
+
+    if args.info {
+        let report = info::info(args.font, args.text.as_deref());
+        // As for the font list, stop quietly if the reader goes away
+        let _ = io::stdout().write_all(report.as_bytes());
+        return ExitCode::SUCCESS;
+    }
+
+    let text = args.text.unwrap_or_default();
+    let options = RenderOptions {
+        y_axis: args.format.y_axis(),
+        ..if args.strict {
+            RenderOptions::strict()
+        } else {
+            RenderOptions::default()
+        }
+    };
+
+    let sheet = if args.specimen {
+        Some(specimen(args.font, 16))
+    } else if !args.waterfall.is_empty() {
+        let scales: Vec<Scale> = args
+            .waterfall
+            .iter()
+            .map(|&size| Scale::from_q8_8((size * 256.0).round() as u16))
+            .collect();
+        Some(waterfall(&text, args.font, &scales, 2))
+    } else {
+        None
+    };
+
+    let rendered = match sheet {
+        Some(sheet) => sheet.map(|mut points| {
+            options.y_axis.apply(&mut points);
+            points
+        }),
+        None => render_text_with_options(&text, args.font, &options),
+    };
+
+    let mut points = match rendered {
+        Ok(points) => points,
+        Err(e) => {
+            eprintln!("vtext: {}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    if args.optimize {
+        let options = TravelOptions {
+            two_opt: true,
+            reverse: true,
+        };
+        points = optimize_travel(&points, &options);
+    }
+
+    let scale = match args.cap_height {
+        Some(height) => Font::metrics(&args.font).scale_for_cap_height(height),
+        None => args.scale,
+    };
+
+    let pens = [(args.pen, points)];
+    let output = match args.format {
+        Format::Svg => export::svg(&pens, scale).into_bytes(),
+        Format::Gcode => {
+            let options = GCodeOptions {
+                scale,
+                pen_up: args.pen_up,
+                pen_down: args.pen_down,
+                feed_rate: args.feed_rate,
+                y_axis: YAxis::Up,
+            };
+            export::gcode(&pens[0].1, &options).into_bytes()
+        }
+        Format::Hpgl => export::hpgl(&pens, scale).into_bytes(),
+        Format::Ild => {
+            let shaded = pens.map(|(pen, points)| (pen, shade(&points, |_| args.intensity)));
+            match export::ilda(&shaded) {
+                Ok(output) => output,
+                Err(e) => {
+                    eprintln!("vtext: {}", e);
+                    return ExitCode::FAILURE;
+                }
+            }
+        }
+        Format::Ascii => export::ascii(&pens[0].1, scale).into_bytes(),
+    };
+
+    let result = match &args.output {
+        Some(path) => fs::write(path, output),
+        None => io::stdout().write_all(&output),
+    };
+
+    if let Err(e) = result {
+        eprintln!("vtext: {}", e);
+        return ExitCode::FAILURE;
+    }
+
+    ExitCode::SUCCESS
+}
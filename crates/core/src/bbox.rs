@@ -0,0 +1,96 @@
+use crate::Point;
+
+/// A rectangle around some points, from its smallest to its largest x and y, inclusive.
+///
+/// ```
+/// # #[cfg(feature = "alloc")] {
+/// use vector_text_core::{BBox, Font, MinimalFont};
+///
+/// let text = BBox::of(&MinimalFont.render_text("HELLO")).unwrap();
+/// let page = BBox { min: (-10, -20), max: (200, 40) };
+///
+/// // The text fits on the page, with room for a margin around it
+/// assert_eq!(page.union(text.expand(5)), page);
+/// assert!(text.intersects(page) && !text.contains((-1, 0)));
+/// # }
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct BBox {
+    /// Smallest x and y, at the top left corner with y increasing downward
+    pub min: (i16, i16),
+    /// Largest x and y, at the bottom right corner with y increasing downward
+    pub max: (i16, i16),
+}
+
+impl BBox {
+    /// Measure the box around some points, or `None` if there are none.
+    ///
+    /// Moves are counted along with lines, since the pen ends up at them too.
+    pub fn of(points: &[Point]) -> Option<Self> {
+        let (first, rest) = points.split_first()?;
+        let first = Self::at((first.x, first.y));
+        Some(rest.iter().fold(first, |bbox, point| {
+            bbox.union(Self::at((point.x, point.y)))
+        }))
+    }
+
+    /// An empty box at a single point.
+    pub fn at(point: (i16, i16)) -> Self {
+        Self {
+            min: point,
+            max: point,
+        }
+    }
+
+    /// Distance from the left edge to the right.
+    pub fn width(&self) -> i32 {
+        self.max.0 as i32 - self.min.0 as i32
+    }
+
+    /// Distance from the top edge to the bottom.
+    pub fn height(&self) -> i32 {
+        self.max.1 as i32 - self.min.1 as i32
+    }
+
+    /// The smallest box around both this box and `other`.
+    pub fn union(self, other: Self) -> Self {
+        Self {
+            min: (self.min.0.min(other.min.0), self.min.1.min(other.min.1)),
+            max: (self.max.0.max(other.max.0), self.max.1.max(other.max.1)),
+        }
+    }
+
+    /// Is the point inside the box, or on its edge?
+    pub fn contains(&self, (x, y): (i16, i16)) -> bool {
+        (self.min.0..=self.max.0).contains(&x) && (self.min.1..=self.max.1).contains(&y)
+    }
+
+    /// Do this box and `other` overlap, or touch?
+    pub fn intersects(&self, other: Self) -> bool {
+        self.min.0 <= other.max.0
+            && other.min.0 <= self.max.0
+            && self.min.1 <= other.max.1
+            && other.min.1 <= self.max.1
+    }
+
+    /// Grow the box by `margin` on every side, or shrink it if `margin` is negative (to
+    /// no less than a point at its centre).
+    pub fn expand(self, margin: i16) -> Self {
+        let grow = |min: i16, max: i16| {
+            let (min, max) = (min as i32 - margin as i32, max as i32 + margin as i32);
+            let centre = (min + max).div_euclid(2);
+            let clamp = |value: i32| value.clamp(i16::MIN as i32, i16::MAX as i32) as i16;
+            match min <= max {
+                true => (clamp(min), clamp(max)),
+                false => (clamp(centre), clamp(centre)),
+            }
+        };
+        let (left, right) = grow(self.min.0, self.max.0);
+        let (top, bottom) = grow(self.min.1, self.max.1);
+        Self {
+            min: (left, top),
+            max: (right, bottom),
+        }
+    }
+}
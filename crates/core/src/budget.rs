@@ -0,0 +1,77 @@
+use alloc::vec::Vec;
+
+use crate::path::{simplify, split_strokes};
+use crate::{BBox, Point};
+
+/// An error returned by [fit_to_budget] when the points can't be cut down far enough.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct OverBudget {
+    /// Fewest points the text can be simplified to, which is about two for each stroke
+    pub fewest: usize,
+}
+
+impl core::fmt::Display for OverBudget {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "text needs at least {} points", self.fewest)
+    }
+}
+
+impl core::error::Error for OverBudget {}
+
+/// Cut rendered text down to at most `max_points` points, for devices which can only draw
+/// so many points each frame, such as laser projectors and oscilloscopes refreshed at a
+/// fixed rate.
+///
+/// Text already within the budget is returned as it is. Otherwise, moves which draw
+/// nothing are left out, and the strokes are [simplified](simplify) with the smallest
+/// tolerance which brings them within it, so detail is lost evenly across the text. If
+/// even the ends of the strokes are too many, [OverBudget] says how many there are.
+///
+/// ```
+/// use vector_text_core::{fit_to_budget, OverBudget, Point};
+///
+/// let point = |x, y, pen| Point { x, y, pen };
+/// let points = [
+///     point(0, 0, false),
+///     point(5, 1, true),
+///     point(10, 0, true),
+///     point(10, 10, true),
+/// ];
+///
+/// assert_eq!(fit_to_budget(&points, 3).unwrap().len(), 3);
+/// assert_eq!(fit_to_budget(&points, 1).err(), Some(OverBudget { fewest: 2 }));
+/// ```
+pub fn fit_to_budget(points: &[Point], max_points: usize) -> Result<Vec<Point>, OverBudget> {
+    if points.len() <= max_points {
+        return Ok(points.to_vec());
+    }
+
+    let points: Vec<Point> = split_strokes(points).flatten().copied().collect();
+    if points.len() <= max_points {
+        return Ok(points);
+    }
+
+    // No point is further than this from the line between the ends of its stroke
+    let bbox = BBox::of(&points).unwrap_or(BBox::at((0, 0)));
+    let size = bbox.width().max(bbox.height()) as f32 * 2.0;
+
+    let mut best = simplify(&points, size);
+    if best.len() > max_points {
+        return Err(OverBudget { fewest: best.len() });
+    }
+
+    // Search between a tolerance which is within the budget and one which isn't
+    let (mut low, mut high) = (0.0, size);
+    for _ in 0..16 {
+        let middle = (low + high) / 2.0;
+        let simplified = simplify(&points, middle);
+        if simplified.len() <= max_points {
+            (high, best) = (middle, simplified);
+        } else {
+            low = middle;
+        }
+    }
+
+    Ok(best)
+}
@@ -0,0 +1,49 @@
+use core::iter::Peekable;
+use core::ops::RangeInclusive;
+
+/// An iterator over the ranges of consecutive characters a font has glyphs for,
+/// in increasing order.
+///
+/// See [crate::Renderer::coverage].
+pub struct Coverage<I: Iterator<Item = char>, F> {
+    candidates: Peekable<I>,
+    supported: F,
+}
+
+impl<I, F> Coverage<I, F>
+where
+    I: Iterator<Item = char>,
+    F: FnMut(char) -> bool,
+{
+    /// Find the coverage of a font by checking each of the `candidates`, which must be
+    /// in increasing order, with `supported`.
+    pub fn new(candidates: I, supported: F) -> Self {
+        Self {
+            candidates: candidates.peekable(),
+            supported,
+        }
+    }
+}
+
+impl<I, F> Iterator for Coverage<I, F>
+where
+    I: Iterator<Item = char>,
+    F: FnMut(char) -> bool,
+{
+    type Item = RangeInclusive<char>;
+
+    fn next(&mut self) -> Option<RangeInclusive<char>> {
+        let supported = &mut self.supported;
+        let start = self.candidates.by_ref().find(|&c| supported(c))?;
+        let mut end = start;
+
+        while let Some(c) = self
+            .candidates
+            .next_if(|&c| c as u32 == end as u32 + 1 && supported(c))
+        {
+            end = c;
+        }
+
+        Some(start..=end)
+    }
+}
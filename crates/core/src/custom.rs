@@ -0,0 +1,95 @@
+use alloc::collections::BTreeMap;
+use alloc::vec::Vec;
+
+use crate::{Anchor, Font, FontMetrics, Glyph, LoadedGlyph, PackedPoint};
+
+/// A font defined in code, glyph by glyph.
+///
+/// This is useful for small bespoke stroke fonts, such as a logo alphabet:
+///
+/// ```
+/// use vector_text_core::{CustomFont, Font, FontMetrics, PackedPoint};
+///
+/// let mut font = CustomFont::new(FontMetrics {
+///     baseline: 10,
+///     cap_height: 10,
+///     x_height: 6,
+///     ascender: 10,
+///     descender: 3,
+///     average_advance: 8,
+/// });
+///
+/// font.add_glyph('V', -4, 4, &[
+///     PackedPoint { x: -3, y: 0, pen: false },
+///     PackedPoint { x: 0, y: 10, pen: true },
+///     PackedPoint { x: 3, y: 0, pen: true },
+/// ])
+/// .add_glyph(' ', -4, 4, &[]);
+///
+/// let points = font.render_text("V V");
+/// assert_eq!(points.len(), 6);
+/// ```
+#[derive(Debug, Clone)]
+pub struct CustomFont {
+    glyphs: BTreeMap<char, LoadedGlyph>,
+    metrics: FontMetrics,
+}
+
+impl CustomFont {
+    /// Create an empty font with the given metrics.
+    pub fn new(metrics: FontMetrics) -> Self {
+        Self {
+            glyphs: BTreeMap::new(),
+            metrics,
+        }
+    }
+
+    /// Add the glyph used to draw a character, replacing any glyph it already had.
+    ///
+    /// `left` and `right` are the horizontal boundaries of the glyph, and `strokes`
+    /// are the points which make it up, in the same coordinates.
+    pub fn add_glyph(
+        &mut self,
+        character: char,
+        left: i8,
+        right: i8,
+        strokes: &[PackedPoint],
+    ) -> &mut Self {
+        self.glyphs.insert(
+            character,
+            LoadedGlyph {
+                left,
+                right,
+                strokes: strokes.to_vec(),
+                anchors: Vec::new(),
+            },
+        );
+        self
+    }
+
+    /// Add an anchor to the glyph for a character, used to attach combining marks to it.
+    ///
+    /// Does nothing if the character has no glyph yet.
+    pub fn add_anchor(&mut self, character: char, anchor: Anchor) -> &mut Self {
+        if let Some(glyph) = self.glyphs.get_mut(&character) {
+            glyph.anchors.retain(|a| a.kind != anchor.kind);
+            glyph.anchors.push(anchor);
+        }
+        self
+    }
+
+    /// Iterate over the characters this font has glyphs for, in increasing order.
+    pub fn chars(&self) -> impl Iterator<Item = char> + '_ {
+        self.glyphs.keys().copied()
+    }
+}
+
+impl Font for CustomFont {
+    fn glyph(&self, character: char) -> Option<Glyph<'_>> {
+        self.glyphs.get(&character).map(LoadedGlyph::as_glyph)
+    }
+
+    fn metrics(&self) -> FontMetrics {
+        self.metrics
+    }
+}
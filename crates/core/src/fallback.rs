@@ -0,0 +1,243 @@
+/// Find the base letter of a character with diacritics, e.g. `'a'` for `'á'`.
+///
+/// Returns `None` if the character has no diacritics to strip.
+pub fn strip_diacritics(character: char) -> Option<char> {
+    DECOMPOSITIONS
+        .binary_search_by_key(&character, |&(composed, _)| composed)
+        .ok()
+        .map(|i| DECOMPOSITIONS[i].1)
+}
+
+/// Standalone (spacing) characters which look like a combining mark, most specific first,
+/// for fonts which have no glyph for the mark itself.
+pub(crate) fn spacing_forms(mark: char) -> &'static [char] {
+    match mark {
+        '\u{0300}' => &['`'],
+        '\u{0301}' => &['\u{00B4}', '\''],
+        '\u{0302}' => &['^'],
+        '\u{0303}' => &['~'],
+        '\u{0304}' => &['\u{00AF}'],
+        '\u{0306}' => &['\u{02D8}'],
+        '\u{0307}' => &['\u{02D9}'],
+        '\u{0308}' => &['\u{00A8}', '"'],
+        '\u{030A}' => &['\u{02DA}', '\u{00B0}'],
+        '\u{030B}' => &['\u{02DD}', '"'],
+        '\u{030C}' => &['\u{02C7}'],
+        '\u{0327}' => &['\u{00B8}', ','],
+        '\u{0328}' => &['\u{02DB}'],
+        _ => &[],
+    }
+}
+
+/// Precomposed Latin, Greek, and Cyrillic characters with their base letters,
+/// sorted by the precomposed character.
+///
+/// Derived from the Unicode canonical decompositions, plus letters with overlaid
+/// strokes (like `Ø` and `Ł`) which Unicode doesn't decompose.
+#[rustfmt::skip]
+static DECOMPOSITIONS: [(char, char); 814] = [
+    ('\u{00C0}', 'A'), ('\u{00C1}', 'A'), ('\u{00C2}', 'A'), ('\u{00C3}', 'A'),
+    ('\u{00C4}', 'A'), ('\u{00C5}', 'A'), ('\u{00C7}', 'C'), ('\u{00C8}', 'E'),
+    ('\u{00C9}', 'E'), ('\u{00CA}', 'E'), ('\u{00CB}', 'E'), ('\u{00CC}', 'I'),
+    ('\u{00CD}', 'I'), ('\u{00CE}', 'I'), ('\u{00CF}', 'I'), ('\u{00D1}', 'N'),
+    ('\u{00D2}', 'O'), ('\u{00D3}', 'O'), ('\u{00D4}', 'O'), ('\u{00D5}', 'O'),
+    ('\u{00D6}', 'O'), ('\u{00D8}', 'O'), ('\u{00D9}', 'U'), ('\u{00DA}', 'U'),
+    ('\u{00DB}', 'U'), ('\u{00DC}', 'U'), ('\u{00DD}', 'Y'), ('\u{00E0}', 'a'),
+    ('\u{00E1}', 'a'), ('\u{00E2}', 'a'), ('\u{00E3}', 'a'), ('\u{00E4}', 'a'),
+    ('\u{00E5}', 'a'), ('\u{00E7}', 'c'), ('\u{00E8}', 'e'), ('\u{00E9}', 'e'),
+    ('\u{00EA}', 'e'), ('\u{00EB}', 'e'), ('\u{00EC}', 'i'), ('\u{00ED}', 'i'),
+    ('\u{00EE}', 'i'), ('\u{00EF}', 'i'), ('\u{00F1}', 'n'), ('\u{00F2}', 'o'),
+    ('\u{00F3}', 'o'), ('\u{00F4}', 'o'), ('\u{00F5}', 'o'), ('\u{00F6}', 'o'),
+    ('\u{00F8}', 'o'), ('\u{00F9}', 'u'), ('\u{00FA}', 'u'), ('\u{00FB}', 'u'),
+    ('\u{00FC}', 'u'), ('\u{00FD}', 'y'), ('\u{00FF}', 'y'), ('\u{0100}', 'A'),
+    ('\u{0101}', 'a'), ('\u{0102}', 'A'), ('\u{0103}', 'a'), ('\u{0104}', 'A'),
+    ('\u{0105}', 'a'), ('\u{0106}', 'C'), ('\u{0107}', 'c'), ('\u{0108}', 'C'),
+    ('\u{0109}', 'c'), ('\u{010A}', 'C'), ('\u{010B}', 'c'), ('\u{010C}', 'C'),
+    ('\u{010D}', 'c'), ('\u{010E}', 'D'), ('\u{010F}', 'd'), ('\u{0110}', 'D'),
+    ('\u{0111}', 'd'), ('\u{0112}', 'E'), ('\u{0113}', 'e'), ('\u{0114}', 'E'),
+    ('\u{0115}', 'e'), ('\u{0116}', 'E'), ('\u{0117}', 'e'), ('\u{0118}', 'E'),
+    ('\u{0119}', 'e'), ('\u{011A}', 'E'), ('\u{011B}', 'e'), ('\u{011C}', 'G'),
+    ('\u{011D}', 'g'), ('\u{011E}', 'G'), ('\u{011F}', 'g'), ('\u{0120}', 'G'),
+    ('\u{0121}', 'g'), ('\u{0122}', 'G'), ('\u{0123}', 'g'), ('\u{0124}', 'H'),
+    ('\u{0125}', 'h'), ('\u{0126}', 'H'), ('\u{0127}', 'h'), ('\u{0128}', 'I'),
+    ('\u{0129}', 'i'), ('\u{012A}', 'I'), ('\u{012B}', 'i'), ('\u{012C}', 'I'),
+    ('\u{012D}', 'i'), ('\u{012E}', 'I'), ('\u{012F}', 'i'), ('\u{0130}', 'I'),
+    ('\u{0134}', 'J'), ('\u{0135}', 'j'), ('\u{0136}', 'K'), ('\u{0137}', 'k'),
+    ('\u{0139}', 'L'), ('\u{013A}', 'l'), ('\u{013B}', 'L'), ('\u{013C}', 'l'),
+    ('\u{013D}', 'L'), ('\u{013E}', 'l'), ('\u{0141}', 'L'), ('\u{0142}', 'l'),
+    ('\u{0143}', 'N'), ('\u{0144}', 'n'), ('\u{0145}', 'N'), ('\u{0146}', 'n'),
+    ('\u{0147}', 'N'), ('\u{0148}', 'n'), ('\u{014C}', 'O'), ('\u{014D}', 'o'),
+    ('\u{014E}', 'O'), ('\u{014F}', 'o'), ('\u{0150}', 'O'), ('\u{0151}', 'o'),
+    ('\u{0154}', 'R'), ('\u{0155}', 'r'), ('\u{0156}', 'R'), ('\u{0157}', 'r'),
+    ('\u{0158}', 'R'), ('\u{0159}', 'r'), ('\u{015A}', 'S'), ('\u{015B}', 's'),
+    ('\u{015C}', 'S'), ('\u{015D}', 's'), ('\u{015E}', 'S'), ('\u{015F}', 's'),
+    ('\u{0160}', 'S'), ('\u{0161}', 's'), ('\u{0162}', 'T'), ('\u{0163}', 't'),
+    ('\u{0164}', 'T'), ('\u{0165}', 't'), ('\u{0166}', 'T'), ('\u{0167}', 't'),
+    ('\u{0168}', 'U'), ('\u{0169}', 'u'), ('\u{016A}', 'U'), ('\u{016B}', 'u'),
+    ('\u{016C}', 'U'), ('\u{016D}', 'u'), ('\u{016E}', 'U'), ('\u{016F}', 'u'),
+    ('\u{0170}', 'U'), ('\u{0171}', 'u'), ('\u{0172}', 'U'), ('\u{0173}', 'u'),
+    ('\u{0174}', 'W'), ('\u{0175}', 'w'), ('\u{0176}', 'Y'), ('\u{0177}', 'y'),
+    ('\u{0178}', 'Y'), ('\u{0179}', 'Z'), ('\u{017A}', 'z'), ('\u{017B}', 'Z'),
+    ('\u{017C}', 'z'), ('\u{017D}', 'Z'), ('\u{017E}', 'z'), ('\u{0197}', 'I'),
+    ('\u{01A0}', 'O'), ('\u{01A1}', 'o'), ('\u{01AF}', 'U'), ('\u{01B0}', 'u'),
+    ('\u{01B5}', 'Z'), ('\u{01B6}', 'z'), ('\u{01CD}', 'A'), ('\u{01CE}', 'a'),
+    ('\u{01CF}', 'I'), ('\u{01D0}', 'i'), ('\u{01D1}', 'O'), ('\u{01D2}', 'o'),
+    ('\u{01D3}', 'U'), ('\u{01D4}', 'u'), ('\u{01D5}', 'U'), ('\u{01D6}', 'u'),
+    ('\u{01D7}', 'U'), ('\u{01D8}', 'u'), ('\u{01D9}', 'U'), ('\u{01DA}', 'u'),
+    ('\u{01DB}', 'U'), ('\u{01DC}', 'u'), ('\u{01DE}', 'A'), ('\u{01DF}', 'a'),
+    ('\u{01E0}', 'A'), ('\u{01E1}', 'a'), ('\u{01E2}', '\u{00C6}'), ('\u{01E3}', '\u{00E6}'),
+    ('\u{01E4}', 'G'), ('\u{01E5}', 'g'), ('\u{01E6}', 'G'), ('\u{01E7}', 'g'),
+    ('\u{01E8}', 'K'), ('\u{01E9}', 'k'), ('\u{01EA}', 'O'), ('\u{01EB}', 'o'),
+    ('\u{01EC}', 'O'), ('\u{01ED}', 'o'), ('\u{01EE}', '\u{01B7}'), ('\u{01EF}', '\u{0292}'),
+    ('\u{01F0}', 'j'), ('\u{01F4}', 'G'), ('\u{01F5}', 'g'), ('\u{01F8}', 'N'),
+    ('\u{01F9}', 'n'), ('\u{01FA}', 'A'), ('\u{01FB}', 'a'), ('\u{01FC}', '\u{00C6}'),
+    ('\u{01FD}', '\u{00E6}'), ('\u{01FE}', '\u{00D8}'), ('\u{01FF}', '\u{00F8}'), ('\u{0200}', 'A'),
+    ('\u{0201}', 'a'), ('\u{0202}', 'A'), ('\u{0203}', 'a'), ('\u{0204}', 'E'),
+    ('\u{0205}', 'e'), ('\u{0206}', 'E'), ('\u{0207}', 'e'), ('\u{0208}', 'I'),
+    ('\u{0209}', 'i'), ('\u{020A}', 'I'), ('\u{020B}', 'i'), ('\u{020C}', 'O'),
+    ('\u{020D}', 'o'), ('\u{020E}', 'O'), ('\u{020F}', 'o'), ('\u{0210}', 'R'),
+    ('\u{0211}', 'r'), ('\u{0212}', 'R'), ('\u{0213}', 'r'), ('\u{0214}', 'U'),
+    ('\u{0215}', 'u'), ('\u{0216}', 'U'), ('\u{0217}', 'u'), ('\u{0218}', 'S'),
+    ('\u{0219}', 's'), ('\u{021A}', 'T'), ('\u{021B}', 't'), ('\u{021E}', 'H'),
+    ('\u{021F}', 'h'), ('\u{0226}', 'A'), ('\u{0227}', 'a'), ('\u{0228}', 'E'),
+    ('\u{0229}', 'e'), ('\u{022A}', 'O'), ('\u{022B}', 'o'), ('\u{022C}', 'O'),
+    ('\u{022D}', 'o'), ('\u{022E}', 'O'), ('\u{022F}', 'o'), ('\u{0230}', 'O'),
+    ('\u{0231}', 'o'), ('\u{0232}', 'Y'), ('\u{0233}', 'y'), ('\u{0268}', 'i'),
+    ('\u{0385}', '\u{00A8}'), ('\u{0386}', '\u{0391}'), ('\u{0388}', '\u{0395}'), ('\u{0389}', '\u{0397}'),
+    ('\u{038A}', '\u{0399}'), ('\u{038C}', '\u{039F}'), ('\u{038E}', '\u{03A5}'), ('\u{038F}', '\u{03A9}'),
+    ('\u{0390}', '\u{03B9}'), ('\u{03AA}', '\u{0399}'), ('\u{03AB}', '\u{03A5}'), ('\u{03AC}', '\u{03B1}'),
+    ('\u{03AD}', '\u{03B5}'), ('\u{03AE}', '\u{03B7}'), ('\u{03AF}', '\u{03B9}'), ('\u{03B0}', '\u{03C5}'),
+    ('\u{03CA}', '\u{03B9}'), ('\u{03CB}', '\u{03C5}'), ('\u{03CC}', '\u{03BF}'), ('\u{03CD}', '\u{03C5}'),
+    ('\u{03CE}', '\u{03C9}'), ('\u{03D3}', '\u{03D2}'), ('\u{03D4}', '\u{03D2}'), ('\u{0400}', '\u{0415}'),
+    ('\u{0401}', '\u{0415}'), ('\u{0403}', '\u{0413}'), ('\u{0407}', '\u{0406}'), ('\u{040C}', '\u{041A}'),
+    ('\u{040D}', '\u{0418}'), ('\u{040E}', '\u{0423}'), ('\u{0419}', '\u{0418}'), ('\u{0439}', '\u{0438}'),
+    ('\u{0450}', '\u{0435}'), ('\u{0451}', '\u{0435}'), ('\u{0453}', '\u{0433}'), ('\u{0457}', '\u{0456}'),
+    ('\u{045C}', '\u{043A}'), ('\u{045D}', '\u{0438}'), ('\u{045E}', '\u{0443}'), ('\u{0476}', '\u{0474}'),
+    ('\u{0477}', '\u{0475}'), ('\u{04C1}', '\u{0416}'), ('\u{04C2}', '\u{0436}'), ('\u{04D0}', '\u{0410}'),
+    ('\u{04D1}', '\u{0430}'), ('\u{04D2}', '\u{0410}'), ('\u{04D3}', '\u{0430}'), ('\u{04D6}', '\u{0415}'),
+    ('\u{04D7}', '\u{0435}'), ('\u{04DA}', '\u{04D8}'), ('\u{04DB}', '\u{04D9}'), ('\u{04DC}', '\u{0416}'),
+    ('\u{04DD}', '\u{0436}'), ('\u{04DE}', '\u{0417}'), ('\u{04DF}', '\u{0437}'), ('\u{04E2}', '\u{0418}'),
+    ('\u{04E3}', '\u{0438}'), ('\u{04E4}', '\u{0418}'), ('\u{04E5}', '\u{0438}'), ('\u{04E6}', '\u{041E}'),
+    ('\u{04E7}', '\u{043E}'), ('\u{04EA}', '\u{04E8}'), ('\u{04EB}', '\u{04E9}'), ('\u{04EC}', '\u{042D}'),
+    ('\u{04ED}', '\u{044D}'), ('\u{04EE}', '\u{0423}'), ('\u{04EF}', '\u{0443}'), ('\u{04F0}', '\u{0423}'),
+    ('\u{04F1}', '\u{0443}'), ('\u{04F2}', '\u{0423}'), ('\u{04F3}', '\u{0443}'), ('\u{04F4}', '\u{0427}'),
+    ('\u{04F5}', '\u{0447}'), ('\u{04F8}', '\u{042B}'), ('\u{04F9}', '\u{044B}'), ('\u{1E00}', 'A'),
+    ('\u{1E01}', 'a'), ('\u{1E02}', 'B'), ('\u{1E03}', 'b'), ('\u{1E04}', 'B'),
+    ('\u{1E05}', 'b'), ('\u{1E06}', 'B'), ('\u{1E07}', 'b'), ('\u{1E08}', 'C'),
+    ('\u{1E09}', 'c'), ('\u{1E0A}', 'D'), ('\u{1E0B}', 'd'), ('\u{1E0C}', 'D'),
+    ('\u{1E0D}', 'd'), ('\u{1E0E}', 'D'), ('\u{1E0F}', 'd'), ('\u{1E10}', 'D'),
+    ('\u{1E11}', 'd'), ('\u{1E12}', 'D'), ('\u{1E13}', 'd'), ('\u{1E14}', 'E'),
+    ('\u{1E15}', 'e'), ('\u{1E16}', 'E'), ('\u{1E17}', 'e'), ('\u{1E18}', 'E'),
+    ('\u{1E19}', 'e'), ('\u{1E1A}', 'E'), ('\u{1E1B}', 'e'), ('\u{1E1C}', 'E'),
+    ('\u{1E1D}', 'e'), ('\u{1E1E}', 'F'), ('\u{1E1F}', 'f'), ('\u{1E20}', 'G'),
+    ('\u{1E21}', 'g'), ('\u{1E22}', 'H'), ('\u{1E23}', 'h'), ('\u{1E24}', 'H'),
+    ('\u{1E25}', 'h'), ('\u{1E26}', 'H'), ('\u{1E27}', 'h'), ('\u{1E28}', 'H'),
+    ('\u{1E29}', 'h'), ('\u{1E2A}', 'H'), ('\u{1E2B}', 'h'), ('\u{1E2C}', 'I'),
+    ('\u{1E2D}', 'i'), ('\u{1E2E}', 'I'), ('\u{1E2F}', 'i'), ('\u{1E30}', 'K'),
+    ('\u{1E31}', 'k'), ('\u{1E32}', 'K'), ('\u{1E33}', 'k'), ('\u{1E34}', 'K'),
+    ('\u{1E35}', 'k'), ('\u{1E36}', 'L'), ('\u{1E37}', 'l'), ('\u{1E38}', 'L'),
+    ('\u{1E39}', 'l'), ('\u{1E3A}', 'L'), ('\u{1E3B}', 'l'), ('\u{1E3C}', 'L'),
+    ('\u{1E3D}', 'l'), ('\u{1E3E}', 'M'), ('\u{1E3F}', 'm'), ('\u{1E40}', 'M'),
+    ('\u{1E41}', 'm'), ('\u{1E42}', 'M'), ('\u{1E43}', 'm'), ('\u{1E44}', 'N'),
+    ('\u{1E45}', 'n'), ('\u{1E46}', 'N'), ('\u{1E47}', 'n'), ('\u{1E48}', 'N'),
+    ('\u{1E49}', 'n'), ('\u{1E4A}', 'N'), ('\u{1E4B}', 'n'), ('\u{1E4C}', 'O'),
+    ('\u{1E4D}', 'o'), ('\u{1E4E}', 'O'), ('\u{1E4F}', 'o'), ('\u{1E50}', 'O'),
+    ('\u{1E51}', 'o'), ('\u{1E52}', 'O'), ('\u{1E53}', 'o'), ('\u{1E54}', 'P'),
+    ('\u{1E55}', 'p'), ('\u{1E56}', 'P'), ('\u{1E57}', 'p'), ('\u{1E58}', 'R'),
+    ('\u{1E59}', 'r'), ('\u{1E5A}', 'R'), ('\u{1E5B}', 'r'), ('\u{1E5C}', 'R'),
+    ('\u{1E5D}', 'r'), ('\u{1E5E}', 'R'), ('\u{1E5F}', 'r'), ('\u{1E60}', 'S'),
+    ('\u{1E61}', 's'), ('\u{1E62}', 'S'), ('\u{1E63}', 's'), ('\u{1E64}', 'S'),
+    ('\u{1E65}', 's'), ('\u{1E66}', 'S'), ('\u{1E67}', 's'), ('\u{1E68}', 'S'),
+    ('\u{1E69}', 's'), ('\u{1E6A}', 'T'), ('\u{1E6B}', 't'), ('\u{1E6C}', 'T'),
+    ('\u{1E6D}', 't'), ('\u{1E6E}', 'T'), ('\u{1E6F}', 't'), ('\u{1E70}', 'T'),
+    ('\u{1E71}', 't'), ('\u{1E72}', 'U'), ('\u{1E73}', 'u'), ('\u{1E74}', 'U'),
+    ('\u{1E75}', 'u'), ('\u{1E76}', 'U'), ('\u{1E77}', 'u'), ('\u{1E78}', 'U'),
+    ('\u{1E79}', 'u'), ('\u{1E7A}', 'U'), ('\u{1E7B}', 'u'), ('\u{1E7C}', 'V'),
+    ('\u{1E7D}', 'v'), ('\u{1E7E}', 'V'), ('\u{1E7F}', 'v'), ('\u{1E80}', 'W'),
+    ('\u{1E81}', 'w'), ('\u{1E82}', 'W'), ('\u{1E83}', 'w'), ('\u{1E84}', 'W'),
+    ('\u{1E85}', 'w'), ('\u{1E86}', 'W'), ('\u{1E87}', 'w'), ('\u{1E88}', 'W'),
+    ('\u{1E89}', 'w'), ('\u{1E8A}', 'X'), ('\u{1E8B}', 'x'), ('\u{1E8C}', 'X'),
+    ('\u{1E8D}', 'x'), ('\u{1E8E}', 'Y'), ('\u{1E8F}', 'y'), ('\u{1E90}', 'Z'),
+    ('\u{1E91}', 'z'), ('\u{1E92}', 'Z'), ('\u{1E93}', 'z'), ('\u{1E94}', 'Z'),
+    ('\u{1E95}', 'z'), ('\u{1E96}', 'h'), ('\u{1E97}', 't'), ('\u{1E98}', 'w'),
+    ('\u{1E99}', 'y'), ('\u{1E9B}', '\u{017F}'), ('\u{1EA0}', 'A'), ('\u{1EA1}', 'a'),
+    ('\u{1EA2}', 'A'), ('\u{1EA3}', 'a'), ('\u{1EA4}', 'A'), ('\u{1EA5}', 'a'),
+    ('\u{1EA6}', 'A'), ('\u{1EA7}', 'a'), ('\u{1EA8}', 'A'), ('\u{1EA9}', 'a'),
+    ('\u{1EAA}', 'A'), ('\u{1EAB}', 'a'), ('\u{1EAC}', 'A'), ('\u{1EAD}', 'a'),
+    ('\u{1EAE}', 'A'), ('\u{1EAF}', 'a'), ('\u{1EB0}', 'A'), ('\u{1EB1}', 'a'),
+    ('\u{1EB2}', 'A'), ('\u{1EB3}', 'a'), ('\u{1EB4}', 'A'), ('\u{1EB5}', 'a'),
+    ('\u{1EB6}', 'A'), ('\u{1EB7}', 'a'), ('\u{1EB8}', 'E'), ('\u{1EB9}', 'e'),
+    ('\u{1EBA}', 'E'), ('\u{1EBB}', 'e'), ('\u{1EBC}', 'E'), ('\u{1EBD}', 'e'),
+    ('\u{1EBE}', 'E'), ('\u{1EBF}', 'e'), ('\u{1EC0}', 'E'), ('\u{1EC1}', 'e'),
+    ('\u{1EC2}', 'E'), ('\u{1EC3}', 'e'), ('\u{1EC4}', 'E'), ('\u{1EC5}', 'e'),
+    ('\u{1EC6}', 'E'), ('\u{1EC7}', 'e'), ('\u{1EC8}', 'I'), ('\u{1EC9}', 'i'),
+    ('\u{1ECA}', 'I'), ('\u{1ECB}', 'i'), ('\u{1ECC}', 'O'), ('\u{1ECD}', 'o'),
+    ('\u{1ECE}', 'O'), ('\u{1ECF}', 'o'), ('\u{1ED0}', 'O'), ('\u{1ED1}', 'o'),
+    ('\u{1ED2}', 'O'), ('\u{1ED3}', 'o'), ('\u{1ED4}', 'O'), ('\u{1ED5}', 'o'),
+    ('\u{1ED6}', 'O'), ('\u{1ED7}', 'o'), ('\u{1ED8}', 'O'), ('\u{1ED9}', 'o'),
+    ('\u{1EDA}', 'O'), ('\u{1EDB}', 'o'), ('\u{1EDC}', 'O'), ('\u{1EDD}', 'o'),
+    ('\u{1EDE}', 'O'), ('\u{1EDF}', 'o'), ('\u{1EE0}', 'O'), ('\u{1EE1}', 'o'),
+    ('\u{1EE2}', 'O'), ('\u{1EE3}', 'o'), ('\u{1EE4}', 'U'), ('\u{1EE5}', 'u'),
+    ('\u{1EE6}', 'U'), ('\u{1EE7}', 'u'), ('\u{1EE8}', 'U'), ('\u{1EE9}', 'u'),
+    ('\u{1EEA}', 'U'), ('\u{1EEB}', 'u'), ('\u{1EEC}', 'U'), ('\u{1EED}', 'u'),
+    ('\u{1EEE}', 'U'), ('\u{1EEF}', 'u'), ('\u{1EF0}', 'U'), ('\u{1EF1}', 'u'),
+    ('\u{1EF2}', 'Y'), ('\u{1EF3}', 'y'), ('\u{1EF4}', 'Y'), ('\u{1EF5}', 'y'),
+    ('\u{1EF6}', 'Y'), ('\u{1EF7}', 'y'), ('\u{1EF8}', 'Y'), ('\u{1EF9}', 'y'),
+    ('\u{1F00}', '\u{03B1}'), ('\u{1F01}', '\u{03B1}'), ('\u{1F02}', '\u{03B1}'), ('\u{1F03}', '\u{03B1}'),
+    ('\u{1F04}', '\u{03B1}'), ('\u{1F05}', '\u{03B1}'), ('\u{1F06}', '\u{03B1}'), ('\u{1F07}', '\u{03B1}'),
+    ('\u{1F08}', '\u{0391}'), ('\u{1F09}', '\u{0391}'), ('\u{1F0A}', '\u{0391}'), ('\u{1F0B}', '\u{0391}'),
+    ('\u{1F0C}', '\u{0391}'), ('\u{1F0D}', '\u{0391}'), ('\u{1F0E}', '\u{0391}'), ('\u{1F0F}', '\u{0391}'),
+    ('\u{1F10}', '\u{03B5}'), ('\u{1F11}', '\u{03B5}'), ('\u{1F12}', '\u{03B5}'), ('\u{1F13}', '\u{03B5}'),
+    ('\u{1F14}', '\u{03B5}'), ('\u{1F15}', '\u{03B5}'), ('\u{1F18}', '\u{0395}'), ('\u{1F19}', '\u{0395}'),
+    ('\u{1F1A}', '\u{0395}'), ('\u{1F1B}', '\u{0395}'), ('\u{1F1C}', '\u{0395}'), ('\u{1F1D}', '\u{0395}'),
+    ('\u{1F20}', '\u{03B7}'), ('\u{1F21}', '\u{03B7}'), ('\u{1F22}', '\u{03B7}'), ('\u{1F23}', '\u{03B7}'),
+    ('\u{1F24}', '\u{03B7}'), ('\u{1F25}', '\u{03B7}'), ('\u{1F26}', '\u{03B7}'), ('\u{1F27}', '\u{03B7}'),
+    ('\u{1F28}', '\u{0397}'), ('\u{1F29}', '\u{0397}'), ('\u{1F2A}', '\u{0397}'), ('\u{1F2B}', '\u{0397}'),
+    ('\u{1F2C}', '\u{0397}'), ('\u{1F2D}', '\u{0397}'), ('\u{1F2E}', '\u{0397}'), ('\u{1F2F}', '\u{0397}'),
+    ('\u{1F30}', '\u{03B9}'), ('\u{1F31}', '\u{03B9}'), ('\u{1F32}', '\u{03B9}'), ('\u{1F33}', '\u{03B9}'),
+    ('\u{1F34}', '\u{03B9}'), ('\u{1F35}', '\u{03B9}'), ('\u{1F36}', '\u{03B9}'), ('\u{1F37}', '\u{03B9}'),
+    ('\u{1F38}', '\u{0399}'), ('\u{1F39}', '\u{0399}'), ('\u{1F3A}', '\u{0399}'), ('\u{1F3B}', '\u{0399}'),
+    ('\u{1F3C}', '\u{0399}'), ('\u{1F3D}', '\u{0399}'), ('\u{1F3E}', '\u{0399}'), ('\u{1F3F}', '\u{0399}'),
+    ('\u{1F40}', '\u{03BF}'), ('\u{1F41}', '\u{03BF}'), ('\u{1F42}', '\u{03BF}'), ('\u{1F43}', '\u{03BF}'),
+    ('\u{1F44}', '\u{03BF}'), ('\u{1F45}', '\u{03BF}'), ('\u{1F48}', '\u{039F}'), ('\u{1F49}', '\u{039F}'),
+    ('\u{1F4A}', '\u{039F}'), ('\u{1F4B}', '\u{039F}'), ('\u{1F4C}', '\u{039F}'), ('\u{1F4D}', '\u{039F}'),
+    ('\u{1F50}', '\u{03C5}'), ('\u{1F51}', '\u{03C5}'), ('\u{1F52}', '\u{03C5}'), ('\u{1F53}', '\u{03C5}'),
+    ('\u{1F54}', '\u{03C5}'), ('\u{1F55}', '\u{03C5}'), ('\u{1F56}', '\u{03C5}'), ('\u{1F57}', '\u{03C5}'),
+    ('\u{1F59}', '\u{03A5}'), ('\u{1F5B}', '\u{03A5}'), ('\u{1F5D}', '\u{03A5}'), ('\u{1F5F}', '\u{03A5}'),
+    ('\u{1F60}', '\u{03C9}'), ('\u{1F61}', '\u{03C9}'), ('\u{1F62}', '\u{03C9}'), ('\u{1F63}', '\u{03C9}'),
+    ('\u{1F64}', '\u{03C9}'), ('\u{1F65}', '\u{03C9}'), ('\u{1F66}', '\u{03C9}'), ('\u{1F67}', '\u{03C9}'),
+    ('\u{1F68}', '\u{03A9}'), ('\u{1F69}', '\u{03A9}'), ('\u{1F6A}', '\u{03A9}'), ('\u{1F6B}', '\u{03A9}'),
+    ('\u{1F6C}', '\u{03A9}'), ('\u{1F6D}', '\u{03A9}'), ('\u{1F6E}', '\u{03A9}'), ('\u{1F6F}', '\u{03A9}'),
+    ('\u{1F70}', '\u{03B1}'), ('\u{1F71}', '\u{03B1}'), ('\u{1F72}', '\u{03B5}'), ('\u{1F73}', '\u{03B5}'),
+    ('\u{1F74}', '\u{03B7}'), ('\u{1F75}', '\u{03B7}'), ('\u{1F76}', '\u{03B9}'), ('\u{1F77}', '\u{03B9}'),
+    ('\u{1F78}', '\u{03BF}'), ('\u{1F79}', '\u{03BF}'), ('\u{1F7A}', '\u{03C5}'), ('\u{1F7B}', '\u{03C5}'),
+    ('\u{1F7C}', '\u{03C9}'), ('\u{1F7D}', '\u{03C9}'), ('\u{1F80}', '\u{03B1}'), ('\u{1F81}', '\u{03B1}'),
+    ('\u{1F82}', '\u{03B1}'), ('\u{1F83}', '\u{03B1}'), ('\u{1F84}', '\u{03B1}'), ('\u{1F85}', '\u{03B1}'),
+    ('\u{1F86}', '\u{03B1}'), ('\u{1F87}', '\u{03B1}'), ('\u{1F88}', '\u{0391}'), ('\u{1F89}', '\u{0391}'),
+    ('\u{1F8A}', '\u{0391}'), ('\u{1F8B}', '\u{0391}'), ('\u{1F8C}', '\u{0391}'), ('\u{1F8D}', '\u{0391}'),
+    ('\u{1F8E}', '\u{0391}'), ('\u{1F8F}', '\u{0391}'), ('\u{1F90}', '\u{03B7}'), ('\u{1F91}', '\u{03B7}'),
+    ('\u{1F92}', '\u{03B7}'), ('\u{1F93}', '\u{03B7}'), ('\u{1F94}', '\u{03B7}'), ('\u{1F95}', '\u{03B7}'),
+    ('\u{1F96}', '\u{03B7}'), ('\u{1F97}', '\u{03B7}'), ('\u{1F98}', '\u{0397}'), ('\u{1F99}', '\u{0397}'),
+    ('\u{1F9A}', '\u{0397}'), ('\u{1F9B}', '\u{0397}'), ('\u{1F9C}', '\u{0397}'), ('\u{1F9D}', '\u{0397}'),
+    ('\u{1F9E}', '\u{0397}'), ('\u{1F9F}', '\u{0397}'), ('\u{1FA0}', '\u{03C9}'), ('\u{1FA1}', '\u{03C9}'),
+    ('\u{1FA2}', '\u{03C9}'), ('\u{1FA3}', '\u{03C9}'), ('\u{1FA4}', '\u{03C9}'), ('\u{1FA5}', '\u{03C9}'),
+    ('\u{1FA6}', '\u{03C9}'), ('\u{1FA7}', '\u{03C9}'), ('\u{1FA8}', '\u{03A9}'), ('\u{1FA9}', '\u{03A9}'),
+    ('\u{1FAA}', '\u{03A9}'), ('\u{1FAB}', '\u{03A9}'), ('\u{1FAC}', '\u{03A9}'), ('\u{1FAD}', '\u{03A9}'),
+    ('\u{1FAE}', '\u{03A9}'), ('\u{1FAF}', '\u{03A9}'), ('\u{1FB0}', '\u{03B1}'), ('\u{1FB1}', '\u{03B1}'),
+    ('\u{1FB2}', '\u{03B1}'), ('\u{1FB3}', '\u{03B1}'), ('\u{1FB4}', '\u{03B1}'), ('\u{1FB6}', '\u{03B1}'),
+    ('\u{1FB7}', '\u{03B1}'), ('\u{1FB8}', '\u{0391}'), ('\u{1FB9}', '\u{0391}'), ('\u{1FBA}', '\u{0391}'),
+    ('\u{1FBB}', '\u{0391}'), ('\u{1FBC}', '\u{0391}'), ('\u{1FC1}', '\u{00A8}'), ('\u{1FC2}', '\u{03B7}'),
+    ('\u{1FC3}', '\u{03B7}'), ('\u{1FC4}', '\u{03B7}'), ('\u{1FC6}', '\u{03B7}'), ('\u{1FC7}', '\u{03B7}'),
+    ('\u{1FC8}', '\u{0395}'), ('\u{1FC9}', '\u{0395}'), ('\u{1FCA}', '\u{0397}'), ('\u{1FCB}', '\u{0397}'),
+    ('\u{1FCC}', '\u{0397}'), ('\u{1FCD}', '\u{1FBF}'), ('\u{1FCE}', '\u{1FBF}'), ('\u{1FCF}', '\u{1FBF}'),
+    ('\u{1FD0}', '\u{03B9}'), ('\u{1FD1}', '\u{03B9}'), ('\u{1FD2}', '\u{03B9}'), ('\u{1FD3}', '\u{03B9}'),
+    ('\u{1FD6}', '\u{03B9}'), ('\u{1FD7}', '\u{03B9}'), ('\u{1FD8}', '\u{0399}'), ('\u{1FD9}', '\u{0399}'),
+    ('\u{1FDA}', '\u{0399}'), ('\u{1FDB}', '\u{0399}'), ('\u{1FDD}', '\u{1FFE}'), ('\u{1FDE}', '\u{1FFE}'),
+    ('\u{1FDF}', '\u{1FFE}'), ('\u{1FE0}', '\u{03C5}'), ('\u{1FE1}', '\u{03C5}'), ('\u{1FE2}', '\u{03C5}'),
+    ('\u{1FE3}', '\u{03C5}'), ('\u{1FE4}', '\u{03C1}'), ('\u{1FE5}', '\u{03C1}'), ('\u{1FE6}', '\u{03C5}'),
+    ('\u{1FE7}', '\u{03C5}'), ('\u{1FE8}', '\u{03A5}'), ('\u{1FE9}', '\u{03A5}'), ('\u{1FEA}', '\u{03A5}'),
+    ('\u{1FEB}', '\u{03A5}'), ('\u{1FEC}', '\u{03A1}'), ('\u{1FED}', '\u{00A8}'), ('\u{1FEE}', '\u{00A8}'),
+    ('\u{1FF2}', '\u{03C9}'), ('\u{1FF3}', '\u{03C9}'), ('\u{1FF4}', '\u{03C9}'), ('\u{1FF6}', '\u{03C9}'),
+    ('\u{1FF7}', '\u{03C9}'), ('\u{1FF8}', '\u{039F}'), ('\u{1FF9}', '\u{039F}'), ('\u{1FFA}', '\u{03A9}'),
+    ('\u{1FFB}', '\u{03A9}'), ('\u{1FFC}', '\u{03A9}'),
+];
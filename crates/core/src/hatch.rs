@@ -0,0 +1,143 @@
+use alloc::vec::Vec;
+
+use crate::Point;
+use crate::path::{round, split_strokes};
+
+/// Options controlling how [hatch_fill] fills outlines.
+#[derive(Debug, Copy, Clone)]
+pub struct HatchOptions {
+    /// Angle of the lines, in radians clockwise from horizontal (with y increasing
+    /// downward)
+    pub angle: f32,
+    /// Distance between one line and the next, in the same units as the points. About
+    /// the width of the pen fills the shapes solidly.
+    pub spacing: f32,
+}
+
+impl Default for HatchOptions {
+    fn default() -> Self {
+        Self {
+            angle: 0.0,
+            spacing: 1.0,
+        }
+    }
+}
+
+/// Fill closed outlines, such as those from [expand_strokes](crate::expand_strokes),
+/// with parallel lines, so that a pen plotter draws heavy, solid-looking text.
+///
+/// Each stroke of `points` is taken as a closed outline, whether or not it ends where it
+/// starts, and the shapes are filled using the nonzero winding rule. Only the hatch lines
+/// are returned, each as a move followed by a line, so add them to the outlines to draw
+/// both. Every other line is drawn backwards, to save moving back across the shape.
+///
+/// ```
+/// use vector_text_core::{expand_strokes, hatch_fill, HatchOptions, OutlineOptions, Point};
+///
+/// let point = |x, y, pen| Point { x, y, pen };
+/// let stroke = [point(0, 0, false), point(20, 0, true)];
+///
+/// let mut points = expand_strokes(&stroke, &OutlineOptions { width: 6.0, ..Default::default() });
+/// let hatching = hatch_fill(&points, &HatchOptions { spacing: 2.0, ..Default::default() });
+/// assert_eq!(hatching.len(), 3 * 2);
+///
+/// points.extend(hatching);
+/// ```
+pub fn hatch_fill(points: &[Point], options: &HatchOptions) -> Vec<Point> {
+    let mut result = Vec::new();
+    if options.spacing.is_nan() || options.spacing <= 0.0 || !options.angle.is_finite() {
+        return result;
+    }
+
+    // Turn the outlines so that the lines are horizontal, and turn the lines back after
+    let (sin, cos) = (libm::sinf(options.angle), libm::cosf(options.angle));
+    let turn = |(x, y): (f32, f32)| (x * cos + y * sin, y * cos - x * sin);
+    let turn_back = |(x, y): (f32, f32)| (x * cos - y * sin, x * sin + y * cos);
+
+    let mut edges = Vec::new();
+    for stroke in split_strokes(points) {
+        let outline: Vec<(f32, f32)> = stroke
+            .iter()
+            .map(|p| turn((p.x as f32, p.y as f32)))
+            .collect();
+        for (i, &from) in outline.iter().enumerate() {
+            let to = outline[(i + 1) % outline.len()];
+            if from.1 != to.1 {
+                edges.push((from, to));
+            }
+        }
+    }
+
+    let (top, bottom) = edges
+        .iter()
+        .flat_map(|&(from, to)| [from.1, to.1])
+        .fold((f32::INFINITY, f32::NEG_INFINITY), |(top, bottom), y| {
+            (top.min(y), bottom.max(y))
+        });
+
+    let mut forwards = true;
+    let mut crossings = Vec::new();
+    let mut y = top + options.spacing / 2.0;
+
+    while y < bottom {
+        // Where each edge crosses the line, and which way around it goes
+        crossings.clear();
+        crossings.extend(edges.iter().filter_map(|&(from, to)| {
+            let (low, high) = if from.1 < to.1 {
+                (from, to)
+            } else {
+                (to, from)
+            };
+            if y < low.1 || y >= high.1 {
+                return None;
+            }
+            let t = (y - low.1) / (high.1 - low.1);
+            let winding = if from.1 < to.1 { 1 } else { -1 };
+            Some((low.0 + t * (high.0 - low.0), winding))
+        }));
+        crossings.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+        // Spans of the line inside the shapes
+        let mut spans = Vec::new();
+        let mut winding = 0;
+        let mut start = 0.0;
+        for &(x, direction) in &crossings {
+            let inside = winding != 0;
+            winding += direction;
+            if !inside && winding != 0 {
+                start = x;
+            } else if inside && winding == 0 {
+                spans.push((start, x));
+            }
+        }
+
+        if !forwards {
+            spans.reverse();
+            spans.iter_mut().for_each(|span| *span = (span.1, span.0));
+        }
+
+        for (from, to) in spans {
+            let (from, to) = (turn_back((from, y)), turn_back((to, y)));
+            let (from, to) = ((round(from.0), round(from.1)), (round(to.0), round(to.1)));
+            if from == to {
+                continue;
+            }
+
+            result.push(Point {
+                x: from.0,
+                y: from.1,
+                pen: false,
+            });
+            result.push(Point {
+                x: to.0,
+                y: to.1,
+                pen: true,
+            });
+        }
+
+        forwards = !forwards;
+        y += options.spacing;
+    }
+
+    result
+}
@@ -0,0 +1,154 @@
+use alloc::vec::Vec;
+
+use crate::Point;
+use crate::path::{distance, position, round};
+
+/// Options controlling how [condition_for_laser] prepares points for a galvo laser.
+#[derive(Debug, Copy, Clone)]
+pub struct LaserOptions {
+    /// Extra points at the start of each stroke, with the laser off, so that the galvos
+    /// settle there after jumping before the line is drawn
+    pub start_dwell: u8,
+    /// Extra points at the end of each stroke, with the laser on, so that the line is
+    /// drawn all the way before blanking
+    pub end_dwell: u8,
+    /// Extra points at each sharp corner, with the laser on, so that it isn't rounded off
+    pub corner_dwell: u8,
+    /// How sharp a corner has to be to get extra points, as the cosine of the angle the
+    /// line turns through: `1.0` for every corner, `0.5` for turns of 60° or more, `0.0`
+    /// for turns of 90° or more, and so on
+    pub corner_threshold: f32,
+    /// Longest distance to draw between two points, with points added along longer
+    /// lines, so that the galvos aren't asked to move faster than they can while drawing
+    pub max_draw_step: Option<f32>,
+    /// Longest distance to jump between two points with the laser off, with points added
+    /// along longer jumps
+    pub max_jump_step: Option<f32>,
+}
+
+impl Default for LaserOptions {
+    fn default() -> Self {
+        Self {
+            start_dwell: 4,
+            end_dwell: 4,
+            corner_dwell: 2,
+            corner_threshold: 0.5,
+            max_draw_step: None,
+            max_jump_step: None,
+        }
+    }
+}
+
+/// Prepare rendered text for a galvo laser, by repeating points where the galvos need
+/// time to catch up. Without these, lines get "tails" where the laser turns on before
+/// the galvos reach the start of a stroke, and sharp corners are drawn rounded.
+///
+/// Each repeated point is a copy of the one before it: starts of strokes are repeated
+/// with the pen up, and ends and sharp corners with the pen down. With a maximum step,
+/// longer lines and jumps are also split up evenly, so the output can be sent to a
+/// projector a point at a time without exceeding how fast its galvos can move.
+///
+/// ```
+/// use vector_text_core::{condition_for_laser, LaserOptions, Point};
+///
+/// let point = |x, y, pen| Point { x, y, pen };
+/// let points = [point(0, 0, false), point(10, 0, true), point(10, 10, true)];
+///
+/// let options = LaserOptions {
+///     start_dwell: 2,
+///     end_dwell: 3,
+///     corner_dwell: 1,
+///     ..Default::default()
+/// };
+/// assert_eq!(condition_for_laser(&points, &options).len(), 3 + 2 + 3 + 1);
+///
+/// let options = LaserOptions {
+///     max_draw_step: Some(2.5),
+///     ..options
+/// };
+/// assert_eq!(condition_for_laser(&points, &options).len(), 3 + 2 + 3 + 1 + 3 + 3);
+/// ```
+pub fn condition_for_laser(points: &[Point], options: &LaserOptions) -> Vec<Point> {
+    let mut result = Vec::with_capacity(points.len());
+
+    for (i, &point) in points.iter().enumerate() {
+        let step = if point.pen {
+            options.max_draw_step
+        } else {
+            options.max_jump_step
+        };
+
+        if let (Some(step), Some(last)) = (step, i.checked_sub(1).map(|i| points[i])) {
+            split(&last, &point, step, &mut result);
+        }
+
+        result.push(point);
+
+        let next = points.get(i + 1);
+        let dwell = if !point.pen {
+            // Only moves which start a stroke need to settle
+            if next.is_some_and(|next| next.pen) {
+                options.start_dwell
+            } else {
+                0
+            }
+        } else if next.is_none_or(|next| !next.pen) {
+            options.end_dwell
+        } else if i > 0 && is_sharp(&points[i - 1], &point, &points[i + 1], options) {
+            options.corner_dwell
+        } else {
+            0
+        };
+
+        for _ in 0..dwell {
+            result.push(point);
+        }
+    }
+
+    result
+}
+
+/// Add points evenly spaced between `from` and `to`, so that none is more than `step`
+/// apart. They're drawn with the pen as it is moving to `to`.
+fn split(from: &Point, to: &Point, step: f32, out: &mut Vec<Point>) {
+    if step.is_nan() || step <= 0.0 {
+        return;
+    }
+
+    let (a, b) = (position(from), position(to));
+    let length = distance(a, b) as f32 / 16.0;
+
+    // Number of steps to take, rounded up
+    let mut steps = (length / step) as u32;
+    if steps as f32 * step < length {
+        steps += 1;
+    }
+
+    for k in 1..steps {
+        let t = k as f32 / steps as f32;
+        out.push(Point {
+            x: round(a.0 as f32 + (b.0 - a.0) as f32 * t),
+            y: round(a.1 as f32 + (b.1 - a.1) as f32 * t),
+            pen: to.pen,
+        });
+    }
+}
+
+/// Does the line turn sharply enough at `b`, on its way from `a` to `c`, to need extra
+/// points there?
+fn is_sharp(a: &Point, b: &Point, c: &Point, options: &LaserOptions) -> bool {
+    let (a, b, c) = (position(a), position(b), position(c));
+    let (ux, uy) = ((b.0 - a.0) as f32, (b.1 - a.1) as f32);
+    let (vx, vy) = ((c.0 - b.0) as f32, (c.1 - b.1) as f32);
+
+    let squared_lengths = (ux * ux + uy * uy) * (vx * vx + vy * vy);
+    if squared_lengths == 0.0 {
+        return false;
+    }
+
+    // Compare the cosine of the turn to the threshold, squared to avoid a square root
+    // while keeping the signs
+    let dot = ux * vx + uy * vy;
+    let threshold = options.corner_threshold;
+    dot * dot.abs() <= threshold * threshold.abs() * squared_lengths
+}
@@ -1,14 +1,90 @@
 #![no_std]
+#![cfg_attr(
+    all(feature = "progmem", target_arch = "avr"),
+    feature(asm_experimental_arch)
+)]
 
 //! `vector-text-core` provides core primitives for the `vector-text` crate.
+//!
+//! Everything which returns a `Vec` needs the `alloc` feature, which is on by default.
+//! Without it, text can still be drawn with no allocator at all, by
+//! [render_glyphs_to] or [Font::render_to], which hand each point to a [PointSink].
 
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+#[cfg(feature = "alloc")]
 use alloc::vec::Vec;
 
+#[cfg(feature = "alloc")]
 extern crate alloc;
 
+mod bbox;
+#[cfg(feature = "alloc")]
+mod budget;
+mod coverage;
+#[cfg(feature = "alloc")]
+mod custom;
+mod fallback;
+#[cfg(feature = "alloc")]
+mod hatch;
+#[cfg(feature = "alloc")]
+mod laser;
+mod minimal;
+#[cfg(feature = "normalize")]
+mod normalize;
+#[cfg(feature = "alloc")]
+mod outline;
+#[cfg(feature = "alloc")]
+mod path;
+mod render;
+mod segment;
+#[cfg(feature = "alloc")]
+mod shade;
+mod strokes;
+#[cfg(feature = "alloc")]
+mod validate;
+#[cfg(feature = "alloc")]
+mod wrap;
+
+pub use bbox::BBox;
+#[cfg(feature = "alloc")]
+pub use budget::{OverBudget, fit_to_budget};
+pub use coverage::Coverage;
+#[cfg(feature = "alloc")]
+pub use custom::CustomFont;
+pub use fallback::strip_diacritics;
+#[cfg(feature = "alloc")]
+pub use hatch::{HatchOptions, hatch_fill};
+#[cfg(feature = "alloc")]
+pub use laser::{LaserOptions, condition_for_laser};
+pub use minimal::MinimalFont;
+#[cfg(feature = "alloc")]
+pub use outline::{Join, OutlineOptions, compensate_tool, double_strokes, expand_strokes};
+#[cfg(feature = "alloc")]
+pub use path::{
+    PathStats, TravelOptions, optimize_travel, remove_duplicate_segments, resample, simplify,
+    smooth, split_strokes,
+};
+pub use render::{
+    ControlChars, MissingGlyph, Origin, PlacedGlyph, PointSink, RenderOptions, Scale, Shadow,
+    YAxis, count_glyphs, is_combining_mark, render_glyphs_to,
+};
+#[cfg(feature = "alloc")]
+pub use render::{Hyphenation, layout_glyphs, render_glyphs, render_mixed_glyphs};
+pub use segment::SegmentFont;
+#[cfg(feature = "alloc")]
+pub use shade::{ShadedPoint, shade};
+#[cfg(feature = "progmem")]
+pub use strokes::ProgmemBytes;
+pub use strokes::{Strokes, StrokesIter};
+#[cfg(feature = "alloc")]
+pub use validate::{FontReport, GlyphProblem, validate};
+
 /// A point, in compact representation.
 /// Used to store the points which make up an individual glyph.
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[repr(C)]
 pub struct PackedPoint {
     /// X coordinate of this point
     pub x: i8,
@@ -18,31 +94,729 @@ pub struct PackedPoint {
     pub pen: bool,
 }
 
+impl PackedPoint {
+    /// Number of bytes each point takes up in the form read by
+    /// [PackedPoint::slice_from_bytes].
+    pub const SIZE: usize = 3;
+
+    /// View some bytes as a slice of points, without copying them.
+    ///
+    /// Each point is three bytes: its x and y coordinates, then 1 if the pen is down or
+    /// 0 if it is up. The built-in fonts store their strokes this way, which is much
+    /// quicker to compile than the equivalent Rust code.
+    ///
+    /// ```
+    /// use vector_text_core::PackedPoint;
+    ///
+    /// const POINTS: &[PackedPoint] = PackedPoint::slice_from_bytes(&[0, 0, 0, 5, 0xFB, 1]);
+    /// assert_eq!((POINTS[1].x, POINTS[1].y, POINTS[1].pen), (5, -5, true));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the length isn't a multiple of three, or a pen byte is neither 0 nor 1.
+    /// When called in a constant, this is an error at compile time instead.
+    pub const fn slice_from_bytes(bytes: &[u8]) -> &[PackedPoint] {
+        assert!(
+            bytes.len().is_multiple_of(Self::SIZE),
+            "length must be a multiple of 3"
+        );
+
+        let mut i = 2;
+        while i < bytes.len() {
+            assert!(bytes[i] <= 1, "pen bytes must be 0 or 1");
+            i += Self::SIZE;
+        }
+
+        // SAFETY: PackedPoint is `repr(C)` with fields of size 1 (so it is 3 bytes long with
+        // an alignment of 1, and has no padding), and every `bool` was checked to be valid.
+        unsafe { core::slice::from_raw_parts(bytes.as_ptr().cast(), bytes.len() / Self::SIZE) }
+    }
+}
+
 /// A single glyph (character) contained within a font.
+///
+/// The glyph data is borrowed, so it can either be compiled into the program
+/// (as in the built-in fonts, where it is `'static`) or belong to a font loaded at runtime.
 #[derive(Debug, Copy, Clone)]
-pub struct Glyph {
+pub struct Glyph<'a> {
     /// Left coordinate boundary of this glyph
     pub left: i8,
     /// Right coordinate boundary of this glyph
     pub right: i8,
     /// Series of points which make up this glyph
-    pub strokes: &'static [PackedPoint],
+    pub strokes: Strokes<'a>,
+    /// Points used to attach combining marks to this glyph
+    pub anchors: &'a [Anchor],
+}
+
+/// A glyph which owns its strokes and anchors, such as one loaded from a file at
+/// runtime, or decoded from a font's compressed data.
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LoadedGlyph {
+    /// Left coordinate boundary of this glyph
+    pub left: i8,
+    /// Right coordinate boundary of this glyph
+    pub right: i8,
+    /// Series of points which make up this glyph
+    pub strokes: Vec<PackedPoint>,
+    /// Points used to attach combining marks to this glyph
+    pub anchors: Vec<Anchor>,
+}
+
+#[cfg(feature = "alloc")]
+impl LoadedGlyph {
+    /// Borrow this glyph's data as a [Glyph].
+    pub fn as_glyph(&self) -> Glyph<'_> {
+        Glyph {
+            left: self.left,
+            right: self.right,
+            strokes: Strokes::Points(&self.strokes),
+            anchors: &self.anchors,
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl From<Glyph<'_>> for LoadedGlyph {
+    /// Copy a glyph's data, decoding its strokes if they are compressed.
+    fn from(glyph: Glyph<'_>) -> Self {
+        Self {
+            left: glyph.left,
+            right: glyph.right,
+            strokes: glyph.strokes.iter().collect(),
+            anchors: glyph.anchors.to_vec(),
+        }
+    }
+}
+
+/// A named point on a glyph, used to position a combining mark relative to its base.
+///
+/// A mark is attached by moving its [AnchorKind::MarkAbove] (or [AnchorKind::MarkBelow])
+/// anchor onto the base glyph's [AnchorKind::Above] (or [AnchorKind::Below]) anchor.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Anchor {
+    /// What this anchor is used for
+    pub kind: AnchorKind,
+    /// X coordinate of this anchor
+    pub x: i8,
+    /// Y coordinate of this anchor
+    pub y: i8,
+}
+
+/// The purpose of an [Anchor].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum AnchorKind {
+    /// On a base glyph, where a mark drawn above it is attached
+    Above,
+    /// On a base glyph, where a mark drawn below it is attached
+    Below,
+    /// On a mark, the point attached to the base glyph's [AnchorKind::Above] anchor
+    MarkAbove,
+    /// On a mark, the point attached to the base glyph's [AnchorKind::Below] anchor
+    MarkBelow,
+}
+
+impl Glyph<'_> {
+    /// Find this glyph's anchor of the given kind, if it has one.
+    pub fn anchor(&self, kind: AnchorKind) -> Option<Anchor> {
+        self.anchors.iter().find(|a| a.kind == kind).copied()
+    }
+
+    /// Horizontal distance to move after drawing this glyph.
+    pub fn advance(&self) -> i16 {
+        self.right as i16 - self.left as i16
+    }
 }
 
 /// Representation of a point with higher range than [PackedPoint].
 /// Used for the output of text rendering.
-#[derive(Default)]
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub struct Point {
     pub x: i16,
     pub y: i16,
     pub pen: bool,
 }
 
+/// Points can be added and subtracted like vectors, and multiplied by a factor, to move
+/// and resize rendered text. The pen is taken from the point on the left, and
+/// coordinates which would overflow are saturated.
+///
+/// ```
+/// use vector_text_core::Point;
+///
+/// let point = Point { x: 3, y: -2, pen: true };
+/// let offset = Point { x: 10, y: 20, pen: false };
+///
+/// assert_eq!(point + offset, Point { x: 13, y: 18, pen: true });
+/// assert_eq!(point * 2 - offset, point.scale(2, 2).translate(-10, -20));
+/// assert_eq!(point.rotate90(), Point { x: 2, y: 3, pen: true });
+/// ```
+impl Point {
+    /// Move the point by (`dx`, `dy`).
+    pub fn translate(self, dx: i16, dy: i16) -> Self {
+        Self {
+            x: self.x.saturating_add(dx),
+            y: self.y.saturating_add(dy),
+            pen: self.pen,
+        }
+    }
+
+    /// Scale the point away from the origin, by `sx` across and `sy` down.
+    pub fn scale(self, sx: i16, sy: i16) -> Self {
+        Self {
+            x: self.x.saturating_mul(sx),
+            y: self.y.saturating_mul(sy),
+            pen: self.pen,
+        }
+    }
+
+    /// Turn the point a quarter turn about the origin, clockwise with y increasing
+    /// downward, such as to draw text running down the page.
+    pub fn rotate90(self) -> Self {
+        Self {
+            x: self.y.saturating_neg(),
+            y: self.x,
+            pen: self.pen,
+        }
+    }
+}
+
+impl core::ops::Add for Point {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        self.translate(other.x, other.y)
+    }
+}
+
+impl core::ops::Sub for Point {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        Self {
+            x: self.x.saturating_sub(other.x),
+            y: self.y.saturating_sub(other.y),
+            pen: self.pen,
+        }
+    }
+}
+
+impl core::ops::Mul<i16> for Point {
+    type Output = Self;
+
+    fn mul(self, factor: i16) -> Self {
+        self.scale(factor, factor)
+    }
+}
+
+/// A point from its x, y, and whether the pen is down.
+///
+/// ```
+/// use vector_text_core::Point;
+///
+/// let points: Vec<Point> = [(0, 0, false), (10, 0, true)].map(Point::from).into();
+/// let corners: Vec<[i16; 2]> = points.iter().map(|&p| p.into()).collect();
+/// assert_eq!(corners, [[0, 0], [10, 0]]);
+/// ```
+impl From<(i16, i16, bool)> for Point {
+    fn from((x, y, pen): (i16, i16, bool)) -> Self {
+        Self { x, y, pen }
+    }
+}
+
+impl From<Point> for (i16, i16, bool) {
+    fn from(point: Point) -> Self {
+        (point.x, point.y, point.pen)
+    }
+}
+
+/// The position of a point, without the pen.
+impl From<Point> for (i16, i16) {
+    fn from(point: Point) -> Self {
+        (point.x, point.y)
+    }
+}
+
+/// The position of a point, without the pen.
+impl From<Point> for [i16; 2] {
+    fn from(point: Point) -> Self {
+        [point.x, point.y]
+    }
+}
+
+/// The position of a point, without the pen.
+#[cfg(feature = "mint")]
+impl From<Point> for mint::Point2<i16> {
+    fn from(point: Point) -> Self {
+        mint::Point2 {
+            x: point.x,
+            y: point.y,
+        }
+    }
+}
+
+/// Measurements describing the overall proportions of a font.
+///
+/// Vertical distances are measured in the same units as the glyph coordinates,
+/// away from the baseline (so all of them are usually positive).
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct FontMetrics {
+    /// Y coordinate of the baseline
+    pub baseline: i16,
+    /// Height of capital letters above the baseline
+    pub cap_height: i16,
+    /// Height of lowercase letters (such as `x`) above the baseline
+    pub x_height: i16,
+    /// Height of the tallest glyphs (such as accented capitals) above the baseline
+    pub ascender: i16,
+    /// Depth of descenders below the baseline
+    pub descender: i16,
+    /// Typical horizontal advance of a glyph
+    pub average_advance: i16,
+}
+
+impl FontMetrics {
+    /// Measure the metrics of a font from the shapes of a few reference glyphs
+    /// (`H`, `x`, and `p`), and the heights and advances of the printable ASCII and
+    /// Latin-1 characters.
+    ///
+    /// This is useful for fonts loaded at runtime. If the reference glyphs are missing,
+    /// typical proportions are assumed instead.
+    pub fn measure<'a>(lookup: impl Fn(char) -> Option<Glyph<'a>>) -> Self {
+        let top = |c| lookup(c).and_then(|g| g.strokes.iter().map(|p| p.y as i16).min());
+        let bottom = |c| lookup(c).and_then(|g| g.strokes.iter().map(|p| p.y as i16).max());
+
+        let baseline = bottom('H').unwrap_or(0);
+        let cap_height = top('H').map(|y| baseline - y).unwrap_or(20);
+        let x_height = top('x').map(|y| baseline - y).unwrap_or(cap_height * 2 / 3);
+        let descender = bottom('p').map(|y| y - baseline).unwrap_or(cap_height / 3);
+        let ascender = (' '..='~')
+            .chain('\u{A0}'..='\u{FF}')
+            .filter_map(top)
+            .map(|y| baseline - y)
+            .fold(cap_height, i16::max);
+
+        let (total, count) = (' '..='~')
+            .filter_map(&lookup)
+            .fold((0i16, 0i16), |(total, count), g| {
+                (total + g.advance(), count + 1)
+            });
+
+        let average_advance = if count == 0 {
+            cap_height
+        } else {
+            total / count
+        };
+
+        Self {
+            baseline,
+            cap_height,
+            x_height,
+            ascender,
+            descender,
+            average_advance,
+        }
+    }
+
+    /// Distance between the baselines of consecutive lines of text.
+    pub fn line_height(&self) -> i16 {
+        // Leave a gap between lines of about a third of the full glyph height
+        (self.cap_height + self.descender) * 4 / 3
+    }
+
+    /// Size of a font unit which makes capital letters `cap_height` tall, such as in
+    /// millimetres, for fonts whose units differ in size to be drawn at the same size.
+    ///
+    /// ```
+    /// use vector_text_core::{Font, MinimalFont};
+    ///
+    /// let metrics = MinimalFont.metrics();
+    /// let scale = metrics.scale_for_cap_height(5.0);
+    /// assert_eq!(metrics.cap_height as f32 * scale, 5.0);
+    /// ```
+    pub fn scale_for_cap_height(&self, cap_height: f32) -> f32 {
+        match self.cap_height {
+            ..=0 => 1.0,
+            units => cap_height / units as f32,
+        }
+    }
+
+    /// The [Scale] to render at for capital letters to be `cap_height` font units tall,
+    /// such as to draw text in several fonts at the same size.
+    pub fn scale_to_cap_height(&self, cap_height: u16) -> Scale {
+        match self.cap_height {
+            ..=0 => Scale::ONE,
+            units => Scale {
+                numerator: cap_height,
+                denominator: units as u16,
+            },
+        }
+    }
+}
+
+/// An error encountered while rendering text.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum RenderError {
+    /// The font has no glyph for this character.
+    UnsupportedCharacter(char),
+    /// This character lies outside the range of codepoints the font is able to index.
+    CodepointOutOfRange(char),
+    /// The text is too wide to be represented with [Point] coordinates.
+    AdvanceOverflow {
+        /// Byte offset in the text of the first character which did not fit
+        offset: usize,
+    },
+}
+
+impl core::fmt::Display for RenderError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::UnsupportedCharacter(c) => write!(f, "no glyph for character {:?}", c),
+            Self::CodepointOutOfRange(c) => {
+                write!(f, "character {:?} is outside the range of this font", c)
+            }
+            Self::AdvanceOverflow { offset } => {
+                write!(f, "text is too wide to render past byte offset {}", offset)
+            }
+        }
+    }
+}
+
+impl core::error::Error for RenderError {}
+
+/// An error returned when parsing the name of a font which does not exist.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct ParseFontError;
+
+impl core::fmt::Display for ParseFontError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "no font with this name")
+    }
+}
+
+impl core::error::Error for ParseFontError {}
+
+/// An error encountered while loading font data, such as an SVG font or KiCad symbol
+/// library read at runtime.
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// Line number (starting from 1) of the file where the error was found
+    pub line: usize,
+    /// Description of the problem
+    pub message: String,
+}
+
+#[cfg(feature = "alloc")]
+impl ParseError {
+    /// Create an error found on line `lineno` of a file, counting from 0 (as given by
+    /// [Iterator::enumerate] over its lines).
+    pub fn new(lineno: usize, message: impl Into<String>) -> Self {
+        Self {
+            line: lineno + 1,
+            message: message.into(),
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl core::fmt::Display for ParseError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl core::error::Error for ParseError {}
+
+/// A font which glyphs can be looked up in one character at a time.
+///
+/// This allows code that lays out text to be written once for every backend:
+///
+/// ```
+/// use vector_text_core::Font;
+///
+/// fn width(font: &impl Font, text: &str) -> i32 {
+///     text.chars()
+///         .filter_map(|c| font.glyph(c))
+///         .map(|glyph| glyph.advance() as i32)
+///         .sum()
+/// }
+/// ```
+///
+/// Fonts loaded at runtime can implement it too, by lending out glyphs which borrow
+/// from their own data:
+///
+/// ```
+/// # #[cfg(feature = "alloc")] {
+/// use vector_text_core::{Font, FontMetrics, Glyph, PackedPoint, Strokes};
+///
+/// struct LoadedFont {
+///     glyphs: Vec<(char, i8, i8, Vec<PackedPoint>)>,
+///     metrics: FontMetrics,
+/// }
+///
+/// impl Font for LoadedFont {
+///     fn glyph(&self, character: char) -> Option<Glyph<'_>> {
+///         let (_, left, right, strokes) = self.glyphs.iter().find(|g| g.0 == character)?;
+///
+///         Some(Glyph {
+///             left: *left,
+///             right: *right,
+///             strokes: Strokes::Points(strokes),
+///             anchors: &[],
+///         })
+///     }
+///
+///     fn metrics(&self) -> FontMetrics {
+///         self.metrics
+///     }
+/// }
+///
+/// let font = LoadedFont {
+///     glyphs: vec![('|', -2, 2, vec![
+///         PackedPoint { x: 0, y: 0, pen: false },
+///         PackedPoint { x: 0, y: 9, pen: true },
+///     ])],
+///     metrics: FontMetrics {
+///         baseline: 9,
+///         cap_height: 9,
+///         x_height: 5,
+///         ascender: 9,
+///         descender: 3,
+///         average_advance: 4,
+///     },
+/// };
+///
+/// assert_eq!(font.render_text("||").len(), 4);
+/// # }
+/// ```
+///
+/// The font to draw with can also be chosen at runtime, from fonts of different types,
+/// as a `dyn Font`:
+///
+/// ```
+/// # #[cfg(feature = "alloc")] {
+/// use vector_text_core::{Font, MinimalFont, SegmentFont};
+///
+/// fn choose(segments: bool) -> Box<dyn Font> {
+///     match segments {
+///         true => Box::new(SegmentFont::Seven),
+///         false => Box::new(MinimalFont),
+///     }
+/// }
+///
+/// let points = choose(true).render_text("12:34");
+/// assert!(!points.is_empty());
+/// # }
+/// ```
+pub trait Font {
+    /// Look up the glyph used to draw the given character, if this font has one.
+    fn glyph(&self, character: char) -> Option<Glyph<'_>>;
+
+    /// Get the overall metrics of this font.
+    fn metrics(&self) -> FontMetrics;
+
+    /// Check whether this font has a glyph for the given character.
+    fn supports_char(&self, character: char) -> bool {
+        self.glyph(character).is_some()
+    }
+
+    /// Render the given text string, giving each point to `sink` as it is drawn,
+    /// without allocating. See [render_glyphs_to].
+    fn render_to(
+        &self,
+        text: &str,
+        options: &RenderOptions,
+        sink: &mut impl PointSink,
+    ) -> Result<(), RenderError>
+    where
+        Self: Sized,
+    {
+        render_glyphs_to(
+            text,
+            |c| self.glyph(c).ok_or(RenderError::UnsupportedCharacter(c)),
+            &self.metrics(),
+            options,
+            sink,
+        )
+    }
+
+    /// Render the given text string to a series of points.
+    ///
+    /// Characters which the font cannot draw are skipped. If the text is too wide
+    /// to represent, it is cut off after the last glyph that fits.
+    #[cfg(feature = "alloc")]
+    fn render_text(&self, text: &str) -> Vec<Point> {
+        let mut result = Vec::new();
+        // On overflow, keep whatever fit before it
+        let _ = render_font(self, text, &RenderOptions::default(), &mut result);
+        result
+    }
+
+    /// Render the given text string to a series of points,
+    /// returning an error if any character cannot be drawn.
+    #[cfg(feature = "alloc")]
+    fn try_render_text(&self, text: &str) -> Result<Vec<Point>, RenderError> {
+        self.render_text_with_options(text, &RenderOptions::strict())
+    }
+
+    /// Render the given text string to a series of points with the given rendering options.
+    #[cfg(feature = "alloc")]
+    fn render_text_with_options(
+        &self,
+        text: &str,
+        options: &RenderOptions,
+    ) -> Result<Vec<Point>, RenderError> {
+        let mut result = Vec::new();
+        render_font(self, text, options, &mut result)?;
+        Ok(result)
+    }
+}
+
+impl<F: Font + ?Sized> Font for &F {
+    fn glyph(&self, character: char) -> Option<Glyph<'_>> {
+        (**self).glyph(character)
+    }
+
+    fn metrics(&self) -> FontMetrics {
+        (**self).metrics()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<F: Font + ?Sized> Font for alloc::boxed::Box<F> {
+    fn glyph(&self, character: char) -> Option<Glyph<'_>> {
+        (**self).glyph(character)
+    }
+
+    fn metrics(&self) -> FontMetrics {
+        (**self).metrics()
+    }
+}
+
+/// Lay out text drawn with a [Font], appending the resulting points to `out`.
+#[cfg(feature = "alloc")]
+fn render_font<T: Font + ?Sized>(
+    font: &T,
+    text: &str,
+    options: &RenderOptions,
+    out: &mut Vec<Point>,
+) -> Result<(), RenderError> {
+    render_glyphs(
+        text,
+        |c| font.glyph(c).ok_or(RenderError::UnsupportedCharacter(c)),
+        &font.metrics(),
+        options,
+        out,
+    )
+}
+
 /// Allows rendering text into vector points.
 ///
 /// Implementors may define their own font mapping (enum or other data structure).
+/// The fonts of each backend are also [Font]s, which draw the same text from `&self`
+/// methods and can be used as a `dyn Font`, for fonts chosen at runtime.
 pub trait Renderer<Mapping> {
+    /// Look up the glyph used to draw the given character with the given font mapping.
+    ///
+    /// The glyph may borrow from the mapping, so fonts loaded at runtime can be drawn too.
+    fn glyph(mapping: &Mapping, character: char) -> Result<Glyph<'_>, RenderError>;
+
+    /// Get the overall metrics of the given font mapping.
+    fn metrics(mapping: &Mapping) -> FontMetrics;
+
+    /// Iterate over every character which might have a glyph in the given font mapping,
+    /// in increasing order.
+    ///
+    /// The default includes every Unicode character; implementors should narrow it down
+    /// to the range of codepoints their fonts are able to index.
+    fn candidates(mapping: &Mapping) -> impl Iterator<Item = char> {
+        let _ = mapping;
+        '\0'..=char::MAX
+    }
+
+    /// Check whether the given font mapping has a glyph for a character.
+    fn supports_char(mapping: &Mapping, character: char) -> bool {
+        Self::glyph(mapping, character).is_ok()
+    }
+
+    /// Iterate over the ranges of characters the given font mapping has glyphs for.
+    fn coverage(
+        mapping: &Mapping,
+    ) -> Coverage<impl Iterator<Item = char>, impl FnMut(char) -> bool> {
+        Coverage::new(Self::candidates(mapping), |c| {
+            Self::supports_char(mapping, c)
+        })
+    }
+
+    /// Render the given text string using the given font mapping, giving each point to
+    /// `sink` as it is drawn, without allocating. See [render_glyphs_to].
+    fn render_to(
+        text: &str,
+        mapping: Mapping,
+        options: &RenderOptions,
+        sink: &mut impl PointSink,
+    ) -> Result<(), RenderError> {
+        render_glyphs_to(
+            text,
+            |c| Self::glyph(&mapping, c),
+            &Self::metrics(&mapping),
+            options,
+            sink,
+        )
+    }
+
+    /// Render the given text string to a series of points,
+    /// using the given font mapping.
+    ///
+    /// Characters which the font cannot draw are skipped. If the text is too wide
+    /// to represent, it is cut off after the last glyph that fits.
+    #[cfg(feature = "alloc")]
+    fn render_text(text: &str, mapping: Mapping) -> Vec<Point> {
+        let mut result = Vec::new();
+        // On overflow, keep whatever fit before it
+        let _ = render_glyphs(
+            text,
+            |c| Self::glyph(&mapping, c),
+            &Self::metrics(&mapping),
+            &RenderOptions::default(),
+            &mut result,
+        );
+        result
+    }
+
     /// Render the given text string to a series of points,
     /// using the given font mapping.
-    fn render_text(text: &str, mapping: Mapping) -> Vec<Point>;
+    ///
+    /// Returns an error if any character cannot be drawn.
+    #[cfg(feature = "alloc")]
+    fn try_render_text(text: &str, mapping: Mapping) -> Result<Vec<Point>, RenderError> {
+        Self::render_text_with_options(text, mapping, &RenderOptions::strict())
+    }
+
+    /// Render the given text string to a series of points,
+    /// using the given font mapping and rendering options.
+    #[cfg(feature = "alloc")]
+    fn render_text_with_options(
+        text: &str,
+        mapping: Mapping,
+        options: &RenderOptions,
+    ) -> Result<Vec<Point>, RenderError> {
+        let mut result = Vec::new();
+        render_glyphs(
+            text,
+            |c| Self::glyph(&mapping, c),
+            &Self::metrics(&mapping),
+            options,
+            &mut result,
+        )?;
+        Ok(result)
+    }
 }
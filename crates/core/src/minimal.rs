@@ -0,0 +1,121 @@
+use crate::{Font, FontMetrics, Glyph, PackedPoint, Strokes};
+
+/// A tiny stroke font covering the uppercase letters, digits, and basic punctuation,
+/// defined directly in code.
+///
+/// Unlike the other fonts, it needs no build script or data files, so it is always
+/// available: as a last resort when other fonts are missing a glyph, or on targets where
+/// the larger fonts don't fit. Lowercase letters are drawn as capitals.
+///
+/// ```
+/// # #[cfg(feature = "alloc")] {
+/// use vector_text_core::{Font, MinimalFont};
+///
+/// assert!(MinimalFont.supports_char('7'));
+/// assert!(MinimalFont.supports_char('q'));
+/// let points = MinimalFont.render_text("HELLO 123");
+/// # }
+/// ```
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Hash)]
+pub struct MinimalFont;
+
+/// Move the pen to a point without drawing
+const fn m(x: i8, y: i8) -> PackedPoint {
+    PackedPoint { x, y, pen: false }
+}
+
+/// Draw a line to a point
+const fn l(x: i8, y: i8) -> PackedPoint {
+    PackedPoint { x, y, pen: true }
+}
+
+/// Every glyph is drawn in a box 8 units wide and 12 tall, with this much space on each side
+const SIDE_BEARING: i8 = 2;
+
+/// The strokes of each glyph, sorted by character
+#[rustfmt::skip]
+static GLYPHS: [(char, &[PackedPoint]); 50] = [
+    (' ', &[]),
+    ('!', &[m(4, -12), l(4, -4), m(4, -1), l(4, 0)]),
+    ('"', &[m(2, -12), l(2, -9), m(6, -12), l(6, -9)]),
+    ('\'', &[m(4, -12), l(4, -9)]),
+    ('(', &[m(5, -12), l(3, -9), l(3, -3), l(5, 0)]),
+    (')', &[m(3, -12), l(5, -9), l(5, -3), l(3, 0)]),
+    ('+', &[m(4, -9), l(4, -3), m(1, -6), l(7, -6)]),
+    (',', &[m(4, -1), l(4, 0), l(3, 2)]),
+    ('-', &[m(1, -6), l(7, -6)]),
+    ('.', &[m(4, -1), l(4, 0)]),
+    ('/', &[m(0, 0), l(8, -12)]),
+    ('0', &[m(2, -12), l(6, -12), l(8, -10), l(8, -2), l(6, 0), l(2, 0), l(0, -2), l(0, -10), l(2, -12), m(0, -2), l(8, -10)]),
+    ('1', &[m(2, -10), l(4, -12), l(4, 0), m(2, 0), l(6, 0)]),
+    ('2', &[m(0, -10), l(2, -12), l(6, -12), l(8, -10), l(8, -7), l(0, 0), l(8, 0)]),
+    ('3', &[m(0, -10), l(2, -12), l(6, -12), l(8, -10), l(8, -8), l(6, -6), l(3, -6), m(6, -6), l(8, -4), l(8, -2), l(6, 0), l(2, 0), l(0, -2)]),
+    ('4', &[m(6, 0), l(6, -12), l(0, -4), l(8, -4)]),
+    ('5', &[m(8, -12), l(0, -12), l(0, -7), l(6, -7), l(8, -5), l(8, -2), l(6, 0), l(2, 0), l(0, -2)]),
+    ('6', &[m(7, -12), l(3, -12), l(0, -9), l(0, -2), l(2, 0), l(6, 0), l(8, -2), l(8, -5), l(6, -7), l(0, -7)]),
+    ('7', &[m(0, -12), l(8, -12), l(3, 0)]),
+    ('8', &[m(2, -6), l(0, -8), l(0, -10), l(2, -12), l(6, -12), l(8, -10), l(8, -8), l(6, -6), l(2, -6), l(0, -4), l(0, -2), l(2, 0), l(6, 0), l(8, -2), l(8, -4), l(6, -6)]),
+    ('9', &[m(8, -7), l(2, -7), l(0, -9), l(0, -10), l(2, -12), l(6, -12), l(8, -10), l(8, -3), l(5, 0), l(1, 0)]),
+    (':', &[m(4, -9), l(4, -8), m(4, -1), l(4, 0)]),
+    ('=', &[m(1, -8), l(7, -8), m(1, -4), l(7, -4)]),
+    ('?', &[m(0, -10), l(2, -12), l(6, -12), l(8, -10), l(8, -8), l(4, -5), l(4, -4), m(4, -1), l(4, 0)]),
+    ('A', &[m(0, 0), l(4, -12), l(8, 0), m(2, -6), l(6, -6)]),
+    ('B', &[m(0, 0), l(0, -12), l(6, -12), l(8, -10), l(8, -8), l(6, -6), l(0, -6), m(6, -6), l(8, -4), l(8, -2), l(6, 0), l(0, 0)]),
+    ('C', &[m(8, -10), l(6, -12), l(2, -12), l(0, -10), l(0, -2), l(2, 0), l(6, 0), l(8, -2)]),
+    ('D', &[m(0, 0), l(0, -12), l(5, -12), l(8, -9), l(8, -3), l(5, 0), l(0, 0)]),
+    ('E', &[m(8, -12), l(0, -12), l(0, 0), l(8, 0), m(0, -6), l(5, -6)]),
+    ('F', &[m(8, -12), l(0, -12), l(0, 0), m(0, -6), l(5, -6)]),
+    ('G', &[m(8, -10), l(6, -12), l(2, -12), l(0, -10), l(0, -2), l(2, 0), l(6, 0), l(8, -2), l(8, -5), l(5, -5)]),
+    ('H', &[m(0, 0), l(0, -12), m(8, 0), l(8, -12), m(0, -6), l(8, -6)]),
+    ('I', &[m(2, -12), l(6, -12), m(4, -12), l(4, 0), m(2, 0), l(6, 0)]),
+    ('J', &[m(8, -12), l(8, -2), l(6, 0), l(2, 0), l(0, -2)]),
+    ('K', &[m(0, 0), l(0, -12), m(8, -12), l(0, -4), m(3, -7), l(8, 0)]),
+    ('L', &[m(0, -12), l(0, 0), l(8, 0)]),
+    ('M', &[m(0, 0), l(0, -12), l(4, -6), l(8, -12), l(8, 0)]),
+    ('N', &[m(0, 0), l(0, -12), l(8, 0), l(8, -12)]),
+    ('O', &[m(2, -12), l(6, -12), l(8, -10), l(8, -2), l(6, 0), l(2, 0), l(0, -2), l(0, -10), l(2, -12)]),
+    ('P', &[m(0, 0), l(0, -12), l(6, -12), l(8, -10), l(8, -8), l(6, -6), l(0, -6)]),
+    ('Q', &[m(2, -12), l(6, -12), l(8, -10), l(8, -2), l(6, 0), l(2, 0), l(0, -2), l(0, -10), l(2, -12), m(5, -3), l(8, 0)]),
+    ('R', &[m(0, 0), l(0, -12), l(6, -12), l(8, -10), l(8, -8), l(6, -6), l(0, -6), m(4, -6), l(8, 0)]),
+    ('S', &[m(8, -10), l(6, -12), l(2, -12), l(0, -10), l(0, -8), l(2, -6), l(6, -6), l(8, -4), l(8, -2), l(6, 0), l(2, 0), l(0, -2)]),
+    ('T', &[m(0, -12), l(8, -12), m(4, -12), l(4, 0)]),
+    ('U', &[m(0, -12), l(0, -2), l(2, 0), l(6, 0), l(8, -2), l(8, -12)]),
+    ('V', &[m(0, -12), l(4, 0), l(8, -12)]),
+    ('W', &[m(0, -12), l(2, 0), l(4, -6), l(6, 0), l(8, -12)]),
+    ('X', &[m(0, -12), l(8, 0), m(8, -12), l(0, 0)]),
+    ('Y', &[m(0, -12), l(4, -6), l(8, -12), m(4, -6), l(4, 0)]),
+    ('Z', &[m(0, -12), l(8, -12), l(0, 0), l(8, 0)]),
+];
+
+impl MinimalFont {
+    /// Iterate over the characters this font has glyphs for, in increasing order.
+    pub fn chars(&self) -> impl Iterator<Item = char> {
+        GLYPHS.iter().map(|&(c, _)| c).chain('a'..='z')
+    }
+}
+
+impl Font for MinimalFont {
+    fn glyph(&self, character: char) -> Option<Glyph<'_>> {
+        let character = character.to_ascii_uppercase();
+        let i = GLYPHS.binary_search_by_key(&character, |&(c, _)| c).ok()?;
+
+        Some(Glyph {
+            left: -SIDE_BEARING,
+            right: 8 + SIDE_BEARING,
+            strokes: Strokes::Points(GLYPHS[i].1),
+            anchors: &[],
+        })
+    }
+
+    fn metrics(&self) -> FontMetrics {
+        FontMetrics {
+            baseline: 0,
+            cap_height: 12,
+            // Lowercase letters are drawn as capitals
+            x_height: 12,
+            ascender: 12,
+            descender: 2,
+            average_advance: 8 + 2 * SIDE_BEARING as i16,
+        }
+    }
+}
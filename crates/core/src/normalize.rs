@@ -0,0 +1,61 @@
+use alloc::collections::VecDeque;
+use core::iter::Peekable;
+
+use unicode_normalization::UnicodeNormalization;
+use unicode_normalization::char::{canonical_combining_class, compose};
+
+/// Normalizes characters with byte offsets to NFC, one cluster at a time.
+///
+/// A cluster is a base character along with any combining characters that follow it.
+/// Every character produced from a cluster is given the byte offset of the start of the
+/// cluster, so that offsets still point into the original text.
+pub(crate) struct Nfc<I: Iterator<Item = (usize, char)>> {
+    chars: Peekable<I>,
+    /// Normalized characters from the current cluster, not yet returned
+    pending: VecDeque<char>,
+    /// Byte offset of the current cluster
+    offset: usize,
+    /// Whether to normalize at all, rather than passing the characters through
+    enabled: bool,
+}
+
+impl<I: Iterator<Item = (usize, char)>> Nfc<I> {
+    pub(crate) fn new(chars: I, enabled: bool) -> Self {
+        Self {
+            chars: chars.peekable(),
+            pending: VecDeque::new(),
+            offset: 0,
+            enabled,
+        }
+    }
+}
+
+impl<I: Iterator<Item = (usize, char)>> Iterator for Nfc<I> {
+    type Item = (usize, char);
+
+    fn next(&mut self) -> Option<(usize, char)> {
+        if !self.enabled {
+            return self.chars.next();
+        }
+
+        if let Some(character) = self.pending.pop_front() {
+            return Some((self.offset, character));
+        }
+
+        let (offset, first) = self.chars.next()?;
+        let mut last = first;
+
+        let cluster = core::iter::once(first).chain(core::iter::from_fn(|| {
+            let (_, c) = self.chars.next_if(|&(_, c)| {
+                canonical_combining_class(c) != 0 || compose(last, c).is_some()
+            })?;
+            last = c;
+            Some(c)
+        }));
+
+        self.pending.extend(cluster.nfc());
+        self.offset = offset;
+
+        self.pending.pop_front().map(|c| (offset, c))
+    }
+}
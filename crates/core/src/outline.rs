@@ -0,0 +1,586 @@
+use alloc::vec::Vec;
+use core::f32::consts::PI;
+
+use crate::Point;
+use crate::path::{round, split_strokes};
+
+/// How [expand_strokes] shapes the corners where a stroke turns, and its ends.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub enum Join {
+    /// Round corners and ends, as drawn by a round pen.
+    #[default]
+    Round,
+    /// Corners cut off straight across, and square ends which reach past the ends of
+    /// the stroke by half its width.
+    Bevel,
+}
+
+/// Options controlling how [expand_strokes] outlines strokes.
+#[derive(Debug, Copy, Clone)]
+pub struct OutlineOptions {
+    /// Width of the strokes, in the same units as the points
+    pub width: f32,
+    /// How corners and ends are shaped
+    pub join: Join,
+    /// Furthest the straight lines making up round corners and ends may stray from a
+    /// true circle. Smaller values give more points.
+    pub tolerance: f32,
+}
+
+impl Default for OutlineOptions {
+    fn default() -> Self {
+        Self {
+            width: 2.0,
+            join: Join::Round,
+            tolerance: 0.25,
+        }
+    }
+}
+
+/// Expand each stroke of rendered text into a closed outline the given width around
+/// it, for filled rendering, cutting out letters, or pocketing them on a CNC router.
+///
+/// Each outline is a point with the pen up followed by points with the pen down, ending
+/// back where it started. A stroke which ends where it starts (like that of an `O`)
+/// gets two outlines, one inside the other, going opposite ways around. Outlines of
+/// strokes which cross or meet are left overlapping rather than merged, but every
+/// outer outline goes the same way around, so the shapes are filled correctly with
+/// the nonzero winding rule.
+///
+/// ```
+/// use vector_text_core::{expand_strokes, Join, OutlineOptions, Point};
+///
+/// let point = |x, y, pen| Point { x, y, pen };
+/// let points = [point(0, 0, false), point(10, 0, true)];
+///
+/// let options = OutlineOptions { width: 4.0, join: Join::Bevel, ..Default::default() };
+/// let outline = expand_strokes(&points, &options);
+///
+/// // A rectangle, reaching 2 units past each end of the line
+/// assert_eq!(outline.iter().map(|p| p.x).min(), Some(-2));
+/// assert_eq!(outline.iter().map(|p| p.x).max(), Some(12));
+/// assert_eq!(outline.iter().map(|p| p.y).max(), Some(2));
+/// ```
+pub fn expand_strokes(points: &[Point], options: &OutlineOptions) -> Vec<Point> {
+    let mut result = Vec::new();
+    let outliner = Outliner {
+        radius: options.width / 2.0,
+        join: options.join,
+        tolerance: options.tolerance,
+    };
+
+    for stroke in split_strokes(points) {
+        let mut path: Vec<(f32, f32)> = stroke.iter().map(|p| (p.x as f32, p.y as f32)).collect();
+        path.dedup();
+
+        let mut outline = Vec::new();
+        if path.len() == 1 {
+            outliner.dot(path[0], &mut outline);
+            push_loop(&outline, &mut result);
+        } else if path.len() > 3 && path.first() == path.last() {
+            path.pop();
+            outliner.side_loop(&path, &mut outline);
+            let mut inner = Vec::new();
+            path.reverse();
+            outliner.side_loop(&path, &mut inner);
+
+            // Outer outlines go the same way around as those of other strokes
+            if area(&outline).abs() < area(&inner).abs() {
+                core::mem::swap(&mut outline, &mut inner);
+            }
+            if area(&outline) > 0.0 {
+                outline.reverse();
+                inner.reverse();
+            }
+
+            push_loop(&outline, &mut result);
+            push_loop(&inner, &mut result);
+        } else {
+            outliner.side(&path, &mut outline);
+            path.reverse();
+            outliner.side(&path, &mut outline);
+            push_loop(&outline, &mut result);
+        }
+    }
+
+    result
+}
+
+/// Move rendered strokes in by the radius of the tool drawing them, so that an engraving
+/// bit or thick pen, which widens every stroke, cuts letters the intended size.
+///
+/// Open strokes are shortened by the radius at each end, so that the round end of the
+/// cut stops where the stroke did. A stroke shorter than the tool is wide becomes a
+/// single spot at its middle. Strokes which end where they start are taken as outlines,
+/// such as those from [expand_strokes], and moved inward by the radius, toward the side
+/// of them which is filled, so that the tool stays within the shapes. An outline too
+/// small for the tool to fit inside is left out.
+///
+/// ```
+/// use vector_text_core::{compensate_tool, Point};
+///
+/// let point = |x, y, pen| Point { x, y, pen };
+/// let points = [point(0, 0, false), point(10, 0, true)];
+///
+/// let compensated = compensate_tool(&points, 1.5);
+/// assert_eq!((compensated[0].x, compensated[1].x), (2, 9));
+/// ```
+pub fn compensate_tool(points: &[Point], radius: f32) -> Vec<Point> {
+    if radius.is_nan() || radius <= 0.0 {
+        return points.to_vec();
+    }
+
+    let mut result = Vec::new();
+    let outliner = Outliner {
+        radius,
+        join: Join::Round,
+        tolerance: OutlineOptions::default().tolerance,
+    };
+
+    for stroke in split_strokes(points) {
+        let mut path: Vec<(f32, f32)> = stroke.iter().map(|p| (p.x as f32, p.y as f32)).collect();
+        path.dedup();
+
+        if path.len() > 3 && path.first() == path.last() {
+            path.pop();
+            let mut outline = Vec::new();
+            outliner.inset(&path, &mut outline);
+
+            // A shape too small for the tool turns inside out
+            if area(&outline) * area(&path) > 0.0 {
+                push_loop(&outline, &mut result);
+            }
+        } else {
+            let start = result.len();
+            for (i, (x, y)) in trim(&path, radius).into_iter().enumerate() {
+                result.push(Point {
+                    x: round(x),
+                    y: round(y),
+                    pen: i > 0,
+                });
+            }
+            if result.len() == start + 1 {
+                // Still cut a spot for the stroke
+                result.push(Point {
+                    pen: true,
+                    ..result[start]
+                });
+            }
+        }
+    }
+
+    result
+}
+
+/// Draw each stroke twice, `spacing` apart, for heavier text from fonts which only
+/// have single-stroke (simplex) faces. This looks much like the duplex Hershey fonts.
+///
+/// The two copies are each moved half of `spacing` to either side of the stroke, with
+/// sharp corners cut off where they would reach far past it. The second copy is drawn
+/// backwards, starting near where the first ends.
+///
+/// ```
+/// use vector_text_core::{double_strokes, Point};
+///
+/// let point = |x, y, pen| Point { x, y, pen };
+/// let points = [point(0, 0, false), point(10, 0, true)];
+///
+/// let doubled = double_strokes(&points, 2.0);
+/// assert_eq!(doubled.len(), 4);
+/// assert_eq!((doubled[0].y, doubled[2].y), (1, -1));
+/// ```
+pub fn double_strokes(points: &[Point], spacing: f32) -> Vec<Point> {
+    if spacing.is_nan() || spacing <= 0.0 {
+        return points.to_vec();
+    }
+
+    let mut result = Vec::with_capacity(points.len() * 2);
+    let outliner = Outliner {
+        radius: spacing / 2.0,
+        join: Join::Bevel,
+        tolerance: OutlineOptions::default().tolerance,
+    };
+
+    let mut push_line = |line: &[(f32, f32)]| {
+        for (i, &(x, y)) in line.iter().enumerate() {
+            result.push(Point {
+                x: round(x),
+                y: round(y),
+                pen: i > 0,
+            });
+        }
+    };
+
+    for stroke in split_strokes(points) {
+        let mut path: Vec<(f32, f32)> = stroke.iter().map(|p| (p.x as f32, p.y as f32)).collect();
+        path.dedup();
+
+        if path.len() == 1 {
+            // Nothing to go either side of
+            push_line(&[path[0], path[0]]);
+            continue;
+        }
+
+        let closed = path.len() > 3 && path.first() == path.last();
+        if closed {
+            path.pop();
+        }
+
+        for _ in 0..2 {
+            let mut line = Vec::new();
+            outliner.offset(&path, closed, &mut line);
+            if closed {
+                line.push(line[0]);
+            }
+            push_line(&line);
+            path.reverse();
+        }
+    }
+
+    result
+}
+
+/// Shorten an open path by `by` at each end, leaving a single point at its middle if
+/// it isn't long enough
+fn trim(path: &[(f32, f32)], by: f32) -> Vec<(f32, f32)> {
+    let lengths: Vec<f32> = path
+        .windows(2)
+        .map(|w| libm::hypotf(w[1].0 - w[0].0, w[1].1 - w[0].1))
+        .collect();
+    let total: f32 = lengths.iter().sum();
+
+    // The point a distance along the path, and the index of the segment it's on
+    let along = |distance: f32| {
+        let mut remaining = distance;
+        for (i, &length) in lengths.iter().enumerate() {
+            if remaining <= length || i == lengths.len() - 1 {
+                let t = if length > 0.0 {
+                    remaining / length
+                } else {
+                    0.0
+                };
+                let (a, b) = (path[i], path[i + 1]);
+                return (i, (a.0 + (b.0 - a.0) * t, a.1 + (b.1 - a.1) * t));
+            }
+            remaining -= length;
+        }
+        (0, path[0])
+    };
+
+    if total <= 2.0 * by {
+        return alloc::vec![along(total / 2.0).1];
+    }
+
+    let (first, start) = along(by);
+    let (last, end) = along(total - by);
+
+    let mut trimmed = alloc::vec![start];
+    trimmed.extend_from_slice(&path[first + 1..=last]);
+    trimmed.push(end);
+    trimmed
+}
+
+/// Traces the outlines of strokes, as a polygon of points in order around them.
+struct Outliner {
+    radius: f32,
+    join: Join,
+    tolerance: f32,
+}
+
+impl Outliner {
+    /// Trace along the left of an open path (as it goes), then around its end
+    fn side(&self, path: &[(f32, f32)], out: &mut Vec<(f32, f32)>) {
+        let normals: Vec<(f32, f32)> = path.windows(2).map(|w| self.normal(w[0], w[1])).collect();
+
+        out.push(add(path[0], normals[0]));
+        for corner in path.windows(3) {
+            self.join(corner[0], corner[1], corner[2], out);
+        }
+
+        // Go around the end, to the right of the path
+        let end = path[path.len() - 1];
+        let last = normals[normals.len() - 1];
+        let ahead = scale(direction(path[path.len() - 2], end), self.radius);
+        out.push(add(end, last));
+        match self.join {
+            Join::Round => self.arc(end, last, neg(last), ahead, out),
+            Join::Bevel => {
+                out.push(add(add(end, last), ahead));
+                out.push(add(sub(end, last), ahead));
+            }
+        }
+    }
+
+    /// Trace along the left of a closed path (as it goes), whose last point joins its
+    /// first
+    fn side_loop(&self, path: &[(f32, f32)], out: &mut Vec<(f32, f32)>) {
+        for i in 0..path.len() {
+            let previous = path[(i + path.len() - 1) % path.len()];
+            self.join(previous, path[i], path[(i + 1) % path.len()], out);
+        }
+    }
+
+    /// Trace a line the radius to the left of a path (as it goes), with corners mitred
+    /// unless they are too sharp. If `closed`, the path's last point joins its first.
+    fn offset(&self, path: &[(f32, f32)], closed: bool, out: &mut Vec<(f32, f32)>) {
+        let len = path.len();
+        let r2 = self.radius * self.radius;
+
+        for i in 0..len {
+            let previous = (closed || i > 0).then(|| path[(i + len - 1) % len]);
+            let next = (closed || i < len - 1).then(|| path[(i + 1) % len]);
+
+            match (previous, next) {
+                (Some(previous), Some(next)) => {
+                    let (from, to) = (self.normal(previous, path[i]), self.normal(path[i], next));
+                    let miter = scale(add(from, to), r2 / (r2 + dot(from, to)).max(1e-6));
+
+                    if dot(miter, miter) <= 4.0 * r2 {
+                        out.push(add(path[i], miter));
+                    } else {
+                        out.extend([add(path[i], from), add(path[i], to)]);
+                    }
+                }
+                (None, Some(next)) => out.push(add(path[i], self.normal(path[i], next))),
+                (Some(previous), None) => out.push(add(path[i], self.normal(previous, path[i]))),
+                (None, None) => {}
+            }
+        }
+    }
+
+    /// Trace the path of a tool of the radius inside a closed outline, whose last point
+    /// joins its first
+    fn inset(&self, path: &[(f32, f32)], out: &mut Vec<(f32, f32)>) {
+        let next = |i: usize| path[(i + 1) % path.len()];
+        // The inside is to the right of outlines, as they go
+        let normals: Vec<(f32, f32)> = (0..path.len())
+            .map(|i| neg(self.normal(path[i], next(i))))
+            .collect();
+        let r2 = self.radius * self.radius;
+
+        for i in 0..path.len() {
+            let previous = (i + path.len() - 1) % path.len();
+            let (from, to) = (normals[previous], normals[i]);
+            let (before, after) = (
+                direction(path[previous], path[i]),
+                direction(path[i], next(i)),
+            );
+
+            if -dot(from, after) > 0.0 {
+                // Turning away from the inside, so the tool rolls around the corner
+                out.push(add(path[i], from));
+                self.arc(path[i], from, to, sub(before, after), out);
+            } else {
+                // Turning toward it, so the tool goes into the corner as far as it fits,
+                // within reason for the sharpest ones
+                let miter = scale(add(from, to), r2 / (r2 + dot(from, to)).max(r2 / 16.0));
+                out.push(add(path[i], miter));
+            }
+        }
+    }
+
+    /// Trace around the corner at `at`, where the path turns between the segments from
+    /// `previous` and to `next`
+    fn join(
+        &self,
+        previous: (f32, f32),
+        at: (f32, f32),
+        next: (f32, f32),
+        out: &mut Vec<(f32, f32)>,
+    ) {
+        let (from, to) = (self.normal(previous, at), self.normal(at, next));
+        let (before, after) = (direction(previous, at), direction(at, next));
+
+        // How far the path turns away from this side, or toward it if negative
+        let away = -dot(from, after) / self.radius;
+        if away.abs() < 1e-4 && dot(before, after) > 0.0 {
+            // Straight on
+            out.push(add(at, from));
+            return;
+        }
+
+        if away > 0.0 || away.abs() < 1e-4 {
+            // The outside of the turn, or straight back the way it came
+            out.push(add(at, from));
+            match self.join {
+                // Bulging out away from where the path turns
+                Join::Round => self.arc(at, from, to, sub(before, after), out),
+                Join::Bevel => out.push(add(at, to)),
+            }
+            return;
+        }
+
+        // The inside of the turn, where the edges of the segments cross
+        let r2 = self.radius * self.radius;
+        let miter = scale(add(from, to), r2 / (r2 + dot(from, to)));
+        let reach = libm::hypotf(at.0 - previous.0, at.1 - previous.1)
+            .min(libm::hypotf(next.0 - at.0, next.1 - at.1));
+
+        if dot(miter, before).abs() <= reach {
+            out.push(add(at, miter));
+        } else {
+            // The segments are too short for the edges to cross beside them, so go
+            // through the corner, which keeps the whole of the stroke covered
+            out.extend([add(at, from), at, add(at, to)]);
+        }
+    }
+
+    /// Trace an arc around `center` from offset `from` to offset `to`, going the way
+    /// which passes `through`. The first point is left out, as it is already traced.
+    fn arc(
+        &self,
+        center: (f32, f32),
+        from: (f32, f32),
+        to: (f32, f32),
+        through: (f32, f32),
+        out: &mut Vec<(f32, f32)>,
+    ) {
+        let start = libm::atan2f(from.1, from.0);
+        let mut sweep = libm::atan2f(to.1, to.0) - start;
+        // Go round the way which passes `through`
+        let ahead = libm::atan2f(through.1, through.0) - start;
+        let (sweep_turns, ahead_turns) = (wrap(sweep), wrap(ahead));
+        if ahead_turns > sweep_turns {
+            sweep = sweep_turns - 2.0 * PI;
+        } else {
+            sweep = sweep_turns;
+        }
+
+        let steps = (libm::ceilf(sweep.abs() / self.step()) as usize).max(1);
+        for i in 1..=steps {
+            let angle = start + sweep * i as f32 / steps as f32;
+            out.push((
+                center.0 + self.radius * libm::cosf(angle),
+                center.1 + self.radius * libm::sinf(angle),
+            ));
+        }
+    }
+
+    /// Trace the outline of a stroke which doesn't move
+    fn dot(&self, at: (f32, f32), out: &mut Vec<(f32, f32)>) {
+        let r = self.radius;
+        match self.join {
+            Join::Round => {
+                out.push((at.0 + r, at.1));
+                self.arc(at, (r, 0.0), (r, 0.0), (0.0, -r), out);
+                out.pop();
+            }
+            Join::Bevel => {
+                out.extend([
+                    (at.0 - r, at.1 - r),
+                    (at.0 - r, at.1 + r),
+                    (at.0 + r, at.1 + r),
+                    (at.0 + r, at.1 - r),
+                ]);
+            }
+        }
+    }
+
+    /// Largest angle between points along an arc, to stay within the tolerance
+    fn step(&self) -> f32 {
+        if self.tolerance <= 0.0 || self.radius <= 0.0 {
+            return PI / 16.0;
+        }
+        if self.tolerance >= self.radius {
+            return PI / 2.0;
+        }
+        (2.0 * libm::acosf(1.0 - self.tolerance / self.radius)).clamp(PI / 64.0, PI / 2.0)
+    }
+
+    /// Offset to the left of the segment from `a` to `b`, as far as the radius
+    fn normal(&self, a: (f32, f32), b: (f32, f32)) -> (f32, f32) {
+        let (dx, dy) = direction(a, b);
+        (-dy * self.radius, dx * self.radius)
+    }
+}
+
+/// Add a closed outline to the result, as a move followed by lines back to its start
+fn push_loop(outline: &[(f32, f32)], out: &mut Vec<Point>) {
+    let Some(&first) = outline.first() else {
+        return;
+    };
+
+    let point = |(x, y): (f32, f32), pen| Point {
+        x: round(x),
+        y: round(y),
+        pen,
+    };
+
+    let start = out.len();
+    out.push(point(first, false));
+    for &position in outline[1..].iter().chain([&first]) {
+        let next = point(position, true);
+        let len = out.len() - start;
+
+        // Rounding can put neighbouring points in the same place
+        if (out[out.len() - 1].x, out[out.len() - 1].y) == (next.x, next.y) {
+            continue;
+        }
+        // Leave out points in the middle of straight lines
+        if len >= 2 && in_line(out[out.len() - 2], out[out.len() - 1], next) {
+            out.pop();
+        }
+        out.push(next);
+    }
+
+    if out.len() - start < 3 {
+        // Too small to draw anything after rounding
+        out.truncate(start);
+    }
+}
+
+/// Is `b` on the straight line from `a` to `c`, between them?
+fn in_line(a: Point, b: Point, c: Point) -> bool {
+    let (ab, bc) = (
+        (b.x as i32 - a.x as i32, b.y as i32 - a.y as i32),
+        (c.x as i32 - b.x as i32, c.y as i32 - b.y as i32),
+    );
+    ab.0 * bc.1 == ab.1 * bc.0 && ab.0 * bc.0 + ab.1 * bc.1 > 0
+}
+
+/// Twice the signed area of a polygon
+fn area(outline: &[(f32, f32)]) -> f32 {
+    let mut total = 0.0;
+    for (i, &(x, y)) in outline.iter().enumerate() {
+        let (nx, ny) = outline[(i + 1) % outline.len()];
+        total += x * ny - nx * y;
+    }
+    total
+}
+
+/// An angle wrapped into `0..2π`
+fn wrap(angle: f32) -> f32 {
+    let wrapped = angle % (2.0 * PI);
+    if wrapped < 0.0 {
+        wrapped + 2.0 * PI
+    } else {
+        wrapped
+    }
+}
+
+/// Unit vector from `a` toward `b`
+fn direction(a: (f32, f32), b: (f32, f32)) -> (f32, f32) {
+    let (dx, dy) = sub(b, a);
+    let length = libm::hypotf(dx, dy);
+    (dx / length, dy / length)
+}
+
+fn add(a: (f32, f32), b: (f32, f32)) -> (f32, f32) {
+    (a.0 + b.0, a.1 + b.1)
+}
+
+fn sub(a: (f32, f32), b: (f32, f32)) -> (f32, f32) {
+    (a.0 - b.0, a.1 - b.1)
+}
+
+fn neg(a: (f32, f32)) -> (f32, f32) {
+    (-a.0, -a.1)
+}
+
+fn scale(a: (f32, f32), by: f32) -> (f32, f32) {
+    (a.0 * by, a.1 * by)
+}
+
+fn dot(a: (f32, f32), b: (f32, f32)) -> f32 {
+    a.0 * b.0 + a.1 * b.1
+}
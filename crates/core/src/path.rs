@@ -0,0 +1,617 @@
+use alloc::collections::BTreeSet;
+use alloc::vec::Vec;
+
+use crate::Point;
+
+/// Options controlling how [optimize_travel] reorders strokes.
+#[derive(Debug, Copy, Clone, Default)]
+pub struct TravelOptions {
+    /// After choosing strokes greedily, improve the order with 2-opt: reverse runs of
+    /// strokes wherever that shortens the travel between them. Slower, but usually finds
+    /// a shorter path.
+    pub two_opt: bool,
+    /// Let strokes be drawn backwards, when that starts them nearer to where the pen is.
+    pub reverse: bool,
+}
+
+/// Most passes 2-opt makes over the strokes, to bound the time taken on long text
+const MAX_PASSES: usize = 16;
+
+/// Where the pen starts and ends drawing a stroke.
+#[derive(Copy, Clone)]
+struct Ends {
+    start: (i32, i32),
+    end: (i32, i32),
+}
+
+/// A stroke in the order they're drawn, and which way it's drawn.
+#[derive(Copy, Clone)]
+struct Visit {
+    stroke: usize,
+    reversed: bool,
+}
+
+impl Visit {
+    /// Where the pen starts and ends drawing this stroke in its direction
+    fn ends(self, ends: &[Ends]) -> Ends {
+        let Ends { start, end } = ends[self.stroke];
+        if self.reversed {
+            Ends {
+                start: end,
+                end: start,
+            }
+        } else {
+            Ends { start, end }
+        }
+    }
+
+    /// This visit, drawn the other way if strokes may be reversed
+    fn turned(self, reverse: bool) -> Self {
+        Self {
+            stroke: self.stroke,
+            reversed: self.reversed != reverse,
+        }
+    }
+}
+
+/// Reorder rendered text so that the pen travels less distance while lifted, such as
+/// to cut the time a pen plotter spends moving between strokes.
+///
+/// A stroke is a point with the pen up, followed by the points drawn from it with the
+/// pen down. Starting from the origin, the next stroke is always the one starting
+/// nearest to where the last one ended. This order is only used if its total travel is
+/// shorter than that of the text's own order, which is kept otherwise. Every stroke is
+/// drawn just as before, only in a different order, and moves which draw nothing are
+/// left out.
+///
+/// With [TravelOptions::reverse], a stroke may be drawn from its last point to its
+/// first instead. Its points are then output in reverse, starting with the pen up at
+/// its last point.
+///
+/// ```
+/// use vector_text_core::{optimize_travel, Point, TravelOptions};
+///
+/// let point = |x, y, pen| Point { x, y, pen };
+/// let points = [
+///     point(10, 0, false),
+///     point(11, 0, true),
+///     point(0, 0, false),
+///     point(1, 0, true),
+/// ];
+///
+/// let optimized = optimize_travel(&points, &TravelOptions::default());
+/// assert_eq!(optimized[0].x, 0);
+/// assert_eq!(optimized[2].x, 10);
+/// ```
+pub fn optimize_travel(points: &[Point], options: &TravelOptions) -> Vec<Point> {
+    // Lines drawn before the first move start wherever the pen already is, so stay first
+    let lead = points.iter().take_while(|p| p.pen).count();
+    let (lead, rest) = points.split_at(lead);
+
+    let strokes: Vec<&[Point]> = rest
+        .chunk_by(|_, next| next.pen)
+        .filter(|stroke| stroke.len() > 1)
+        .collect();
+
+    let ends: Vec<Ends> = strokes
+        .iter()
+        .map(|stroke| Ends {
+            start: position(&stroke[0]),
+            end: position(&stroke[stroke.len() - 1]),
+        })
+        .collect();
+
+    let from = lead.last().map_or((0, 0), position);
+
+    // Choosing greedily can do worse than the text's own order, so keep whichever is shorter
+    let mut order = nearest_neighbor(&ends, from, options.reverse);
+    let original: Vec<Visit> = (0..ends.len())
+        .map(|stroke| Visit {
+            stroke,
+            reversed: false,
+        })
+        .collect();
+    if travel(&ends, &original, from) <= travel(&ends, &order, from) {
+        order = original;
+    }
+
+    if options.two_opt {
+        two_opt(&ends, &mut order, from, options.reverse);
+    }
+
+    let mut result =
+        Vec::with_capacity(lead.len() + strokes.iter().map(|s| s.len()).sum::<usize>());
+    result.extend_from_slice(lead);
+    for visit in order {
+        let stroke = strokes[visit.stroke];
+        if visit.reversed {
+            result.extend(stroke.iter().rev().enumerate().map(|(i, point)| Point {
+                pen: i > 0,
+                ..*point
+            }));
+        } else {
+            result.extend_from_slice(stroke);
+        }
+    }
+
+    result
+}
+
+pub(crate) fn position(point: &Point) -> (i32, i32) {
+    (point.x as i32, point.y as i32)
+}
+
+/// Distance between two positions, in sixteenths of a unit
+pub(crate) fn distance(a: (i32, i32), b: (i32, i32)) -> u64 {
+    let dx = (a.0 - b.0) as i64;
+    let dy = (a.1 - b.1) as i64;
+    ((dx * dx + dy * dy) as u64 * 256).isqrt()
+}
+
+/// Total distance travelled between strokes when drawn in the given order
+fn travel(ends: &[Ends], order: &[Visit], mut from: (i32, i32)) -> u64 {
+    let mut total = 0;
+    for visit in order {
+        let Ends { start, end } = visit.ends(ends);
+        total += distance(from, start);
+        from = end;
+    }
+    total
+}
+
+/// Order the strokes by repeatedly drawing whichever starts nearest the pen, from
+/// either end if `reverse` is set.
+fn nearest_neighbor(ends: &[Ends], mut from: (i32, i32), reverse: bool) -> Vec<Visit> {
+    let mut remaining: Vec<usize> = (0..ends.len()).collect();
+    let mut order = Vec::with_capacity(ends.len());
+
+    while let Some((k, visit)) = remaining
+        .iter()
+        .enumerate()
+        .flat_map(|(k, &stroke)| {
+            let forward = Visit {
+                stroke,
+                reversed: false,
+            };
+            [
+                Some((k, forward)),
+                reverse.then(|| (k, forward.turned(true))),
+            ]
+        })
+        .flatten()
+        .min_by_key(|(_, visit)| distance(from, visit.ends(ends).start))
+    {
+        // Removing in place keeps ties in their original order
+        remaining.remove(k);
+        from = visit.ends(ends).end;
+        order.push(visit);
+    }
+
+    order
+}
+
+/// Reverse runs of strokes in the order wherever that shortens the travel, until no
+/// run does (or enough passes have been made). If `reverse` is set, the strokes of a
+/// reversed run are drawn backwards too.
+fn two_opt(ends: &[Ends], order: &mut [Visit], from: (i32, i32), reverse: bool) {
+    for _ in 0..MAX_PASSES {
+        let mut improved = false;
+
+        for i in 0..order.len() {
+            let before = if i == 0 {
+                from
+            } else {
+                order[i - 1].ends(ends).end
+            };
+
+            // Travel between the strokes of the run order[i..=j], drawn forwards and reversed
+            let mut forward = 0;
+            let mut reversed = 0;
+
+            for j in i + 1..order.len() {
+                forward += distance(order[j - 1].ends(ends).end, order[j].ends(ends).start);
+                reversed += distance(
+                    order[j].turned(reverse).ends(ends).end,
+                    order[j - 1].turned(reverse).ends(ends).start,
+                );
+
+                let after = order.get(j + 1).map(|visit| visit.ends(ends).start);
+                let leave = |visit: Visit| after.map_or(0, |a| distance(visit.ends(ends).end, a));
+
+                let first = order[i].turned(reverse);
+                let last = order[j].turned(reverse);
+
+                let old = distance(before, order[i].ends(ends).start) + forward + leave(order[j]);
+                let new = distance(before, last.ends(ends).start) + reversed + leave(first);
+
+                if new < old {
+                    order[i..=j].reverse();
+                    for visit in &mut order[i..=j] {
+                        *visit = visit.turned(reverse);
+                    }
+                    improved = true;
+                    break;
+                }
+            }
+        }
+
+        if !improved {
+            break;
+        }
+    }
+}
+
+/// Remove lines drawn more than once, such as where a font retraces part of a glyph,
+/// so that a laser engraver doesn't burn them twice.
+///
+/// A line is a duplicate if it joins the same two points as one drawn earlier, in
+/// either direction. The pen is lifted over it instead, and moves which end up not
+/// drawing anything are left out.
+///
+/// ```
+/// use vector_text_core::{remove_duplicate_segments, Point};
+///
+/// let point = |x, y, pen| Point { x, y, pen };
+/// let points = [
+///     point(0, 0, false),
+///     point(1, 0, true),
+///     point(0, 0, true),
+///     point(0, 1, true),
+/// ];
+///
+/// let deduplicated = remove_duplicate_segments(&points);
+/// assert_eq!(deduplicated.iter().filter(|p| p.pen).count(), 2);
+/// ```
+pub fn remove_duplicate_segments(points: &[Point]) -> Vec<Point> {
+    let mut drawn = BTreeSet::new();
+    let mut result: Vec<Point> = Vec::with_capacity(points.len());
+
+    for (i, &point) in points.iter().enumerate() {
+        let mut point = point;
+
+        if point.pen && i > 0 {
+            let (a, b) = (position(&points[i - 1]), position(&point));
+            if !drawn.insert(if a <= b { (a, b) } else { (b, a) }) {
+                point.pen = false;
+            }
+        }
+
+        // A move straight after another replaces it
+        if !point.pen && result.last().is_some_and(|last| !last.pen) {
+            result.pop();
+        }
+
+        result.push(point);
+    }
+
+    if result.last().is_some_and(|last| !last.pen) {
+        result.pop();
+    }
+
+    result
+}
+
+/// Simplify each stroke with the Ramer–Douglas–Peucker algorithm, to send fewer points
+/// to devices with little bandwidth, such as laser DACs or slow serial plotters.
+///
+/// Points are removed wherever the line would move by no more than `tolerance`. The
+/// first and last point of every stroke are kept, so strokes still meet where they
+/// did.
+///
+/// ```
+/// use vector_text_core::{simplify, Point};
+///
+/// let point = |x, y, pen| Point { x, y, pen };
+/// let points = [
+///     point(0, 0, false),
+///     point(5, 1, true),
+///     point(10, 0, true),
+///     point(10, 10, true),
+/// ];
+///
+/// assert_eq!(simplify(&points, 1.0).len(), 3);
+/// assert_eq!(simplify(&points, 0.5).len(), 4);
+/// ```
+pub fn simplify(points: &[Point], tolerance: f32) -> Vec<Point> {
+    let mut result = Vec::with_capacity(points.len());
+    let mut keep = Vec::new();
+    let mut pending = Vec::new();
+
+    // Each stroke is a point followed by the points drawn from it
+    for stroke in points.chunk_by(|_, next| next.pen) {
+        keep.clear();
+        keep.resize(stroke.len(), false);
+        keep[0] = true;
+        keep[stroke.len() - 1] = true;
+
+        pending.push((0, stroke.len() - 1));
+        while let Some((first, last)) = pending.pop() {
+            let farthest = (first + 1..last).max_by(|&a, &b| {
+                let a = offset_squared(&stroke[a], &stroke[first], &stroke[last]);
+                let b = offset_squared(&stroke[b], &stroke[first], &stroke[last]);
+                a.total_cmp(&b)
+            });
+
+            if let Some(i) = farthest
+                && offset_squared(&stroke[i], &stroke[first], &stroke[last])
+                    > tolerance as f64 * tolerance as f64
+            {
+                keep[i] = true;
+                pending.push((first, i));
+                pending.push((i, last));
+            }
+        }
+
+        result.extend(
+            stroke
+                .iter()
+                .zip(&keep)
+                .filter(|(_, keep)| **keep)
+                .map(|(point, _)| *point),
+        );
+    }
+
+    result
+}
+
+/// Squared distance from `point` to the line through `a` and `b` (or to `a`, if they're
+/// the same point)
+fn offset_squared(point: &Point, a: &Point, b: &Point) -> f64 {
+    let (px, py) = position(point);
+    let (ax, ay) = position(a);
+    let (bx, by) = position(b);
+
+    let (dx, dy) = ((bx - ax) as f64, (by - ay) as f64);
+    let (ex, ey) = ((px - ax) as f64, (py - ay) as f64);
+
+    let length_squared = dx * dx + dy * dy;
+    if length_squared == 0.0 {
+        ex * ex + ey * ey
+    } else {
+        let cross = dx * ey - dy * ex;
+        cross * cross / length_squared
+    }
+}
+
+/// Round off the corners of each stroke by Chaikin's corner-cutting algorithm, so that
+/// large text gets gentle curves instead of sharp corners.
+///
+/// Each iteration replaces every corner with two points a quarter of the way along the
+/// lines either side of it, so more iterations give smoother (but longer) strokes. The
+/// ends of a stroke stay where they are, unless it is closed (ends where it started),
+/// in which case the corner there is rounded off too.
+///
+/// Points are rounded to whole units, so any scaling should be done beforehand: at the
+/// size glyphs are drawn, smoothing more than once or twice changes little.
+///
+/// ```
+/// use vector_text_core::{smooth, Point};
+///
+/// let point = |x, y, pen| Point { x, y, pen };
+/// let points = [point(0, 0, false), point(40, 0, true), point(40, 40, true)];
+///
+/// let smoothed = smooth(&points, 1);
+/// assert_eq!(smoothed.len(), 4);
+/// assert_eq!((smoothed[1].x, smoothed[1].y), (30, 0));
+/// assert_eq!((smoothed[2].x, smoothed[2].y), (40, 10));
+/// ```
+pub fn smooth(points: &[Point], iterations: u8) -> Vec<Point> {
+    let mut result = Vec::with_capacity(points.len());
+    let mut line = Vec::new();
+    let mut cut = Vec::new();
+
+    // Each stroke is a point followed by the points drawn from it
+    for stroke in points.chunk_by(|_, next| next.pen) {
+        line.clear();
+        line.extend(stroke.iter().map(|p| (p.x as f32, p.y as f32)));
+
+        let closed =
+            stroke.len() > 2 && position(&stroke[0]) == position(&stroke[stroke.len() - 1]);
+
+        for _ in 0..iterations {
+            if line.len() < 3 {
+                break;
+            }
+
+            // Every point but the ends of an open stroke is a corner, between its neighbours
+            let n = line.len();
+            let corners = if closed { 0..n - 1 } else { 1..n - 1 };
+
+            cut.clear();
+            if !closed {
+                cut.push(line[0]);
+            }
+
+            for i in corners {
+                let (ax, ay) = line[if i == 0 { n - 2 } else { i - 1 }];
+                let (bx, by) = line[i];
+                let (cx, cy) = line[i + 1];
+                cut.push((0.25 * ax + 0.75 * bx, 0.25 * ay + 0.75 * by));
+                cut.push((0.75 * bx + 0.25 * cx, 0.75 * by + 0.25 * cy));
+            }
+
+            if closed {
+                cut.push(cut[0]);
+            } else {
+                cut.push(line[n - 1]);
+            }
+
+            core::mem::swap(&mut line, &mut cut);
+        }
+
+        let start = result.len();
+        for &(x, y) in &line {
+            let point = Point {
+                x: round(x),
+                y: round(y),
+                pen: result.len() > start || stroke[0].pen,
+            };
+
+            // Rounding can put neighbouring points in the same place
+            if result.len() > start
+                && result
+                    .last()
+                    .is_some_and(|last: &Point| position(last) == position(&point))
+            {
+                continue;
+            }
+
+            result.push(point);
+        }
+    }
+
+    result
+}
+
+/// Round a coordinate to the nearest whole unit
+pub(crate) fn round(value: f32) -> i16 {
+    if value < 0.0 {
+        (value - 0.5) as i16
+    } else {
+        (value + 0.5) as i16
+    }
+}
+
+/// Redraw each stroke with its points spaced evenly along it, such as for an XY
+/// oscilloscope or galvo laser, which draw each point for the same time and so need
+/// them evenly spaced for the line to be evenly bright.
+///
+/// Points are placed every `spacing` units along the stroke, from its first point, and
+/// its last point is kept too. Corners between them are cut, so a small spacing keeps
+/// the shape closest. If `spacing` isn't positive, the points are returned unchanged.
+///
+/// ```
+/// use vector_text_core::{resample, Point};
+///
+/// let point = |x, y, pen| Point { x, y, pen };
+/// let points = [point(0, 0, false), point(10, 0, true), point(10, 10, true)];
+///
+/// let resampled = resample(&points, 2.0);
+/// assert_eq!(resampled.len(), 11);
+/// assert!(resampled.iter().all(|p| p.x % 2 == 0 && p.y % 2 == 0));
+/// ```
+pub fn resample(points: &[Point], spacing: f32) -> Vec<Point> {
+    if spacing.is_nan() || spacing <= 0.0 {
+        return points.to_vec();
+    }
+
+    let mut result = Vec::with_capacity(points.len());
+
+    // Each stroke is a point followed by the points drawn from it
+    for stroke in points.chunk_by(|_, next| next.pen) {
+        result.push(stroke[0]);
+
+        // Distance along the current line to place the next point at
+        let mut next = spacing;
+
+        for pair in stroke.windows(2) {
+            let (a, b) = (position(&pair[0]), position(&pair[1]));
+            let length = distance(a, b) as f32 / 16.0;
+
+            while next <= length {
+                let t = next / length;
+                result.push(Point {
+                    x: round(a.0 as f32 + (b.0 - a.0) as f32 * t),
+                    y: round(a.1 as f32 + (b.1 - a.1) as f32 * t),
+                    pen: true,
+                });
+                next += spacing;
+            }
+
+            next -= length;
+        }
+
+        let last = stroke[stroke.len() - 1];
+        if stroke.len() > 1
+            && result
+                .last()
+                .is_none_or(|point: &Point| position(point) != position(&last))
+        {
+            result.push(last);
+        }
+    }
+
+    result
+}
+
+/// Measurements of the path rendered text takes, for comparing fonts and the passes in
+/// this crate, or estimating how long it will take to plot.
+#[derive(Debug, Copy, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct PathStats {
+    /// Total length of the lines drawn
+    pub pen_down_distance: f32,
+    /// Total distance moved with the pen up, starting from the origin
+    pub pen_up_distance: f32,
+    /// Number of strokes (unbroken runs of lines drawn)
+    pub strokes: usize,
+    /// Number of points
+    pub points: usize,
+}
+
+impl PathStats {
+    /// Measure the path through some points.
+    ///
+    /// ```
+    /// use vector_text_core::{PathStats, Point};
+    ///
+    /// let point = |x, y, pen| Point { x, y, pen };
+    /// let points = [point(0, 3, false), point(4, 0, true), point(4, 10, false), point(4, 20, true)];
+    ///
+    /// let stats = PathStats::from_points(&points);
+    /// assert_eq!(stats.pen_down_distance, 15.0);
+    /// assert_eq!(stats.pen_up_distance, 13.0);
+    /// assert_eq!(stats.strokes, 2);
+    /// assert_eq!(stats.points, 4);
+    /// ```
+    pub fn from_points(points: &[Point]) -> Self {
+        let mut stats = Self {
+            points: points.len(),
+            ..Self::default()
+        };
+
+        let mut from = (0, 0);
+        let mut drawing = false;
+
+        for point in points {
+            let length = distance(from, position(point)) as f32 / 16.0;
+
+            if point.pen {
+                stats.pen_down_distance += length;
+                if !drawing {
+                    stats.strokes += 1;
+                }
+            } else {
+                stats.pen_up_distance += length;
+            }
+
+            from = position(point);
+            drawing = point.pen;
+        }
+
+        stats
+    }
+}
+
+/// Split rendered text into its strokes: runs of points joined by lines, each starting
+/// with the point the pen moves to and continuing with the points drawn from it.
+///
+/// Each stroke is a polyline, so this is the way to hand text to libraries which take
+/// polylines rather than pen moves (as the `dxf` feature of `vector-text` does). Moves
+/// which draw nothing are left out.
+///
+/// ```
+/// use vector_text_core::{split_strokes, Point};
+///
+/// let point = |x, y, pen| Point { x, y, pen };
+/// let points = [point(0, 0, false), point(1, 0, true), point(5, 5, false), point(6, 6, false), point(6, 7, true)];
+///
+/// let strokes: Vec<&[Point]> = split_strokes(&points).collect();
+/// assert_eq!(strokes.len(), 2);
+/// assert_eq!(strokes[1][0].x, 6);
+/// ```
+pub fn split_strokes(points: &[Point]) -> impl Iterator<Item = &[Point]> {
+    points
+        .chunk_by(|_, next| next.pen)
+        .filter(|stroke| stroke.len() > 1)
+}
@@ -0,0 +1,1390 @@
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+use core::ops::Range;
+
+use crate::fallback::spacing_forms;
+use crate::{AnchorKind, FontMetrics, Glyph, Point, RenderError, strip_diacritics};
+
+/// What to do when the font has no glyph for a character.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub enum MissingGlyph {
+    /// Leave the character out entirely.
+    #[default]
+    Skip,
+    /// Stop rendering and return the lookup error.
+    Error,
+    /// Draw this character instead (e.g. `'?'`). If the font lacks it too, the character is skipped.
+    Replace(char),
+    /// Draw an empty box ("tofu") the size of a typical capital letter,
+    /// so that missing coverage stays visible.
+    ReplacementBox,
+}
+
+/// What to do with control characters, other than tabs and line breaks.
+#[derive(Debug, Copy, Clone, Default)]
+pub enum ControlChars {
+    /// Leave the character out entirely.
+    #[default]
+    Skip,
+    /// Stop rendering and return [RenderError::UnsupportedCharacter].
+    Error,
+    /// Look up a glyph for the character like any other.
+    Render,
+    /// Decide per character: draw the returned character instead, or skip it if `None`.
+    ///
+    /// For example, `|c| char::from_u32(0x2400 + c as u32)` draws C0 controls as
+    /// their Unicode "control picture" symbols.
+    Custom(fn(char) -> Option<char>),
+}
+
+/// Where else words may be broken when wrapping lines, besides at soft hyphens.
+///
+/// A hyphen is drawn at the end of a line broken inside a word.
+#[cfg(feature = "alloc")]
+#[derive(Debug, Copy, Clone, Default)]
+pub enum Hyphenation {
+    /// Only break words which are too long to fit on a line by themselves.
+    #[default]
+    None,
+    /// Break the words in this list where they are written with hyphens, like
+    /// `"hy-phen-a-tion"`. Words are matched ignoring case.
+    Dictionary(&'static [&'static str]),
+    /// Decide per word: push the byte offsets in the word where it may be broken.
+    ///
+    /// Words are passed as runs of letters, without any punctuation around them.
+    Custom(fn(&str, &mut Vec<usize>)),
+}
+
+/// Which way y increases in rendered points.
+///
+/// Either way, x increases to the right, and the text is positioned by its
+/// [Origin] first.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub enum YAxis {
+    /// Downward, as on screens and in SVG and most image formats. Text is drawn at
+    /// positive y.
+    #[default]
+    Down,
+    /// Upward, as on plotters, in G-code, and in maths. Text is drawn at negative y.
+    Up,
+}
+
+impl YAxis {
+    /// Convert points with y increasing downward to this convention, or back again.
+    ///
+    /// ```
+    /// use vector_text_core::{Point, YAxis};
+    ///
+    /// let mut points = [Point { x: 1, y: 2, pen: false }];
+    /// YAxis::Up.apply(&mut points);
+    /// assert_eq!(points[0].y, -2);
+    /// ```
+    pub fn apply(self, points: &mut [Point]) {
+        if self == Self::Up {
+            for point in points {
+                point.y = point.y.saturating_neg();
+            }
+        }
+    }
+}
+
+/// Which point of the text is put at (0, 0) in rendered points.
+///
+/// Text is measured by its layout rather than its strokes, so that it is positioned the
+/// same way whichever letters it has: it is as wide as its widest line, advances
+/// included, and reaches from the top of its first line, as high as the font's
+/// [ascender](FontMetrics::ascender) above the baseline, to the bottom of its last, as
+/// low as the [descender](FontMetrics::descender) below it. This holds wherever the
+/// font puts its baseline.
+///
+/// ```
+/// # #[cfg(feature = "alloc")] {
+/// use vector_text_core::{BBox, Font, MinimalFont, Origin, RenderOptions};
+///
+/// let font = MinimalFont;
+/// let render = |origin| {
+///     let options = RenderOptions { origin, ..Default::default() };
+///     BBox::of(&font.render_text_with_options("HI", &options).unwrap()).unwrap()
+/// };
+///
+/// // The bottoms of the letters sit on the baseline, either side of 0
+/// let text = render(Origin::BaselineCenter);
+/// assert_eq!(text.max.1, 0);
+/// assert!(text.min.0 < 0 && text.max.0 > 0);
+///
+/// // Capitals reach the ascender, which is at the top; the descender is below them
+/// assert_eq!(render(Origin::TopLeft).min.1, 0);
+/// assert_eq!(render(Origin::BottomLeft).max.1, -font.metrics().descender);
+/// # }
+/// ```
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub enum Origin {
+    /// The top left corner, where the first line starts.
+    #[default]
+    TopLeft,
+    /// The middle of the top edge.
+    TopCenter,
+    /// The top right corner.
+    TopRight,
+    /// The left end of the first line's baseline, where a pen would start writing.
+    BaselineLeft,
+    /// The middle of the first line's baseline.
+    BaselineCenter,
+    /// The right end of the first line's baseline.
+    BaselineRight,
+    /// The middle of the left edge.
+    CenterLeft,
+    /// The middle of the text, both across and down.
+    Center,
+    /// The middle of the right edge.
+    CenterRight,
+    /// The bottom left corner, below the last line.
+    BottomLeft,
+    /// The middle of the bottom edge.
+    BottomCenter,
+    /// The bottom right corner.
+    BottomRight,
+}
+
+impl Origin {
+    /// Find this origin in text `width` wide, with y increasing downward, whose top and
+    /// bottom edges are at y = `top` and `bottom` and whose first line's baseline is at
+    /// y = `baseline`.
+    ///
+    /// Subtracting the result from every point of the text moves it into place.
+    pub fn locate(self, width: i16, top: i16, bottom: i16, baseline: i16) -> (i16, i16) {
+        let x = match self {
+            Self::TopLeft | Self::BaselineLeft | Self::CenterLeft | Self::BottomLeft => 0,
+            Self::TopCenter | Self::BaselineCenter | Self::Center | Self::BottomCenter => width / 2,
+            Self::TopRight | Self::BaselineRight | Self::CenterRight | Self::BottomRight => width,
+        };
+
+        let y = match self {
+            Self::TopLeft | Self::TopCenter | Self::TopRight => top,
+            Self::BaselineLeft | Self::BaselineCenter | Self::BaselineRight => baseline,
+            Self::CenterLeft | Self::Center | Self::CenterRight => {
+                ((top as i32 + bottom as i32) / 2) as i16
+            }
+            Self::BottomLeft | Self::BottomCenter | Self::BottomRight => bottom,
+        };
+
+        (x, y)
+    }
+}
+
+/// A copy of the text drawn behind it, moved a little way, as a drop shadow.
+///
+/// The points of the shadow come first, so that a plotter draws the text over it,
+/// followed by the same number of points for the text. To draw the shadow with a
+/// different pen, split the points in half:
+///
+/// ```
+/// # #[cfg(feature = "alloc")] {
+/// use vector_text_core::{Font, MinimalFont, RenderOptions, Shadow};
+///
+/// let options = RenderOptions { shadow: Some(Shadow { dx: 1, dy: 1 }), ..Default::default() };
+/// let points = MinimalFont.render_text_with_options("HI", &options).unwrap();
+///
+/// let (shadow, text) = points.split_at(points.len() / 2);
+/// assert_eq!((shadow[0].x, shadow[0].y), (text[0].x + 1, text[0].y + 1));
+/// # }
+/// ```
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub struct Shadow {
+    /// Distance to move the shadow to the right
+    pub dx: i16,
+    /// Distance to move the shadow down, as the text is seen (before flipping it for
+    /// [RenderOptions::y_axis])
+    pub dy: i16,
+}
+
+impl Shadow {
+    /// Put a copy of the points from `start` onward before them, moved by the shadow's
+    /// offset.
+    #[cfg(feature = "alloc")]
+    pub fn apply(self, points: &mut Vec<Point>, start: usize) {
+        let len = points.len() - start;
+        points.extend_from_within(start..);
+        for point in &mut points[start..start + len] {
+            point.x = point.x.saturating_add(self.dx);
+            point.y = point.y.saturating_add(self.dy);
+        }
+    }
+}
+
+/// A size to draw text at, as a fraction of the font's own size, for sizing text with
+/// integer maths alone on processors without floating point hardware.
+///
+/// ```
+/// # #[cfg(feature = "alloc")] {
+/// use vector_text_core::{Font, MinimalFont, RenderOptions, Scale};
+///
+/// // Half size, in Q8.8 fixed point
+/// let options = RenderOptions { scale: Scale::from_q8_8(0x0080), ..Default::default() };
+/// let half = MinimalFont.render_text_with_options("HI", &options).unwrap();
+/// let full = MinimalFont.render_text("HI");
+///
+/// assert_eq!(half[1].y * 2, full[1].y);
+///
+/// // Only the scaled points have to fit in a Point, so small text can be longer
+/// let long = "HI".repeat(3000);
+/// let small = RenderOptions { scale: Scale { numerator: 1, denominator: 100 }, ..Default::default() };
+/// assert!(MinimalFont.render_text_with_options(&long, &small).is_ok());
+/// assert!(MinimalFont.render_text_with_options(&long, &RenderOptions::default()).is_err());
+/// # }
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Scale {
+    pub numerator: u16,
+    pub denominator: u16,
+}
+
+impl Scale {
+    /// The font's own size
+    pub const ONE: Self = Self {
+        numerator: 1,
+        denominator: 1,
+    };
+
+    /// A scale given in Q8.8 fixed point, where `0x0100` is the font's own size.
+    pub const fn from_q8_8(value: u16) -> Self {
+        Self {
+            numerator: value,
+            denominator: 0x100,
+        }
+    }
+
+    /// Scale a distance, rounding to the nearest whole unit (and halves away from 0).
+    pub fn apply(self, value: i32) -> i32 {
+        if self.denominator == 0 {
+            return value.signum() * i32::MAX;
+        }
+
+        let (numerator, denominator) = (self.numerator as i64, self.denominator as i64);
+        let scaled = value as i64 * numerator;
+        let rounded = (scaled.abs() + denominator / 2) / denominator * scaled.signum();
+        rounded.clamp(i32::MIN as i64, i32::MAX as i64) as i32
+    }
+}
+
+impl Default for Scale {
+    fn default() -> Self {
+        Self::ONE
+    }
+}
+
+/// Options controlling how text is rendered.
+#[derive(Debug, Copy, Clone)]
+pub struct RenderOptions {
+    /// Policy for characters the font cannot draw
+    pub missing_glyph: MissingGlyph,
+    /// When the font lacks an accented character, draw its base letter instead
+    /// (e.g. `e` for `é`)
+    pub strip_diacritics: bool,
+    /// When the font lacks a lowercase character, draw its uppercase form instead
+    pub fold_case: bool,
+    /// Distance between tab stops, as a number of spaces
+    pub tab_size: u8,
+    /// Policy for control characters other than tabs and line breaks
+    pub control_chars: ControlChars,
+    /// Wrap lines between words so that none is wider than this
+    #[cfg(feature = "alloc")]
+    pub max_width: Option<i16>,
+    /// Start the first line of the text this far to the right, as for the first line of
+    /// a paragraph. Wrapping leaves room for it.
+    pub first_line_indent: i16,
+    /// Where words may be broken when wrapping lines
+    #[cfg(feature = "alloc")]
+    pub hyphenation: Hyphenation,
+    /// Turn each glyph by the angle returned for the byte offset of its character, in
+    /// radians clockwise, around the middle of the glyph (halfway along its advance and
+    /// halfway up its capitals). Combining marks turn with the glyph they are drawn on.
+    ///
+    /// Glyphs are still laid out along a straight baseline, so this suits effects like
+    /// spinning letters, or keeping letters upright on a baseline turned as a whole.
+    pub glyph_rotation: Option<fn(usize) -> f32>,
+    /// Size to draw the text at. Other distances in the options are in the font's own
+    /// units, except for those of the shadow, which is moved after scaling.
+    pub scale: Scale,
+    /// Draw a copy of the text behind it as a drop shadow
+    pub shadow: Option<Shadow>,
+    /// Pen to draw the text with (counting from 0), for plotters with more than one or
+    /// output in several colours. It is noted in each [PlacedGlyph], so that text laid
+    /// out in several styles can be drawn with several pens.
+    pub pen: u8,
+    /// Which point of the text is put at (0, 0)
+    pub origin: Origin,
+    /// Which way y increases in the rendered points
+    pub y_axis: YAxis,
+    /// Normalize the text to NFC first, so that decomposed characters (e.g. `e` followed by
+    /// U+0301 COMBINING ACUTE ACCENT) are drawn using the font's precomposed glyphs.
+    ///
+    /// On by default when the `normalize` feature is enabled. Byte offsets in errors
+    /// point to the start of the character (with any combining marks) they occurred in.
+    #[cfg(feature = "normalize")]
+    pub normalize: bool,
+}
+
+impl Default for RenderOptions {
+    fn default() -> Self {
+        Self {
+            missing_glyph: MissingGlyph::default(),
+            strip_diacritics: false,
+            fold_case: false,
+            tab_size: 4,
+            control_chars: ControlChars::default(),
+            #[cfg(feature = "alloc")]
+            max_width: None,
+            first_line_indent: 0,
+            #[cfg(feature = "alloc")]
+            hyphenation: Hyphenation::None,
+            glyph_rotation: None,
+            scale: Scale::ONE,
+            shadow: None,
+            pen: 0,
+            origin: Origin::TopLeft,
+            y_axis: YAxis::Down,
+            #[cfg(feature = "normalize")]
+            normalize: true,
+        }
+    }
+}
+
+impl RenderOptions {
+    /// Options which fail on any character the font cannot draw.
+    pub fn strict() -> Self {
+        Self {
+            missing_glyph: MissingGlyph::Error,
+            ..Default::default()
+        }
+    }
+}
+
+/// Lay out the glyphs for each character of the text, appending the resulting points to `out`.
+///
+/// `lookup` is used to find the glyph for each character. Characters without a glyph
+/// are handled according to the [MissingGlyph] policy in `options`.
+///
+/// Line breaks (`\n`, `\r\n`, or a lone `\r`) start a new line one
+/// [FontMetrics::line_height] further down, and tabs advance to the next tab stop. The
+/// points have the top left of the text at (0, 0) and y increasing downward, unless
+/// [RenderOptions::origin] and [RenderOptions::y_axis] say otherwise. With a
+/// [RenderOptions::shadow], its points come before those of the text.
+/// If [RenderOptions::max_width] is set, lines are also wrapped between words, or within
+/// them where [RenderOptions::hyphenation] allows.
+///
+/// Combining marks (such as U+0301 COMBINING ACUTE ACCENT) are drawn on top of the
+/// glyph before them, using the glyphs' [crate::Anchor]s where the font provides them.
+///
+/// If the text grows too large for [Point] coordinates, [RenderError::AdvanceOverflow] is
+/// returned and `out` holds the points of every glyph before the one which overflowed,
+/// which are not moved to the origin.
+#[cfg(feature = "alloc")]
+pub fn render_glyphs<'g, F>(
+    text: &str,
+    mut lookup: F,
+    metrics: &FontMetrics,
+    options: &RenderOptions,
+    out: &mut Vec<Point>,
+) -> Result<(), RenderError>
+where
+    F: FnMut(char) -> Result<Glyph<'g>, RenderError>,
+{
+    render_mixed_glyphs(
+        text,
+        |_, c| lookup(c).map(|glyph| (glyph, 0)),
+        metrics,
+        options,
+        out,
+    )
+}
+
+/// Lay out the glyphs for text drawn with a mix of fonts, appending the resulting points
+/// to `out`.
+///
+/// This works like [render_glyphs], except that `lookup` is also given the byte offset
+/// of the character in the text, and returns how far down to move its glyph along with it.
+/// This allows parts of the text to be drawn with different fonts, with their baselines
+/// lined up. `metrics` should be those of the main font.
+#[cfg(feature = "alloc")]
+pub fn render_mixed_glyphs<'g, F>(
+    text: &str,
+    mut lookup: F,
+    metrics: &FontMetrics,
+    options: &RenderOptions,
+    out: &mut Vec<Point>,
+) -> Result<(), RenderError>
+where
+    F: FnMut(usize, char) -> Result<(Glyph<'g>, i16), RenderError>,
+{
+    // Count the points first, so that `out` only has to grow once
+    let mut count = PointCount(0);
+    let _ = layout(text, &mut lookup, metrics, options, &mut count);
+    out.reserve_exact(count.0 * if options.shadow.is_some() { 2 } else { 1 });
+
+    let start = out.len();
+    let result = layout(text, lookup, metrics, options, out);
+    if let Ok(extent) = &result {
+        let origin = extent.origin(options.origin);
+        for point in &mut out[start..] {
+            point.x = point.x.saturating_sub(origin.0);
+            point.y = point.y.saturating_sub(origin.1);
+        }
+        if let Some(shadow) = options.shadow {
+            shadow.apply(out, start);
+        }
+    }
+    options.y_axis.apply(&mut out[start..]);
+    result.map(|_| ())
+}
+
+/// Count the points which [render_glyphs] would draw for some text, without drawing
+/// them.
+///
+/// If the text can't be drawn, this is the number of points drawn before the error.
+///
+/// ```
+/// # #[cfg(feature = "alloc")] {
+/// use vector_text_core::{Font, MinimalFont, RenderError, RenderOptions, count_glyphs};
+///
+/// let font = MinimalFont;
+/// let count = count_glyphs(
+///     "HI",
+///     |c| font.glyph(c).ok_or(RenderError::UnsupportedCharacter(c)),
+///     &font.metrics(),
+///     &RenderOptions::default(),
+/// );
+///
+/// assert_eq!(count, font.render_text("HI").len());
+/// # }
+/// ```
+pub fn count_glyphs<'g, F>(
+    text: &str,
+    mut lookup: F,
+    metrics: &FontMetrics,
+    options: &RenderOptions,
+) -> usize
+where
+    F: FnMut(char) -> Result<Glyph<'g>, RenderError>,
+{
+    let mut count = PointCount(0);
+    let _ = layout(
+        text,
+        |_, c| lookup(c).map(|glyph| (glyph, 0)),
+        metrics,
+        options,
+        &mut count,
+    );
+
+    match options.shadow {
+        Some(_) => count.0 * 2,
+        None => count.0,
+    }
+}
+
+/// Somewhere to put the points of text drawn by [render_glyphs_to], one at a time, such
+/// as a fixed-size buffer or the DAC of a display.
+pub trait PointSink {
+    /// Take the next point of the text.
+    fn push(&mut self, point: Point);
+}
+
+#[cfg(feature = "alloc")]
+impl PointSink for Vec<Point> {
+    fn push(&mut self, point: Point) {
+        Vec::push(self, point)
+    }
+}
+
+/// Draw text like [render_glyphs], giving each point to `sink` as it is drawn, without
+/// allocating any memory. This works without the `alloc` feature, for targets with no
+/// allocator.
+///
+/// The text is laid out once to measure it first, and nothing is drawn if that fails,
+/// so the sink never gets part of the text. It is laid out again to draw it, and again
+/// before that for a [RenderOptions::shadow]. Lines are only wrapped with the `alloc`
+/// feature, which [RenderOptions::max_width] needs.
+///
+/// ```
+/// use vector_text_core::{
+///     Font, MinimalFont, Point, PointSink, RenderError, RenderOptions, count_glyphs,
+///     render_glyphs_to,
+/// };
+///
+/// /// Points kept in a fixed-size buffer
+/// struct Buffer {
+///     points: [Point; 64],
+///     len: usize,
+/// }
+///
+/// impl PointSink for Buffer {
+///     fn push(&mut self, point: Point) {
+///         if let Some(slot) = self.points.get_mut(self.len) {
+///             *slot = point;
+///             self.len += 1;
+///         }
+///     }
+/// }
+///
+/// let font = MinimalFont;
+/// let lookup = |c| font.glyph(c).ok_or(RenderError::UnsupportedCharacter(c));
+/// let options = RenderOptions::default();
+///
+/// let mut buffer = Buffer { points: [Point::default(); 64], len: 0 };
+/// render_glyphs_to("HI", lookup, &font.metrics(), &options, &mut buffer).unwrap();
+///
+/// assert_eq!(buffer.len, count_glyphs("HI", lookup, &font.metrics(), &options));
+/// ```
+pub fn render_glyphs_to<'g, F>(
+    text: &str,
+    mut lookup: F,
+    metrics: &FontMetrics,
+    options: &RenderOptions,
+    sink: &mut impl PointSink,
+) -> Result<(), RenderError>
+where
+    F: FnMut(char) -> Result<Glyph<'g>, RenderError>,
+{
+    let mut lookup = |_, c| lookup(c).map(|glyph| (glyph, 0));
+
+    let extent = layout(text, &mut lookup, metrics, options, &mut PointCount(0))?;
+    let origin = extent.origin(options.origin);
+
+    let shadow = options.shadow.map(|shadow| (shadow.dx, shadow.dy));
+    for (dx, dy) in shadow.into_iter().chain([(0, 0)]) {
+        let mut stream = Stream {
+            sink,
+            len: 0,
+            origin,
+            shift: (dx, dy),
+            y_axis: options.y_axis,
+        };
+        layout(text, &mut lookup, metrics, options, &mut stream)?;
+    }
+
+    Ok(())
+}
+
+/// Where [layout_glyphs] placed the glyph for a character.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlacedGlyph {
+    /// Byte offset of the character in the text
+    pub offset: usize,
+    /// The character (or [SOFT_HYPHEN], for the hyphen drawn where a line was broken at
+    /// one)
+    pub character: char,
+    /// Position of the glyph's left edge
+    pub x: i16,
+    /// Position of the top of the line the glyph is on
+    pub y: i16,
+    /// Distance from this glyph to the next, which is 0 for combining marks
+    pub advance: i16,
+    /// Range of the points drawn for the glyph
+    pub points: Range<usize>,
+    /// Pen the glyph is drawn with, from [RenderOptions::pen]
+    pub pen: u8,
+}
+
+/// Lay out text like [render_mixed_glyphs], also returning where each glyph was placed.
+///
+/// `style` gives the options for the glyph at each byte offset, so that parts of the
+/// text can handle missing glyphs and control characters differently. The options
+/// which apply to the text as a whole (`max_width`, `first_line_indent`, `hyphenation`,
+/// `tab_size`, `scale`, `shadow`, `origin`, `y_axis`, and `normalize`) are taken from
+/// `options`.
+///
+/// Every character which draws something or takes up space gets a [PlacedGlyph],
+/// including spaces and tabs (but not line breaks). Its `points` are a range of `out`,
+/// which leaves out any shadow drawn for it.
+///
+/// ```
+/// use vector_text_core::{Font, MinimalFont, RenderError, RenderOptions, layout_glyphs};
+///
+/// let font = MinimalFont;
+/// let options = RenderOptions::default();
+/// let mut points = Vec::new();
+/// let glyphs = layout_glyphs(
+///     "HI",
+///     |_, c| font.glyph(c).map(|glyph| (glyph, 0)).ok_or(RenderError::UnsupportedCharacter(c)),
+///     |_| &options,
+///     &font.metrics(),
+///     &options,
+///     &mut points,
+/// )
+/// .unwrap();
+///
+/// assert_eq!(glyphs.len(), 2);
+/// assert_eq!(glyphs[1].x, glyphs[0].advance);
+/// assert_eq!(glyphs[1].points.end, points.len());
+/// ```
+#[cfg(feature = "alloc")]
+pub fn layout_glyphs<'o, 'g, F, S>(
+    text: &str,
+    lookup: F,
+    style: S,
+    metrics: &FontMetrics,
+    options: &RenderOptions,
+    out: &mut Vec<Point>,
+) -> Result<Vec<PlacedGlyph>, RenderError>
+where
+    F: FnMut(usize, char) -> Result<(Glyph<'g>, i16), RenderError>,
+    S: Fn(usize) -> &'o RenderOptions,
+{
+    let start = out.len();
+    let mut placement = Placement {
+        points: out,
+        glyphs: Vec::new(),
+    };
+
+    let style = |offset| *style(offset);
+    let result = layout_styled(text, lookup, Some(&style), metrics, options, &mut placement);
+
+    let mut glyphs = placement.glyphs;
+    if let Ok(extent) = &result {
+        let origin = extent.origin(options.origin);
+        for point in &mut out[start..] {
+            point.x = point.x.saturating_sub(origin.0);
+            point.y = point.y.saturating_sub(origin.1);
+        }
+        for glyph in &mut glyphs {
+            glyph.x = glyph.x.saturating_sub(origin.0);
+            glyph.y = glyph.y.saturating_sub(origin.1);
+        }
+
+        if let Some(shadow) = options.shadow {
+            let len = out.len() - start;
+            shadow.apply(out, start);
+            for glyph in &mut glyphs {
+                glyph.points = glyph.points.start + len..glyph.points.end + len;
+            }
+        }
+    }
+    if options.y_axis == YAxis::Up {
+        YAxis::Up.apply(&mut out[start..]);
+        for glyph in &mut glyphs {
+            glyph.y = glyph.y.saturating_neg();
+        }
+    }
+
+    result.map(|_| glyphs)
+}
+
+/// Where the points of laid-out text go.
+trait Output {
+    fn len(&self) -> usize;
+    fn push(&mut self, point: Point);
+    fn truncate(&mut self, len: usize);
+
+    /// Note where a glyph was placed, after drawing it.
+    fn place(&mut self, _glyph: PlacedGlyph) {}
+}
+
+#[cfg(feature = "alloc")]
+impl Output for Vec<Point> {
+    fn len(&self) -> usize {
+        Vec::len(self)
+    }
+
+    fn push(&mut self, point: Point) {
+        Vec::push(self, point)
+    }
+
+    fn truncate(&mut self, len: usize) {
+        Vec::truncate(self, len)
+    }
+}
+
+/// Counts the points of laid-out text, rather than keeping them.
+struct PointCount(usize);
+
+impl Output for PointCount {
+    fn len(&self) -> usize {
+        self.0
+    }
+
+    fn push(&mut self, _point: Point) {
+        self.0 += 1;
+    }
+
+    fn truncate(&mut self, len: usize) {
+        self.0 = self.0.min(len);
+    }
+}
+
+/// Passes the points of laid-out text on to a [PointSink], moved to the origin (and by
+/// `shift`, for a shadow) and flipped to the y axis.
+struct Stream<'s, S> {
+    sink: &'s mut S,
+    len: usize,
+    origin: (i16, i16),
+    shift: (i16, i16),
+    y_axis: YAxis,
+}
+
+impl<S: PointSink> Output for Stream<'_, S> {
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    fn push(&mut self, point: Point) {
+        let mut point = [Point {
+            x: point
+                .x
+                .saturating_sub(self.origin.0)
+                .saturating_add(self.shift.0),
+            y: point
+                .y
+                .saturating_sub(self.origin.1)
+                .saturating_add(self.shift.1),
+            pen: point.pen,
+        }];
+        self.y_axis.apply(&mut point);
+
+        self.sink.push(point[0]);
+        self.len += 1;
+    }
+
+    fn truncate(&mut self, len: usize) {
+        // Only on overflow, which measuring the text first has ruled out
+        self.len = self.len.min(len);
+    }
+}
+
+/// Keeps the points of laid-out text, and where each glyph was placed.
+#[cfg(feature = "alloc")]
+struct Placement<'o> {
+    points: &'o mut Vec<Point>,
+    glyphs: Vec<PlacedGlyph>,
+}
+
+#[cfg(feature = "alloc")]
+impl Output for Placement<'_> {
+    fn len(&self) -> usize {
+        self.points.len()
+    }
+
+    fn push(&mut self, point: Point) {
+        self.points.push(point)
+    }
+
+    fn truncate(&mut self, len: usize) {
+        self.points.truncate(len)
+    }
+
+    fn place(&mut self, glyph: PlacedGlyph) {
+        self.glyphs.push(glyph)
+    }
+}
+
+/// The size of some laid-out text, in the font's coordinates with y increasing downward.
+struct Extent {
+    width: i16,
+    /// Top of the first line, at its ascender
+    top: i16,
+    /// Bottom of the last line, at its descender
+    bottom: i16,
+    /// Baseline of the first line
+    baseline: i16,
+}
+
+impl Extent {
+    /// Where the given origin is in the text
+    fn origin(&self, origin: Origin) -> (i16, i16) {
+        origin.locate(self.width, self.top, self.bottom, self.baseline)
+    }
+}
+
+/// Lay out the glyphs for some text, as described by [render_mixed_glyphs].
+fn layout<'g, F>(
+    text: &str,
+    lookup: F,
+    metrics: &FontMetrics,
+    options: &RenderOptions,
+    out: &mut impl Output,
+) -> Result<Extent, RenderError>
+where
+    F: FnMut(usize, char) -> Result<(Glyph<'g>, i16), RenderError>,
+{
+    layout_styled(text, lookup, None, metrics, options, out)
+}
+
+/// Lay out the glyphs for some text, as described by [render_mixed_glyphs], taking the
+/// options for each glyph from `style` if given.
+fn layout_styled<'a, 'g, F>(
+    text: &str,
+    lookup: F,
+    style: Option<&'a dyn Fn(usize) -> RenderOptions>,
+    metrics: &'a FontMetrics,
+    options: &'a RenderOptions,
+    out: &mut impl Output,
+) -> Result<Extent, RenderError>
+where
+    F: FnMut(usize, char) -> Result<(Glyph<'g>, i16), RenderError>,
+{
+    let mut engine = Engine::new(lookup, metrics, options);
+    engine.style = style;
+
+    #[cfg(feature = "alloc")]
+    let breaks = match options.max_width {
+        Some(max_width) => crate::wrap::break_lines(text, max_width as i32, &mut engine)?,
+        None => Vec::new(),
+    };
+    // Lines can't be wrapped without somewhere to keep the breaks
+    #[cfg(not(feature = "alloc"))]
+    let breaks: [SoftBreak; 0] = [];
+    let mut breaks = breaks.iter().peekable();
+
+    // Track the position with extra range, so overflow can be caught before it wraps
+    let mut x_idx: i32 = options.first_line_indent as i32;
+    let mut y_idx: i32 = 0;
+    // Top of the first line, where the font's tallest glyphs reach up to
+    let top = metrics.baseline as i32 - metrics.ascender as i32;
+    // Width of the widest line so far
+    let mut width: i32 = x_idx;
+    // The last glyph drawn on this line and its position, for combining marks to attach to
+    let mut base: Option<(Glyph, i16, i32, Option<Turn>)> = None;
+
+    let mut chars = chars(text, options).peekable();
+
+    while let Some((offset, character)) = chars.next() {
+        let overflow = RenderError::AdvanceOverflow { offset };
+
+        if let Some(soft_break) = breaks.next_if(|b| b.at == offset) {
+            if soft_break.hyphen {
+                let hyphen = engine.hyphen(offset);
+                let start = out.len();
+                let turn = engine.turn(offset, &hyphen, x_idx, y_idx);
+                engine.draw(&hyphen, x_idx, y_idx, turn.as_ref(), out, overflow)?;
+                out.place(PlacedGlyph {
+                    offset,
+                    character: SOFT_HYPHEN,
+                    x: engine.scaled(x_idx, overflow)?,
+                    y: engine.scaled(y_idx + top, overflow)?,
+                    advance: engine.scaled(engine.advance(&hyphen, x_idx), overflow)?,
+                    points: start..out.len(),
+                    pen: engine.options_at(offset).pen,
+                });
+                width = width.max(x_idx + engine.advance(&hyphen, x_idx));
+            }
+
+            x_idx = 0;
+            y_idx = y_idx
+                .checked_add(metrics.line_height() as i32)
+                .ok_or(overflow)?;
+            engine.scaled(y_idx, overflow)?;
+            base = None;
+
+            if soft_break.resume > offset {
+                // Skip the spaces (or soft hyphen) the line was broken at
+                while chars.next_if(|&(o, _)| o < soft_break.resume).is_some() {}
+                continue;
+            }
+
+            // Otherwise, the line was broken in the middle of a word,
+            // and this character starts the next line
+        }
+
+        match engine.resolve(offset, character)? {
+            Item::LineBreak => {
+                x_idx = 0;
+                y_idx = y_idx
+                    .checked_add(metrics.line_height() as i32)
+                    .ok_or(overflow)?;
+                engine.scaled(y_idx, overflow)?;
+                base = None;
+            }
+            Item::Mark(mark, dy) => {
+                let start = out.len();
+                let turn = engine.turn(offset, &Item::Mark(mark, dy), x_idx, y_idx);
+                engine.draw_mark(&mark, dy, base.as_ref(), x_idx, y_idx, turn, out, overflow)?;
+                out.place(PlacedGlyph {
+                    offset,
+                    character,
+                    x: engine.scaled(x_idx, overflow)?,
+                    y: engine.scaled(y_idx + top, overflow)?,
+                    advance: 0,
+                    points: start..out.len(),
+                    pen: engine.options_at(offset).pen,
+                });
+            }
+            Item::Nothing => {}
+            item => {
+                let start = out.len();
+                let turn = engine.turn(offset, &item, x_idx, y_idx);
+                engine.draw(&item, x_idx, y_idx, turn.as_ref(), out, overflow)?;
+
+                let advance = engine.advance(&item, x_idx);
+                out.place(PlacedGlyph {
+                    offset,
+                    character,
+                    x: engine.scaled(x_idx, overflow)?,
+                    y: engine.scaled(y_idx + top, overflow)?,
+                    advance: engine.scaled(advance, overflow)?,
+                    points: start..out.len(),
+                    pen: engine.options_at(offset).pen,
+                });
+
+                match item {
+                    Item::Glyph(glyph, dy) => base = Some((glyph, dy, x_idx, turn)),
+                    _ => base = None,
+                }
+
+                x_idx += advance;
+                width = width.max(x_idx);
+            }
+        }
+    }
+
+    let bottom = y_idx + metrics.baseline as i32 + metrics.descender as i32;
+    let scaled = |value: i32| {
+        let value = options.scale.apply(value);
+        value.clamp(i16::MIN as i32, i16::MAX as i32) as i16
+    };
+    Ok(Extent {
+        width: scaled(width),
+        top: scaled(top),
+        bottom: scaled(bottom),
+        baseline: scaled(metrics.baseline as i32),
+    })
+}
+
+/// Iterate over the characters of some text with their byte offsets,
+/// merging each CRLF pair into a single line break.
+///
+/// If [RenderOptions::normalize] is set, the characters are normalized to NFC first.
+#[cfg_attr(not(feature = "normalize"), allow(unused_variables))]
+pub(crate) fn chars<'t>(
+    text: &'t str,
+    options: &RenderOptions,
+) -> impl Iterator<Item = (usize, char)> + 't {
+    let chars = text.char_indices();
+    #[cfg(feature = "normalize")]
+    let chars = crate::normalize::Nfc::new(chars, options.normalize);
+    let mut chars = chars.peekable();
+
+    core::iter::from_fn(move || {
+        let (offset, character) = chars.next()?;
+
+        if character == '\r' {
+            chars.next_if(|&(_, c)| c == '\n');
+        }
+
+        Some((offset, character))
+    })
+}
+
+/// What a single character of the input turns into.
+pub(crate) enum Item<'g> {
+    /// Draw this glyph, moved down by the given distance.
+    Glyph(Glyph<'g>, i16),
+    /// Draw this combining mark over the previous glyph, without moving.
+    Mark(Glyph<'g>, i16),
+    /// Draw a replacement box for a missing glyph.
+    ReplacementBox,
+    /// Move to the next tab stop.
+    Tab,
+    /// Start a new line.
+    LineBreak,
+    /// Draw nothing and don't move (e.g. a soft hyphen that wasn't broken at).
+    Nothing,
+}
+
+/// The state shared by the measuring and drawing passes over the text.
+pub(crate) struct Engine<'a, F> {
+    lookup: F,
+    metrics: &'a FontMetrics,
+    pub(crate) options: &'a RenderOptions,
+    /// Options for the glyphs at each byte offset, if they differ through the text
+    style: Option<&'a dyn Fn(usize) -> RenderOptions>,
+    /// Distance between tab stops
+    tab_stop: i32,
+}
+
+impl<'a, 'g, F> Engine<'a, F>
+where
+    F: FnMut(usize, char) -> Result<(Glyph<'g>, i16), RenderError>,
+{
+    fn new(mut lookup: F, metrics: &'a FontMetrics, options: &'a RenderOptions) -> Self {
+        let space = match lookup(0, ' ') {
+            Ok((glyph, _)) => glyph.advance() as i32,
+            Err(_) => metrics.average_advance as i32,
+        };
+
+        Self {
+            lookup,
+            metrics,
+            options,
+            style: None,
+            tab_stop: (space * options.tab_size as i32).max(1),
+        }
+    }
+
+    /// Scale a distance to the size the text is drawn at, if it fits in [Point]
+    /// coordinates.
+    fn scaled(&self, value: i32, overflow: RenderError) -> Result<i16, RenderError> {
+        i16::try_from(self.options.scale.apply(value)).map_err(|_| overflow)
+    }
+
+    /// The options for drawing the glyph of the character at `offset`.
+    fn options_at(&self, offset: usize) -> RenderOptions {
+        self.style.map_or(*self.options, |style| style(offset))
+    }
+
+    /// Work out what to draw for a single character, found at `offset` in the text.
+    pub(crate) fn resolve(
+        &mut self,
+        offset: usize,
+        character: char,
+    ) -> Result<Item<'g>, RenderError> {
+        let options = self.options_at(offset);
+
+        let character = match character {
+            '\r' | '\n' | '\u{2028}' | '\u{2029}' => return Ok(Item::LineBreak),
+            '\t' => return Ok(Item::Tab),
+            // Only visible if a line is broken there, which is up to the wrapping pass
+            SOFT_HYPHEN => return Ok(Item::Nothing),
+            // Same as a space, except when wrapping
+            c if is_no_break_space(c) => ' ',
+            c if c.is_control() => match options.control_chars {
+                ControlChars::Skip => return Ok(Item::Nothing),
+                ControlChars::Error => return Err(RenderError::UnsupportedCharacter(c)),
+                ControlChars::Render => c,
+                ControlChars::Custom(hook) => match hook(c) {
+                    Some(replacement) => replacement,
+                    None => return Ok(Item::Nothing),
+                },
+            },
+            c => c,
+        };
+
+        let mut lookup = |c| (self.lookup)(offset, c);
+
+        if is_combining_mark(character) {
+            let mark = core::iter::once(character)
+                .chain(spacing_forms(character).iter().copied())
+                .find_map(|c| lookup(c).ok());
+
+            if let Some((mark, dy)) = mark {
+                return Ok(Item::Mark(mark, dy));
+            }
+        }
+
+        match (
+            lookup_with_fallback(&mut lookup, character, &options),
+            options.missing_glyph,
+        ) {
+            (Ok((glyph, dy)), _) => Ok(Item::Glyph(glyph, dy)),
+            (Err(_), MissingGlyph::Skip) => Ok(Item::Nothing),
+            (Err(e), MissingGlyph::Error) => Err(e),
+            (Err(_), MissingGlyph::Replace(replacement)) => match lookup(replacement) {
+                Ok((glyph, dy)) => Ok(Item::Glyph(glyph, dy)),
+                Err(_) => Ok(Item::Nothing),
+            },
+            (Err(_), MissingGlyph::ReplacementBox) => Ok(Item::ReplacementBox),
+        }
+    }
+
+    /// The hyphen drawn where a line is broken at the soft hyphen at `offset`.
+    pub(crate) fn hyphen(&mut self, offset: usize) -> Item<'g> {
+        ['-', '\u{2010}']
+            .into_iter()
+            .find_map(|c| (self.lookup)(offset, c).ok())
+            .map_or(Item::Nothing, |(glyph, dy)| Item::Glyph(glyph, dy))
+    }
+
+    /// How far to move horizontally after drawing an item at position `x`.
+    pub(crate) fn advance(&self, item: &Item, x: i32) -> i32 {
+        match item {
+            Item::Glyph(glyph, _) => glyph.advance() as i32,
+            Item::ReplacementBox => self.metrics.average_advance as i32,
+            Item::Tab => (x.div_euclid(self.tab_stop) + 1) * self.tab_stop - x,
+            Item::Mark(..) | Item::LineBreak | Item::Nothing => 0,
+        }
+    }
+
+    /// How to turn the item for the character at `offset`, drawn with its left edge at
+    /// `x` on the line starting at `y`, if at all.
+    fn turn(&self, offset: usize, item: &Item, x: i32, y: i32) -> Option<Turn> {
+        let rotation = self.options_at(offset).glyph_rotation?;
+        let angle = rotation(offset);
+        if angle == 0.0 || !angle.is_finite() {
+            return None;
+        }
+
+        Some(Turn {
+            cos: libm::cosf(angle),
+            sin: libm::sinf(angle),
+            x: x + self.advance(item, x) / 2,
+            y: y + self.metrics.baseline as i32 - self.metrics.cap_height as i32 / 2,
+        })
+    }
+
+    /// Draw an item with its left edge at `x` on the line starting at `y`, turned by
+    /// `turn` if given.
+    ///
+    /// If any part of it would not fit in [Point] coordinates, nothing is drawn and
+    /// the `overflow` error is returned.
+    fn draw(
+        &self,
+        item: &Item,
+        x: i32,
+        y: i32,
+        turn: Option<&Turn>,
+        out: &mut impl Output,
+        overflow: RenderError,
+    ) -> Result<(), RenderError> {
+        // Only the scaled position has to fit in a point, so small text can be laid out
+        // across more font units than that
+        let right = x.checked_add(self.advance(item, x)).ok_or(overflow)?;
+        self.scaled(right, overflow)?;
+
+        match item {
+            // A mark with no base is drawn on its own
+            Item::Glyph(glyph, dy) | Item::Mark(glyph, dy) => {
+                let scale = self.options.scale;
+                draw_glyph(glyph, x, y + *dy as i32, turn, scale, out, overflow)?
+            }
+            Item::ReplacementBox => {
+                let mut points = [Point::default(); 5];
+                for (point, (px, py, pen)) in
+                    points.iter_mut().zip(replacement_box(self.metrics, x, y))
+                {
+                    let (px, py) = turn.map_or((px, py), |turn| turn.apply(px, py));
+                    let (x, y) = (self.scaled(px, overflow)?, self.scaled(py, overflow)?);
+                    *point = Point { x, y, pen };
+                }
+
+                for point in points {
+                    out.push(point);
+                }
+            }
+            Item::Tab | Item::LineBreak | Item::Nothing => {}
+        }
+
+        Ok(())
+    }
+
+    /// Draw a combining mark (moved down by `mark_dy`) attached to the `base` glyph
+    /// (moved down by its own distance) drawn with its left edge at `base_x` and turned
+    /// by its own turn, or on its own at `x`, turned by `turn`, if there is no base.
+    #[allow(clippy::too_many_arguments)]
+    fn draw_mark(
+        &self,
+        mark: &Glyph<'g>,
+        mark_dy: i16,
+        base: Option<&(Glyph<'g>, i16, i32, Option<Turn>)>,
+        x: i32,
+        y: i32,
+        turn: Option<Turn>,
+        out: &mut impl Output,
+        overflow: RenderError,
+    ) -> Result<(), RenderError> {
+        let Some((base, base_dy, base_x, base_turn)) = base else {
+            let item = Item::Mark(*mark, mark_dy);
+            return self.draw(&item, x, y, turn.as_ref(), out, overflow);
+        };
+
+        let (dx, dy) = self.attach(mark, mark_dy as i32, base, *base_dy as i32);
+        let x = base_x - base.left as i32 + mark.left as i32 + dx;
+
+        draw_glyph(
+            mark,
+            x,
+            y + mark_dy as i32 + dy,
+            base_turn.as_ref(),
+            self.options.scale,
+            out,
+            overflow,
+        )
+    }
+
+    /// Work out how far to move a combining mark, relative to its base glyph,
+    /// so that it sits in the right place. Each glyph is moved down by its `dy` first.
+    fn attach(&self, mark: &Glyph, mark_dy: i32, base: &Glyph, base_dy: i32) -> (i32, i32) {
+        for (on_base, on_mark) in [
+            (AnchorKind::Above, AnchorKind::MarkAbove),
+            (AnchorKind::Below, AnchorKind::MarkBelow),
+        ] {
+            if let (Some(b), Some(m)) = (base.anchor(on_base), mark.anchor(on_mark)) {
+                return (
+                    b.x as i32 - m.x as i32,
+                    b.y as i32 + base_dy - m.y as i32 - mark_dy,
+                );
+            }
+        }
+
+        // Without anchors, centre the mark on the base...
+        let dx = (base.left as i32 + base.right as i32 - mark.left as i32 - mark.right as i32) / 2;
+
+        // ...and if it is drawn above the base, move it to just clear the top of the base
+        let baseline = self.metrics.baseline as i32;
+        let x_height = self.metrics.x_height as i32;
+        let top = base.strokes.iter().map(|p| p.y as i32 + base_dy).min();
+        let bottom = mark.strokes.iter().map(|p| p.y as i32 + mark_dy).max();
+
+        let dy = match bottom {
+            Some(bottom) if bottom < baseline - x_height / 2 => {
+                let gap = self.metrics.cap_height as i32 / 4;
+                top.unwrap_or(baseline - x_height) - gap - bottom
+            }
+            _ => 0,
+        };
+
+        (dx, dy)
+    }
+}
+
+/// A place where a line of wrapped text is broken.
+pub(crate) struct SoftBreak {
+    /// Byte offset where the line ends
+    pub at: usize,
+    /// Byte offset where the next line starts
+    pub resume: usize,
+    /// Should a hyphen be drawn at the end of the line?
+    pub hyphen: bool,
+}
+
+/// Soft hyphen, marking where a word may be hyphenated when wrapping.
+pub(crate) const SOFT_HYPHEN: char = '\u{AD}';
+
+/// Is this one of the space characters which must not be broken at when wrapping?
+pub(crate) fn is_no_break_space(character: char) -> bool {
+    matches!(character, '\u{A0}' | '\u{2007}' | '\u{202F}')
+}
+
+/// A rotation of a glyph around a point, from [RenderOptions::glyph_rotation].
+#[derive(Copy, Clone)]
+struct Turn {
+    cos: f32,
+    sin: f32,
+    /// The point turned around
+    x: i32,
+    y: i32,
+}
+
+impl Turn {
+    /// Turn a point, with y increasing downward
+    fn apply(&self, x: i32, y: i32) -> (i32, i32) {
+        let (dx, dy) = ((x - self.x) as f32, (y - self.y) as f32);
+        (
+            self.x + libm::roundf(dx * self.cos - dy * self.sin) as i32,
+            self.y + libm::roundf(dx * self.sin + dy * self.cos) as i32,
+        )
+    }
+}
+
+/// Draw a glyph with its left edge at `x` on the line starting at `y`, turned by `turn`
+/// if given, then scaled by `scale`.
+///
+/// If any part of it would not fit in [Point] coordinates, nothing is drawn and
+/// the `overflow` error is returned.
+fn draw_glyph(
+    glyph: &Glyph,
+    x: i32,
+    y: i32,
+    turn: Option<&Turn>,
+    scale: Scale,
+    out: &mut impl Output,
+    overflow: RenderError,
+) -> Result<(), RenderError> {
+    let start = out.len();
+
+    for point in glyph.strokes {
+        let (px, py) = (point.x as i32 - glyph.left as i32 + x, point.y as i32 + y);
+        let (px, py) = turn.map_or((px, py), |turn| turn.apply(px, py));
+        let (px, py) = (scale.apply(px), scale.apply(py));
+
+        let (Ok(px), Ok(py)) = (i16::try_from(px), i16::try_from(py)) else {
+            // Don't leave half of a glyph behind
+            out.truncate(start);
+            return Err(overflow);
+        };
+
+        out.push(Point {
+            x: px,
+            y: py,
+            pen: point.pen,
+        });
+    }
+
+    Ok(())
+}
+
+/// Is this a combining mark, drawn on top of the character before it?
+pub fn is_combining_mark(character: char) -> bool {
+    matches!(
+        character,
+        '\u{0300}'..='\u{036F}'
+            | '\u{1AB0}'..='\u{1AFF}'
+            | '\u{1DC0}'..='\u{1DFF}'
+            | '\u{20D0}'..='\u{20FF}'
+            | '\u{FE20}'..='\u{FE2F}'
+    )
+}
+
+/// Look up the glyph for a character, trying the fallbacks enabled in `options`
+/// if the font has no glyph for it.
+fn lookup_with_fallback<'g, F>(
+    lookup: &mut F,
+    character: char,
+    options: &RenderOptions,
+) -> Result<(Glyph<'g>, i16), RenderError>
+where
+    F: FnMut(char) -> Result<(Glyph<'g>, i16), RenderError>,
+{
+    let error = match lookup(character) {
+        Ok(glyph) => return Ok(glyph),
+        Err(e) => e,
+    };
+
+    let stripped = strip_diacritics(character).filter(|_| options.strip_diacritics);
+
+    if let Some(base) = stripped
+        && let Ok(glyph) = lookup(base)
+    {
+        return Ok(glyph);
+    }
+
+    if options.fold_case {
+        // Try the uppercase form of the original character first, then of its base letter
+        for candidate in [Some(character), stripped].into_iter().flatten() {
+            if let Some(upper) = single_uppercase(candidate)
+                && let Ok(glyph) = lookup(upper)
+            {
+                return Ok(glyph);
+            }
+        }
+    }
+
+    Err(error)
+}
+
+/// Get the uppercase form of a lowercase character, if it is a single character.
+fn single_uppercase(character: char) -> Option<char> {
+    let mut upper = character.to_uppercase();
+
+    match (upper.next(), upper.next()) {
+        (Some(c), None) if c != character => Some(c),
+        _ => None,
+    }
+}
+
+/// Build the "tofu" box drawn in place of a missing glyph, with its left edge at `x`
+/// on the line starting at `y`, as the x, y, and pen of each of its points in font
+/// units.
+fn replacement_box(metrics: &FontMetrics, x: i32, y: i32) -> [(i32, i32, bool); 5] {
+    // Leave some space on either side, like a real glyph would
+    let margin = metrics.average_advance as i32 / 6;
+    let left = x + margin;
+    let right = x + metrics.average_advance as i32 - margin;
+    let top = y + metrics.baseline as i32 - metrics.cap_height as i32;
+    let bottom = y + metrics.baseline as i32;
+
+    [
+        (left, bottom, false),
+        (left, top, true),
+        (right, top, true),
+        (right, bottom, true),
+        (left, bottom, true),
+    ]
+}
@@ -0,0 +1,407 @@
+use crate::{Font, FontMetrics, Glyph, PackedPoint, ParseFontError, Strokes};
+
+/// A font imitating a segment display, generated in code from the shapes of its segments.
+///
+/// This needs no font data, and suits XY oscilloscope clocks, laser counters, and
+/// other displays of mostly numbers. Each character is drawn in a box 8 units wide
+/// and 12 tall, like [crate::MinimalFont].
+///
+/// If a font has no glyph for a letter, it is drawn in the other case instead
+/// (so the seven-segment font draws `B` as `b`, and the sixteen-segment font draws
+/// lowercase letters as capitals).
+///
+/// ```
+/// # #[cfg(feature = "alloc")] {
+/// use vector_text_core::{Font, SegmentFont};
+///
+/// assert!(SegmentFont::Seven.supports_char('8'));
+/// assert!(!SegmentFont::Seven.supports_char('W'));
+/// assert!(SegmentFont::Sixteen.supports_char('W'));
+/// let points = SegmentFont::Seven.render_text("12:34");
+/// # }
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum SegmentFont {
+    /// A seven-segment display, which draws digits and a few letters
+    Seven,
+    /// A sixteen-segment display, which draws digits, capital letters, and some symbols
+    Sixteen,
+}
+
+/// A segment, from one point to another
+type Segment = ((i8, i8), (i8, i8));
+
+/// Every glyph is drawn in a box 8 units wide and 12 tall, with this much space on each side
+const SIDE_BEARING: i8 = 2;
+
+/// Maximum number of points in a glyph
+const MAX_POINTS: usize = 24;
+
+/// The dots drawn after the segments of each display, shared by both displays
+const DOTS: [Segment; 3] = [
+    // Decimal point
+    ((4, -1), (4, 0)),
+    // Colon
+    ((4, -9), (4, -8)),
+    ((4, -4), (4, -3)),
+];
+
+const DOT: u32 = 1 << 0;
+const COLON: u32 = 1 << 1 | 1 << 2;
+
+/// Segments of the seven-segment display, going around the outside and then across
+/// the middle
+const SEVEN_SEGMENTS: [Segment; 7] = [
+    ((0, -12), (8, -12)),
+    ((8, -12), (8, -6)),
+    ((8, -6), (8, 0)),
+    ((8, 0), (0, 0)),
+    ((0, 0), (0, -6)),
+    ((0, -6), (0, -12)),
+    ((0, -6), (8, -6)),
+];
+
+mod seven {
+    pub const A: u32 = 1 << 3;
+    pub const B: u32 = 1 << 4;
+    pub const C: u32 = 1 << 5;
+    pub const D: u32 = 1 << 6;
+    pub const E: u32 = 1 << 7;
+    pub const F: u32 = 1 << 8;
+    pub const G: u32 = 1 << 9;
+}
+
+/// Segments of the sixteen-segment display: around the outside, across the middle,
+/// then the diagonals and verticals meeting in the center
+const SIXTEEN_SEGMENTS: [Segment; 16] = [
+    ((0, -12), (4, -12)),
+    ((4, -12), (8, -12)),
+    ((8, -12), (8, -6)),
+    ((8, -6), (8, 0)),
+    ((8, 0), (4, 0)),
+    ((4, 0), (0, 0)),
+    ((0, 0), (0, -6)),
+    ((0, -6), (0, -12)),
+    ((0, -6), (4, -6)),
+    ((4, -6), (8, -6)),
+    ((0, -12), (4, -6)),
+    ((4, -12), (4, -6)),
+    ((8, -12), (4, -6)),
+    ((4, -6), (0, 0)),
+    ((4, -6), (4, 0)),
+    ((4, -6), (8, 0)),
+];
+
+mod sixteen {
+    pub const A1: u32 = 1 << 3;
+    pub const A2: u32 = 1 << 4;
+    pub const B: u32 = 1 << 5;
+    pub const C: u32 = 1 << 6;
+    pub const D2: u32 = 1 << 7;
+    pub const D1: u32 = 1 << 8;
+    pub const E: u32 = 1 << 9;
+    pub const F: u32 = 1 << 10;
+    pub const G1: u32 = 1 << 11;
+    pub const G2: u32 = 1 << 12;
+    pub const H: u32 = 1 << 13;
+    pub const I: u32 = 1 << 14;
+    pub const J: u32 = 1 << 15;
+    pub const K: u32 = 1 << 16;
+    pub const L: u32 = 1 << 17;
+    pub const M: u32 = 1 << 18;
+}
+
+/// The segments lit for each character on the seven-segment display, sorted by character
+#[rustfmt::skip]
+const SEVEN_CHARS: [(char, u32); 44] = {
+    use seven::*;
+    [
+        (' ', 0),
+        ('"', B | F),
+        ('-', G),
+        ('.', DOT),
+        ('0', A | B | C | D | E | F),
+        ('1', B | C),
+        ('2', A | B | D | E | G),
+        ('3', A | B | C | D | G),
+        ('4', B | C | F | G),
+        ('5', A | C | D | F | G),
+        ('6', A | C | D | E | F | G),
+        ('7', A | B | C),
+        ('8', A | B | C | D | E | F | G),
+        ('9', A | B | C | D | F | G),
+        (':', COLON),
+        ('=', D | G),
+        ('A', A | B | C | E | F | G),
+        ('C', A | D | E | F),
+        ('E', A | D | E | F | G),
+        ('F', A | E | F | G),
+        ('G', A | C | D | E | F),
+        ('H', B | C | E | F | G),
+        ('I', E | F),
+        ('J', B | C | D | E),
+        ('L', D | E | F),
+        ('O', A | B | C | D | E | F),
+        ('P', A | B | E | F | G),
+        ('S', A | C | D | F | G),
+        ('U', B | C | D | E | F),
+        ('_', D),
+        ('b', C | D | E | F | G),
+        ('c', D | E | G),
+        ('d', B | C | D | E | G),
+        ('h', C | E | F | G),
+        ('i', C),
+        ('j', C | D),
+        ('l', E | F),
+        ('n', C | E | G),
+        ('o', C | D | E | G),
+        ('q', A | B | C | F | G),
+        ('r', E | G),
+        ('t', D | E | F | G),
+        ('u', C | D | E),
+        ('y', B | C | D | F | G),
+    ]
+};
+
+/// The segments lit for each character on the sixteen-segment display, sorted by character
+#[rustfmt::skip]
+const SIXTEEN_CHARS: [(char, u32); 59] = {
+    use sixteen::*;
+    [
+        (' ', 0),
+        ('"', F | I),
+        ('$', A1 | A2 | C | D1 | D2 | F | G1 | G2 | I | L),
+        ('%', A1 | F | G1 | I | C | D2 | G2 | L | J | K),
+        ('\'', I),
+        ('(', J | M),
+        (')', H | K),
+        ('*', G1 | G2 | H | I | J | K | L | M),
+        ('+', G1 | G2 | I | L),
+        (',', K),
+        ('-', G1 | G2),
+        ('.', DOT),
+        ('/', J | K),
+        ('0', A1 | A2 | B | C | D1 | D2 | E | F | J | K),
+        ('1', B | C | J),
+        ('2', A1 | A2 | B | D1 | D2 | E | G1 | G2),
+        ('3', A1 | A2 | B | C | D1 | D2 | G2),
+        ('4', B | C | F | G1 | G2),
+        ('5', A1 | A2 | C | D1 | D2 | F | G1 | G2),
+        ('6', A1 | A2 | C | D1 | D2 | E | F | G1 | G2),
+        ('7', A1 | A2 | B | C),
+        ('8', A1 | A2 | B | C | D1 | D2 | E | F | G1 | G2),
+        ('9', A1 | A2 | B | C | D1 | D2 | F | G1 | G2),
+        (':', COLON),
+        ('<', J | M),
+        ('=', G1 | G2 | D1 | D2),
+        ('>', H | K),
+        ('?', A1 | A2 | B | G2 | L),
+        ('A', A1 | A2 | B | C | E | F | G1 | G2),
+        ('B', A1 | A2 | B | C | D1 | D2 | G2 | I | L),
+        ('C', A1 | A2 | D1 | D2 | E | F),
+        ('D', A1 | A2 | B | C | D1 | D2 | I | L),
+        ('E', A1 | A2 | D1 | D2 | E | F | G1),
+        ('F', A1 | A2 | E | F | G1),
+        ('G', A1 | A2 | C | D1 | D2 | E | F | G2),
+        ('H', B | C | E | F | G1 | G2),
+        ('I', A1 | A2 | D1 | D2 | I | L),
+        ('J', B | C | D1 | D2 | E),
+        ('K', E | F | G1 | J | M),
+        ('L', D1 | D2 | E | F),
+        ('M', B | C | E | F | H | J),
+        ('N', B | C | E | F | H | M),
+        ('O', A1 | A2 | B | C | D1 | D2 | E | F),
+        ('P', A1 | A2 | B | E | F | G1 | G2),
+        ('Q', A1 | A2 | B | C | D1 | D2 | E | F | M),
+        ('R', A1 | A2 | B | E | F | G1 | G2 | M),
+        ('S', A1 | A2 | C | D1 | D2 | F | G1 | G2),
+        ('T', A1 | A2 | I | L),
+        ('U', B | C | D1 | D2 | E | F),
+        ('V', E | F | J | K),
+        ('W', B | C | E | F | K | M),
+        ('X', H | J | K | M),
+        ('Y', H | J | L),
+        ('Z', A1 | A2 | D1 | D2 | J | K),
+        ('[', A2 | D2 | I | L),
+        ('\\', H | M),
+        (']', A1 | D1 | I | L),
+        ('_', D1 | D2),
+        ('|', I | L),
+    ]
+};
+
+/// The strokes of a glyph, in a fixed-size buffer.
+struct SegmentGlyph {
+    points: [PackedPoint; MAX_POINTS],
+    len: usize,
+}
+
+/// Draw the lit segments of a display, joining segments which meet end to end into a
+/// single stroke.
+const fn draw(mask: u32, segments: &[Segment]) -> SegmentGlyph {
+    const fn point((x, y): (i8, i8), pen: bool) -> PackedPoint {
+        PackedPoint { x, y, pen }
+    }
+
+    const fn at(p: PackedPoint, (x, y): (i8, i8)) -> bool {
+        p.x == x && p.y == y
+    }
+
+    let mut glyph = SegmentGlyph {
+        points: [point((0, 0), false); MAX_POINTS],
+        len: 0,
+    };
+
+    // The dots come first in the mask, but are drawn last
+    let mut i = 0;
+    while i < DOTS.len() + segments.len() {
+        let bit = (i + DOTS.len()) % (DOTS.len() + segments.len());
+
+        if mask & (1 << bit) != 0 {
+            let (mut from, mut to) = if bit < DOTS.len() {
+                DOTS[bit]
+            } else {
+                segments[bit - DOTS.len()]
+            };
+
+            if glyph.len > 0 {
+                let last = glyph.points[glyph.len - 1];
+
+                // Draw the segment backwards if that joins it on to the previous one
+                if at(last, to) && !at(last, from) {
+                    (from, to) = (to, from);
+                }
+            }
+
+            if glyph.len == 0 || !at(glyph.points[glyph.len - 1], from) {
+                glyph.points[glyph.len] = point(from, false);
+                glyph.len += 1;
+            }
+
+            glyph.points[glyph.len] = point(to, true);
+            glyph.len += 1;
+        }
+
+        i += 1;
+    }
+
+    glyph
+}
+
+/// Draw the glyph for each character of a display.
+const fn draw_all<const N: usize>(
+    chars: [(char, u32); N],
+    segments: &[Segment],
+) -> [(char, SegmentGlyph); N] {
+    let mut glyphs = [const {
+        (
+            ' ',
+            SegmentGlyph {
+                points: [PackedPoint {
+                    x: 0,
+                    y: 0,
+                    pen: false,
+                }; MAX_POINTS],
+                len: 0,
+            },
+        )
+    }; N];
+
+    let mut i = 0;
+    while i < N {
+        glyphs[i] = (chars[i].0, draw(chars[i].1, segments));
+        i += 1;
+    }
+
+    glyphs
+}
+
+static SEVEN_GLYPHS: [(char, SegmentGlyph); SEVEN_CHARS.len()] =
+    draw_all(SEVEN_CHARS, &SEVEN_SEGMENTS);
+
+static SIXTEEN_GLYPHS: [(char, SegmentGlyph); SIXTEEN_CHARS.len()] =
+    draw_all(SIXTEEN_CHARS, &SIXTEEN_SEGMENTS);
+
+impl SegmentFont {
+    /// Every segment font.
+    pub const ALL: [Self; 2] = [Self::Seven, Self::Sixteen];
+
+    /// Iterate over every segment font.
+    pub fn iter() -> impl Iterator<Item = Self> {
+        Self::ALL.into_iter()
+    }
+
+    /// The name of this font: `seven` or `sixteen`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Seven => "seven",
+            Self::Sixteen => "sixteen",
+        }
+    }
+
+    /// Iterate over the characters this font has glyphs for, in increasing order.
+    pub fn chars(&self) -> impl Iterator<Item = char> + '_ {
+        (' '..='~').filter(|&c| self.supports_char(c))
+    }
+
+    fn table(&self) -> &'static [(char, SegmentGlyph)] {
+        match self {
+            Self::Seven => &SEVEN_GLYPHS,
+            Self::Sixteen => &SIXTEEN_GLYPHS,
+        }
+    }
+}
+
+impl Font for SegmentFont {
+    fn glyph(&self, character: char) -> Option<Glyph<'_>> {
+        let table = self.table();
+        let find = |c| table.binary_search_by_key(&c, |&(c, _)| c).ok();
+
+        let other_case = if character.is_ascii_lowercase() {
+            character.to_ascii_uppercase()
+        } else {
+            character.to_ascii_lowercase()
+        };
+
+        let glyph = &table[find(character).or_else(|| find(other_case))?].1;
+
+        Some(Glyph {
+            left: -SIDE_BEARING,
+            right: 8 + SIDE_BEARING,
+            strokes: Strokes::Points(&glyph.points[..glyph.len]),
+            anchors: &[],
+        })
+    }
+
+    fn metrics(&self) -> FontMetrics {
+        FontMetrics {
+            baseline: 0,
+            cap_height: 12,
+            x_height: match self {
+                Self::Seven => 6,
+                // Lowercase letters are drawn as capitals
+                Self::Sixteen => 12,
+            },
+            ascender: 12,
+            descender: 0,
+            average_advance: 8 + 2 * SIDE_BEARING as i16,
+        }
+    }
+}
+
+impl core::fmt::Display for SegmentFont {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl core::str::FromStr for SegmentFont {
+    type Err = ParseFontError;
+
+    /// Parse the name of a segment font (as given by [SegmentFont::name]), ignoring case.
+    fn from_str(name: &str) -> Result<Self, ParseFontError> {
+        Self::iter()
+            .find(|font| font.name().eq_ignore_ascii_case(name))
+            .ok_or(ParseFontError)
+    }
+}
@@ -0,0 +1,60 @@
+use alloc::vec::Vec;
+
+use crate::Point;
+
+/// A point with how brightly to draw the line to it, for laser projectors and
+/// oscilloscopes with an intensity (Z) input, which can draw more than on or off.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct ShadedPoint {
+    pub x: i16,
+    pub y: i16,
+    /// Brightness of the line to this point, from 0 (blanked, as for a move) to 255
+    pub intensity: u8,
+}
+
+impl ShadedPoint {
+    /// Is the beam turned off on the way to this point, as for a move?
+    pub fn is_blanked(&self) -> bool {
+        self.intensity == 0
+    }
+}
+
+impl From<Point> for ShadedPoint {
+    /// Draw lines at full brightness, and blank moves.
+    fn from(point: Point) -> Self {
+        Self {
+            x: point.x,
+            y: point.y,
+            intensity: if point.pen { u8::MAX } else { 0 },
+        }
+    }
+}
+
+/// Give each line of rendered text a brightness, such as to dim some words or fade text
+/// in and out.
+///
+/// `intensity` is called with the index of each point drawn with the pen down, and
+/// gives the brightness of the line to it. Moves are always blanked.
+///
+/// ```
+/// use vector_text_core::{shade, Point};
+///
+/// let point = |x, y, pen| Point { x, y, pen };
+/// let points = [point(0, 0, false), point(10, 0, true), point(10, 10, true)];
+///
+/// // Fade the line in as it is drawn
+/// let shaded = shade(&points, |i| (i * 100) as u8);
+/// assert!(shaded[0].is_blanked());
+/// assert_eq!((shaded[1].intensity, shaded[2].intensity), (100, 200));
+/// ```
+pub fn shade(points: &[Point], mut intensity: impl FnMut(usize) -> u8) -> Vec<ShadedPoint> {
+    points
+        .iter()
+        .enumerate()
+        .map(|(i, &point)| ShadedPoint {
+            intensity: if point.pen { intensity(i) } else { 0 },
+            ..point.into()
+        })
+        .collect()
+}
@@ -0,0 +1,312 @@
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+use core::slice;
+
+use crate::PackedPoint;
+
+/// The points which make up a glyph.
+///
+/// Usually these are a slice of [PackedPoint]s, but the built-in fonts can instead store
+/// them delta-encoded (with their `compress` feature), which takes about half the space,
+/// and decode them while rendering. With their `progmem` feature, they are kept in the
+/// program memory of AVR microcontrollers, and read from it as they are drawn.
+///
+/// ```
+/// # #[cfg(feature = "alloc")] {
+/// use vector_text_core::{PackedPoint, Strokes};
+///
+/// let points = [
+///     PackedPoint { x: -3, y: 0, pen: false },
+///     PackedPoint { x: 0, y: 10, pen: true },
+///     PackedPoint { x: 3, y: 0, pen: true },
+/// ];
+///
+/// let encoded = Strokes::encode_delta(&points);
+/// assert_eq!(encoded.len(), 5);
+///
+/// let strokes = Strokes::Delta(&encoded);
+/// assert_eq!(strokes.len(), 3);
+/// assert!(strokes.iter().zip(points).all(|(a, b)| (a.x, a.y, a.pen) == (b.x, b.y, b.pen)));
+/// # }
+/// ```
+#[derive(Debug, Copy, Clone)]
+pub enum Strokes<'a> {
+    /// Points stored as they are
+    Points(&'a [PackedPoint]),
+    /// Points encoded by [Strokes::encode_delta]
+    Delta(&'a [u8]),
+    /// Points stored as they are, three bytes each (as by [PackedPoint::slice_from_bytes]),
+    /// in program memory
+    #[cfg(feature = "progmem")]
+    ProgmemPoints(ProgmemBytes),
+    /// Points encoded by [Strokes::encode_delta], in program memory
+    #[cfg(feature = "progmem")]
+    ProgmemDelta(ProgmemBytes),
+}
+
+/// Bytes in the program memory (flash) of an AVR microcontroller, which can't be read
+/// like other memory, for the points of the built-in fonts with their `progmem` feature.
+///
+/// On AVR, the bytes are read with the `lpm` instruction, which needs a nightly compiler
+/// for its inline assembly; elsewhere they are read like any other memory, so that code
+/// using them can be tested on the host.
+///
+/// ```
+/// use vector_text_core::{PackedPoint, ProgmemBytes, Strokes};
+///
+/// static POINTS: [u8; 6] = [0, 0, 0, 5, 0xFB, 1];
+///
+/// // SAFETY: POINTS is a static which is never written to
+/// let bytes = unsafe { ProgmemBytes::new(&raw const POINTS as *const u8, POINTS.len()) };
+/// let strokes = Strokes::ProgmemPoints(bytes);
+///
+/// let last = strokes.iter().last().unwrap();
+/// assert_eq!((last.x, last.y, last.pen), (5, -5, true));
+/// ```
+#[cfg(feature = "progmem")]
+#[derive(Debug, Copy, Clone)]
+pub struct ProgmemBytes {
+    start: *const u8,
+    len: usize,
+}
+
+// SAFETY: the bytes are never written to, so they can be read from any thread, like a
+// shared slice
+#[cfg(feature = "progmem")]
+unsafe impl Send for ProgmemBytes {}
+#[cfg(feature = "progmem")]
+unsafe impl Sync for ProgmemBytes {}
+
+#[cfg(feature = "progmem")]
+impl ProgmemBytes {
+    /// Refer to `len` bytes of program memory, starting at `start`.
+    ///
+    /// # Safety
+    ///
+    /// The bytes must be in program memory on AVR (such as a static in the
+    /// `.progmem.data` linker section), or in ordinary memory on other targets, and must
+    /// last as long as the program without being written to.
+    pub const unsafe fn new(start: *const u8, len: usize) -> Self {
+        Self { start, len }
+    }
+
+    /// Number of bytes.
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Are there no bytes at all?
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Read the byte at `index`, if there is one.
+    pub fn get(&self, index: usize) -> Option<u8> {
+        // SAFETY: the index is in bounds, and the bytes are in program memory (see `new`)
+        (index < self.len).then(|| unsafe { read_progmem(self.start.add(index)) })
+    }
+}
+
+/// Read a byte of program memory, in the same way as `avr_progmem::raw::read_byte`.
+#[cfg(all(feature = "progmem", target_arch = "avr"))]
+unsafe fn read_progmem(address: *const u8) -> u8 {
+    let byte: u8;
+    // SAFETY: `lpm` only reads the byte of program memory the Z register points to
+    unsafe {
+        core::arch::asm!(
+            "lpm {}, Z",
+            out(reg) byte,
+            in("Z") address,
+            options(pure, readonly, preserves_flags, nostack),
+        );
+    }
+    byte
+}
+
+/// Read a byte of "program memory", which on targets other than AVR is ordinary memory.
+#[cfg(all(feature = "progmem", not(target_arch = "avr")))]
+unsafe fn read_progmem(address: *const u8) -> u8 {
+    // SAFETY: the caller gives the address of a byte which can be read
+    unsafe { address.read() }
+}
+
+/// Smallest and largest deltas which fit in the 3 bits of a one-byte point
+#[cfg(feature = "alloc")]
+const SHORT_DELTA: (i8, i8) = (-4, 3);
+/// Largest magnitude of an X delta which fits in the 6 bits of a two-byte point
+#[cfg(feature = "alloc")]
+const MEDIUM_DELTA: i8 = 31;
+/// Value of the 6 bits of an X delta which means it follows in a byte of its own
+const DELTA_ESCAPE: u8 = 0b10_0000;
+
+impl<'a> Strokes<'a> {
+    /// Iterate over the points.
+    pub fn iter(&self) -> StrokesIter<'a> {
+        StrokesIter(match *self {
+            Self::Points(points) => Decoder::Points(points.iter()),
+            Self::Delta(bytes) => Decoder::Delta {
+                bytes: Bytes::Memory(bytes.iter()),
+                x: 0,
+                y: 0,
+            },
+            #[cfg(feature = "progmem")]
+            Self::ProgmemPoints(bytes) => Decoder::Packed(Bytes::Progmem(bytes, 0)),
+            #[cfg(feature = "progmem")]
+            Self::ProgmemDelta(bytes) => Decoder::Delta {
+                bytes: Bytes::Progmem(bytes, 0),
+                x: 0,
+                y: 0,
+            },
+        })
+    }
+
+    /// Number of points.
+    ///
+    /// This is quick for plain points, but delta-encoded ones have to be decoded to be
+    /// counted.
+    pub fn len(&self) -> usize {
+        match self {
+            Self::Points(points) => points.len(),
+            #[cfg(feature = "progmem")]
+            Self::ProgmemPoints(bytes) => bytes.len() / PackedPoint::SIZE,
+            _ => self.iter().count(),
+        }
+    }
+
+    /// Are there no points at all?
+    pub fn is_empty(&self) -> bool {
+        match self {
+            Self::Points(points) => points.is_empty(),
+            Self::Delta(bytes) => bytes.is_empty(),
+            #[cfg(feature = "progmem")]
+            Self::ProgmemPoints(bytes) | Self::ProgmemDelta(bytes) => bytes.is_empty(),
+        }
+    }
+
+    /// Delta-encode some points, for use with [Strokes::Delta].
+    ///
+    /// Each point is stored as its distance from the previous one (or from the origin,
+    /// for the first point), in one of three forms:
+    ///
+    /// - `1PXXXYYY`, if both deltas are between -4 and 3
+    /// - `0PXXXXXX YYYYYYYY`, if the X delta is between -31 and 31
+    /// - `0P100000 XXXXXXXX YYYYYYYY` otherwise
+    ///
+    /// where `P` is set if the pen is down. Deltas are stored in two's complement, and
+    /// wrap around so that every point can be reached.
+    #[cfg(feature = "alloc")]
+    pub fn encode_delta(points: &[PackedPoint]) -> Vec<u8> {
+        let mut out = Vec::new();
+        let (mut x, mut y) = (0i8, 0i8);
+
+        for point in points {
+            let dx = point.x.wrapping_sub(x);
+            let dy = point.y.wrapping_sub(y);
+            let pen = (point.pen as u8) << 6;
+            (x, y) = (point.x, point.y);
+
+            let short = SHORT_DELTA.0..=SHORT_DELTA.1;
+            if short.contains(&dx) && short.contains(&dy) {
+                out.push(0x80 | pen | (dx as u8 & 0b111) << 3 | (dy as u8 & 0b111));
+            } else if dx.unsigned_abs() <= MEDIUM_DELTA as u8 {
+                out.extend([pen | (dx as u8 & 0b11_1111), dy as u8]);
+            } else {
+                out.extend([pen | DELTA_ESCAPE, dx as u8, dy as u8]);
+            }
+        }
+
+        out
+    }
+}
+
+impl<'a> From<&'a [PackedPoint]> for Strokes<'a> {
+    fn from(points: &'a [PackedPoint]) -> Self {
+        Self::Points(points)
+    }
+}
+
+impl<'a> IntoIterator for Strokes<'a> {
+    type Item = PackedPoint;
+    type IntoIter = StrokesIter<'a>;
+
+    fn into_iter(self) -> StrokesIter<'a> {
+        self.iter()
+    }
+}
+
+/// An iterator over the points of some [Strokes].
+pub struct StrokesIter<'a>(Decoder<'a>);
+
+enum Decoder<'a> {
+    Points(slice::Iter<'a, PackedPoint>),
+    /// Points stored as they are, three bytes each
+    #[cfg(feature = "progmem")]
+    Packed(Bytes<'a>),
+    Delta {
+        bytes: Bytes<'a>,
+        x: i8,
+        y: i8,
+    },
+}
+
+/// The bytes of some strokes, wherever they are kept
+enum Bytes<'a> {
+    Memory(slice::Iter<'a, u8>),
+    /// The bytes, and the index of the next one
+    #[cfg(feature = "progmem")]
+    Progmem(ProgmemBytes, usize),
+}
+
+impl Iterator for Bytes<'_> {
+    type Item = u8;
+
+    fn next(&mut self) -> Option<u8> {
+        match self {
+            Self::Memory(bytes) => bytes.next().copied(),
+            #[cfg(feature = "progmem")]
+            Self::Progmem(bytes, index) => {
+                let byte = bytes.get(*index)?;
+                *index += 1;
+                Some(byte)
+            }
+        }
+    }
+}
+
+/// Interpret the low `bits` bits of a byte as a two's complement number.
+fn sign_extend(value: u8, bits: u32) -> i8 {
+    ((value << (8 - bits)) as i8) >> (8 - bits)
+}
+
+impl Iterator for StrokesIter<'_> {
+    type Item = PackedPoint;
+
+    fn next(&mut self) -> Option<PackedPoint> {
+        match &mut self.0 {
+            Decoder::Points(points) => points.next().copied(),
+            #[cfg(feature = "progmem")]
+            Decoder::Packed(bytes) => Some(PackedPoint {
+                x: bytes.next()? as i8,
+                y: bytes.next()? as i8,
+                pen: bytes.next()? != 0,
+            }),
+            Decoder::Delta { bytes, x, y } => {
+                let first = bytes.next()?;
+                let pen = first & 0x40 != 0;
+
+                let (dx, dy) = if first & 0x80 != 0 {
+                    (sign_extend(first >> 3, 3), sign_extend(first, 3))
+                } else if first & 0b11_1111 == DELTA_ESCAPE {
+                    (bytes.next()? as i8, bytes.next()? as i8)
+                } else {
+                    (sign_extend(first, 6), bytes.next()? as i8)
+                };
+
+                *x = x.wrapping_add(dx);
+                *y = y.wrapping_add(dy);
+
+                Some(PackedPoint { x: *x, y: *y, pen })
+            }
+        }
+    }
+}
@@ -0,0 +1,157 @@
+use alloc::vec::Vec;
+
+use crate::{FontMetrics, Glyph};
+
+/// Something wrong with the data of a glyph, found by [validate].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum GlyphProblem {
+    /// The left boundary is to the right of the right boundary, so drawing the glyph
+    /// moves backward
+    Reversed { left: i8, right: i8 },
+    /// The first point is drawn to with the pen down, from wherever the pen was left by
+    /// the glyph before
+    StartsPenDown,
+    /// The glyph has points, but only moves to them, so nothing is drawn, though its
+    /// character isn't whitespace
+    NothingDrawn,
+    /// The glyph has no points, though its character isn't whitespace
+    Empty,
+    /// A point is further from the glyph's box (from its left to its right boundary,
+    /// and from the top of the [ascender](FontMetrics::ascender) to the bottom of the
+    /// descender) than the height of a line, as if it were misread
+    OutOfRange { x: i8, y: i8 },
+}
+
+impl core::fmt::Display for GlyphProblem {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Reversed { left, right } => {
+                write!(
+                    f,
+                    "left boundary {} is right of right boundary {}",
+                    left, right
+                )
+            }
+            Self::StartsPenDown => f.write_str("first point is drawn to with the pen down"),
+            Self::NothingDrawn => f.write_str("points are only moved to, never drawn"),
+            Self::Empty => f.write_str("no points, though the character isn't whitespace"),
+            Self::OutOfRange { x, y } => write!(f, "point ({}, {}) is far outside the glyph", x, y),
+        }
+    }
+}
+
+/// The problems found in a font by [validate].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FontReport {
+    /// Number of glyphs checked
+    pub glyphs: usize,
+    /// Each problem, with the character of the glyph it was found in
+    pub problems: Vec<(char, GlyphProblem)>,
+}
+
+impl FontReport {
+    /// Were no problems found?
+    pub fn is_ok(&self) -> bool {
+        self.problems.is_empty()
+    }
+}
+
+impl core::fmt::Display for FontReport {
+    /// Write each problem on a line of its own.
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        for (character, problem) in &self.problems {
+            writeln!(
+                f,
+                "U+{:04X} {:?}: {}",
+                *character as u32, character, problem
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Check the glyphs of a font for data which is likely to be wrong, such as in a font
+/// loaded at runtime or converted from another format.
+///
+/// `lookup` is called with each of `chars`, and the glyphs it finds are checked for
+/// each [GlyphProblem].
+///
+/// ```
+/// use vector_text_core::{validate, CustomFont, Font, GlyphProblem, MinimalFont, PackedPoint};
+///
+/// let report = validate(' '..='~', |c| MinimalFont.glyph(c), &MinimalFont.metrics());
+/// assert!(report.is_ok());
+///
+/// // A slash which is drawn from wherever the pen was before it
+/// let mut font = CustomFont::new(MinimalFont.metrics());
+/// font.add_glyph('/', 0, 6, &[
+///     PackedPoint { x: 0, y: 12, pen: true },
+///     PackedPoint { x: 6, y: 0, pen: true },
+/// ]);
+///
+/// let report = validate(['/'], |c| font.glyph(c), &font.metrics());
+/// assert_eq!(report.problems, [('/', GlyphProblem::StartsPenDown)]);
+/// ```
+pub fn validate<'a>(
+    chars: impl IntoIterator<Item = char>,
+    lookup: impl Fn(char) -> Option<Glyph<'a>>,
+    metrics: &FontMetrics,
+) -> FontReport {
+    let mut report = FontReport::default();
+
+    for character in chars {
+        let Some(glyph) = lookup(character) else {
+            continue;
+        };
+        report.glyphs += 1;
+
+        let mut problem = |problem| report.problems.push((character, problem));
+
+        if glyph.left > glyph.right {
+            problem(GlyphProblem::Reversed {
+                left: glyph.left,
+                right: glyph.right,
+            });
+        }
+
+        let mut points = glyph.strokes.iter().peekable();
+        match points.peek() {
+            None if !character.is_whitespace() => problem(GlyphProblem::Empty),
+            Some(first) if first.pen => problem(GlyphProblem::StartsPenDown),
+            _ => {}
+        }
+
+        let slack = metrics.line_height();
+        let top = metrics.baseline - metrics.ascender - slack;
+        let bottom = metrics.baseline + metrics.descender + slack;
+        let (left, right) = (
+            glyph.left.min(glyph.right) as i16 - slack,
+            glyph.left.max(glyph.right) as i16 + slack,
+        );
+
+        let mut drawn = false;
+        let mut out_of_range = None;
+        for point in points {
+            drawn |= point.pen;
+            let (x, y) = (point.x as i16, point.y as i16);
+            if out_of_range.is_none()
+                && !((left..=right).contains(&x) && (top..=bottom).contains(&y))
+            {
+                out_of_range = Some(GlyphProblem::OutOfRange {
+                    x: point.x,
+                    y: point.y,
+                });
+            }
+        }
+
+        if !drawn && !glyph.strokes.is_empty() && !character.is_whitespace() {
+            problem(GlyphProblem::NothingDrawn);
+        }
+        if let Some(out_of_range) = out_of_range {
+            problem(out_of_range);
+        }
+    }
+
+    report
+}
@@ -0,0 +1,311 @@
+use alloc::vec::Vec;
+
+use crate::is_combining_mark;
+use crate::render::{Engine, Item, SOFT_HYPHEN, SoftBreak, chars, is_no_break_space};
+use crate::{Glyph, Hyphenation, RenderError};
+
+/// Find where to break lines of text so that none is wider than `max_width`.
+///
+/// Lines are broken after spaces (but never at a no-break space), or at soft hyphens and
+/// the places [Hyphenation] allows, in which case a hyphen is drawn at the end of the
+/// line. They may also be broken without a space where [breaks_between] allows, such as
+/// after dashes or between ideographs. A word too long to fit on a line by itself is
+/// broken wherever it overflows.
+pub(crate) fn break_lines<'g, F>(
+    text: &str,
+    max_width: i32,
+    engine: &mut Engine<'_, F>,
+) -> Result<Vec<SoftBreak>, RenderError>
+where
+    F: FnMut(usize, char) -> Result<(Glyph<'g>, i16), RenderError>,
+{
+    let mut breaks = Vec::new();
+
+    // Width of the current line so far
+    let mut x = engine.options.first_line_indent as i32;
+    // The last place the current line could be broken, and the width of the text after it
+    let mut candidate: Option<(SoftBreak, i32)> = None;
+    // Was the previous character a space we could break at?
+    let mut in_spaces = false;
+    // Places the current word may be hyphenated, as byte offsets in the text
+    let mut hyphens = Vec::new();
+    let mut in_word = false;
+    // The character before this one, unless it was a space or line break
+    let mut previous = None;
+
+    for (offset, character) in chars(text, engine.options) {
+        if is_letter(character) {
+            if !in_word {
+                let len = text[offset..]
+                    .find(|c| !is_letter(c))
+                    .unwrap_or(text.len() - offset);
+
+                hyphens.clear();
+                engine
+                    .options
+                    .hyphenation
+                    .breaks(&text[offset..offset + len], &mut hyphens);
+                hyphens.retain(|&at| at > 0 && at < len);
+                hyphens.iter_mut().for_each(|at| *at += offset);
+                hyphens.sort_unstable();
+                hyphens.reverse();
+            }
+            in_word = true;
+
+            // A hyphen here takes the place of a soft hyphen before this character
+            if hyphens.last() == Some(&offset) {
+                hyphens.pop();
+                let hyphen = engine.hyphen(offset);
+
+                if x + engine.advance(&hyphen, 0) <= max_width {
+                    candidate = Some((
+                        SoftBreak {
+                            at: offset,
+                            resume: offset,
+                            hyphen: true,
+                        },
+                        0,
+                    ));
+                }
+            }
+        } else {
+            in_word = false;
+        }
+
+        let item = engine.resolve(offset, character)?;
+        let advance = engine.advance(&item, x);
+        let next = offset + character.len_utf8();
+
+        if let Item::LineBreak = item {
+            x = 0;
+            candidate = None;
+            in_spaces = false;
+            previous = None;
+            continue;
+        }
+
+        if let Item::Mark(..) = item {
+            // Marks take no space, and must stay on the same line as their base
+            continue;
+        }
+
+        if character.is_whitespace() && !is_no_break_space(character) {
+            // Spaces are allowed to hang past the end of the line
+            x += advance;
+
+            match &mut candidate {
+                Some((soft_break, rest)) if in_spaces => {
+                    soft_break.resume = next;
+                    *rest = 0;
+                }
+                _ => {
+                    candidate = Some((
+                        SoftBreak {
+                            at: offset,
+                            resume: next,
+                            hyphen: false,
+                        },
+                        0,
+                    ))
+                }
+            }
+
+            in_spaces = true;
+            previous = None;
+            continue;
+        }
+
+        in_spaces = false;
+
+        if let Some(before) = previous
+            && breaks_between(before, character)
+        {
+            candidate = Some((
+                SoftBreak {
+                    at: offset,
+                    resume: offset,
+                    hyphen: false,
+                },
+                0,
+            ));
+        }
+        previous = Some(character);
+
+        if character == SOFT_HYPHEN {
+            let hyphen = engine.hyphen(offset);
+
+            if x + engine.advance(&hyphen, 0) <= max_width {
+                candidate = Some((
+                    SoftBreak {
+                        at: offset,
+                        resume: next,
+                        hyphen: true,
+                    },
+                    0,
+                ));
+            }
+
+            continue;
+        }
+
+        if x + advance > max_width && x > 0 {
+            if let Some((soft_break, rest)) = candidate.take() {
+                breaks.push(soft_break);
+                x = rest;
+            }
+
+            if x + advance > max_width && x > 0 {
+                // No better place to break this word, so split it here
+                breaks.push(SoftBreak {
+                    at: offset,
+                    resume: offset,
+                    hyphen: false,
+                });
+                x = 0;
+            }
+        }
+
+        x += advance;
+
+        if let Some((_, rest)) = &mut candidate {
+            *rest += advance;
+        }
+    }
+
+    Ok(breaks)
+}
+
+/// May a line be broken between these two characters, with no space between them?
+///
+/// This follows a practical subset of the Unicode line breaking algorithm
+/// ([UAX #14](https://www.unicode.org/reports/tr14/)): lines may break after hyphens,
+/// dashes, and zero width spaces, and before or after ideographs and kana (as CJK text
+/// has no spaces), but never before closing punctuation or after opening punctuation.
+fn breaks_between(before: char, after: char) -> bool {
+    if is_joiner(before) || is_joiner(after) || is_closing(after) || is_opening(before) {
+        return false;
+    }
+
+    match (before, after) {
+        // Zero width space
+        ('\u{200B}', _) => true,
+        // Em dashes may be broken around, but not between
+        ('\u{2014}', '\u{2014}') => false,
+        ('\u{2014}', _) | (_, '\u{2014}') => true,
+        // Hyphens may be broken after, but not when they are minus signs
+        ('-' | '\u{2010}' | '\u{2013}', after) => !after.is_ascii_digit(),
+        (before, after) => is_ideographic(before) || is_ideographic(after),
+    }
+}
+
+/// Does this character keep the characters on either side of it on the same line?
+fn is_joiner(character: char) -> bool {
+    matches!(character, '\u{2060}' | '\u{FEFF}') || is_no_break_space(character)
+}
+
+/// Is this character punctuation which starts something, and must stay with the
+/// character after it?
+fn is_opening(character: char) -> bool {
+    matches!(
+        character,
+        '(' | '['
+            | '{'
+            | '\u{3008}'
+            | '\u{300A}'
+            | '\u{300C}'
+            | '\u{300E}'
+            | '\u{3010}'
+            | '\u{3014}'
+            | '\u{3016}'
+            | '\u{3018}'
+            | '\u{301A}'
+            | '\u{FF08}'
+            | '\u{FF3B}'
+            | '\u{FF5B}'
+    )
+}
+
+/// Is this character punctuation (or a small kana or other mark) which must stay with
+/// the character before it?
+fn is_closing(character: char) -> bool {
+    matches!(
+        character,
+        ')' | ']' | '}' | ',' | '.' | ';' | ':' | '!' | '?' | '%'
+            // Ideographic comma, full stop, and closing brackets
+            | '\u{3001}' | '\u{3002}' | '\u{3009}' | '\u{300B}' | '\u{300D}' | '\u{300F}'
+            | '\u{3011}' | '\u{3015}' | '\u{3017}' | '\u{3019}' | '\u{301B}'
+            // Iteration marks and the prolonged sound mark
+            | '\u{3005}' | '\u{309D}' | '\u{309E}' | '\u{30FC}' | '\u{30FD}' | '\u{30FE}'
+            // Small hiragana and katakana
+            | '\u{3041}' | '\u{3043}' | '\u{3045}' | '\u{3047}' | '\u{3049}' | '\u{3063}'
+            | '\u{3083}' | '\u{3085}' | '\u{3087}' | '\u{308E}' | '\u{30A1}' | '\u{30A3}'
+            | '\u{30A5}' | '\u{30A7}' | '\u{30A9}' | '\u{30C3}' | '\u{30E3}' | '\u{30E5}'
+            | '\u{30E7}' | '\u{30EE}' | '\u{30F5}' | '\u{30F6}'
+            // Fullwidth punctuation
+            | '\u{FF01}' | '\u{FF09}' | '\u{FF0C}' | '\u{FF0E}' | '\u{FF1A}' | '\u{FF1B}'
+            | '\u{FF1F}' | '\u{FF3D}' | '\u{FF5D}'
+    )
+}
+
+/// Is this character written without spaces around it, like the characters of Chinese
+/// and Japanese?
+fn is_ideographic(character: char) -> bool {
+    matches!(
+        character,
+        // Kana and CJK symbols
+        '\u{3000}'..='\u{30FF}'
+            // CJK Unified Ideographs, with Extension A
+            | '\u{3400}'..='\u{4DBF}'
+            | '\u{4E00}'..='\u{9FFF}'
+            // Hangul syllables
+            | '\u{AC00}'..='\u{D7AF}'
+            // CJK Compatibility Ideographs
+            | '\u{F900}'..='\u{FAFF}'
+            // Fullwidth forms
+            | '\u{FF01}'..='\u{FF60}'
+            // Supplementary ideographs
+            | '\u{20000}'..='\u{3FFFF}'
+    )
+}
+
+/// Is this character part of a word which may be hyphenated?
+fn is_letter(character: char) -> bool {
+    character.is_alphabetic() || is_combining_mark(character)
+}
+
+impl Hyphenation {
+    /// Push the byte offsets in `word` where it may be broken
+    fn breaks(&self, word: &str, out: &mut Vec<usize>) {
+        match self {
+            Self::None => {}
+            Self::Dictionary(words) => {
+                if let Some(entry) = words.iter().find(|entry| matches(entry, word)) {
+                    // Count through the word alongside the entry, which has the hyphens
+                    let mut at = 0;
+                    for c in entry.chars() {
+                        if c == '-' {
+                            out.push(at);
+                        } else {
+                            at += word[at..].chars().next().map_or(0, char::len_utf8);
+                        }
+                    }
+                }
+            }
+            Self::Custom(hyphenate) => hyphenate(word, out),
+        }
+    }
+}
+
+/// Is `entry` of a hyphenation dictionary the word `word`, ignoring case?
+fn matches(entry: &str, word: &str) -> bool {
+    let mut letters = entry.chars().filter(|&c| c != '-');
+    let mut word = word.chars();
+
+    loop {
+        match (letters.next(), word.next()) {
+            (None, None) => return true,
+            (Some(a), Some(b)) if a.to_lowercase().eq(b.to_lowercase()) => {}
+            _ => return false,
+        }
+    }
+}
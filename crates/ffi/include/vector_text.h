@@ -0,0 +1,80 @@
+#ifndef VECTOR_TEXT_H
+#define VECTOR_TEXT_H
+
+/* Generated by cbindgen from crates/ffi/src/lib.rs. Do not edit by hand. */
+
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+
+/**
+ * Returned when there is no font with the given index or name
+ */
+#define VECTOR_TEXT_INVALID_FONT -1
+
+/**
+ * Returned when the text is null or not valid UTF-8
+ */
+#define VECTOR_TEXT_INVALID_TEXT -2
+
+/**
+ * A point of rendered text, like `vector_text::Point`.
+ *
+ * If `pen` is false, the pen moves to this point without drawing. Otherwise, a line is
+ * drawn to it from the previous point.
+ */
+typedef struct VectorTextPoint {
+  int16_t x;
+  int16_t y;
+  bool pen;
+} VectorTextPoint;
+
+/**
+ * Number of built-in fonts, which have indices from 0 up to (but not including) this.
+ */
+size_t vector_text_font_count(void);
+
+/**
+ * Write the name of the font with the given index to `buffer`, such as
+ * `"hershey-romans"`.
+ *
+ * At most `capacity` bytes are written, including the terminating null byte. Returns the
+ * length of the whole name (not including the null byte), or
+ * `VECTOR_TEXT_INVALID_FONT` if there is no such font.
+ *
+ * # Safety
+ *
+ * `buffer` must be valid for writing `capacity` bytes, or null if `capacity` is 0. It
+ * doesn't need to be initialized.
+ */
+ptrdiff_t vector_text_font_name(size_t index, char *buffer, size_t capacity);
+
+/**
+ * Find the index of the font with the given name, ignoring case, or return
+ * `VECTOR_TEXT_INVALID_FONT` if there is none.
+ *
+ * # Safety
+ *
+ * `name` must be a null-terminated string, or null.
+ */
+ptrdiff_t vector_text_find_font(const char *name);
+
+/**
+ * Render UTF-8 text in the font with the given index, writing the points to `points`.
+ *
+ * At most `capacity` points are written. Returns the number of points in the whole
+ * rendered text, `VECTOR_TEXT_INVALID_FONT` if there is no such font, or
+ * `VECTOR_TEXT_INVALID_TEXT` if the text is null or not valid UTF-8. Characters the font
+ * cannot draw are left out.
+ *
+ * # Safety
+ *
+ * `text` must be a null-terminated string, or null. `points` must be valid for writing
+ * `capacity` points, or null if `capacity` is 0. They don't need to be initialized.
+ */
+ptrdiff_t vector_text_render(size_t font_index,
+                             const char *text,
+                             struct VectorTextPoint *points,
+                             size_t capacity);
+
+#endif  /* VECTOR_TEXT_H */
@@ -0,0 +1,156 @@
+//! `vector-text-ffi` exposes the `vector-text` fonts and renderer to C, so that firmware
+//! and laser DAC programs written in C can draw text with them.
+//!
+//! The crate builds a static and a shared library, `vector_text_ffi`, to link against.
+//! The functions are declared in `include/vector_text.h`, which is generated from this
+//! file by [cbindgen](https://github.com/mozilla/cbindgen):
+//!
+//! ```text
+//! cbindgen --config crates/ffi/cbindgen.toml --output crates/ffi/include/vector_text.h crates/ffi
+//! ```
+//!
+//! Fonts are referred to by their index in the list of built-in fonts (see
+//! `vector_text_font_count`), which can be found by name with `vector_text_find_font`.
+//! Rendered points are written to a buffer the caller allocates. Like `snprintf`, the
+//! functions return how much they would have written, so a caller can pass a buffer too
+//! small, then try again with one large enough.
+
+use core::ffi::{CStr, c_char};
+use core::ptr;
+
+use vector_text::{VectorFont, render_text};
+
+/// A point of rendered text, like `vector_text::Point`.
+///
+/// If `pen` is false, the pen moves to this point without drawing. Otherwise, a line is
+/// drawn to it from the previous point.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+#[repr(C)]
+pub struct VectorTextPoint {
+    pub x: i16,
+    pub y: i16,
+    pub pen: bool,
+}
+
+/// Returned when there is no font with the given index or name
+pub const VECTOR_TEXT_INVALID_FONT: isize = -1;
+/// Returned when the text is null or not valid UTF-8
+pub const VECTOR_TEXT_INVALID_TEXT: isize = -2;
+
+/// The built-in font with the given index, if there is one
+fn font(index: usize) -> Option<VectorFont<'static>> {
+    VectorFont::iter().nth(index)
+}
+
+/// Number of built-in fonts, which have indices from 0 up to (but not including) this.
+#[unsafe(no_mangle)]
+pub extern "C" fn vector_text_font_count() -> usize {
+    VectorFont::iter().count()
+}
+
+/// Write the name of the font with the given index to `buffer`, such as
+/// `"hershey-romans"`.
+///
+/// At most `capacity` bytes are written, including the terminating null byte. Returns the
+/// length of the whole name (not including the null byte), or
+/// `VECTOR_TEXT_INVALID_FONT` if there is no such font.
+///
+/// # Safety
+///
+/// `buffer` must be valid for writing `capacity` bytes, or null if `capacity` is 0. It
+/// doesn't need to be initialized.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn vector_text_font_name(
+    index: usize,
+    buffer: *mut c_char,
+    capacity: usize,
+) -> isize {
+    let Some(name) = font(index).and_then(|font| font.name()) else {
+        return VECTOR_TEXT_INVALID_FONT;
+    };
+
+    if capacity > 0 {
+        let len = name.len().min(capacity - 1);
+        // SAFETY: the caller guarantees `buffer` holds `capacity` bytes, and
+        // `len < capacity`. The buffer may be uninitialized, so it is only written to.
+        unsafe {
+            ptr::copy_nonoverlapping(name.as_ptr(), buffer.cast::<u8>(), len);
+            buffer.add(len).write(0);
+        }
+    }
+
+    name.len() as isize
+}
+
+/// Find the index of the font with the given name, ignoring case, or return
+/// `VECTOR_TEXT_INVALID_FONT` if there is none.
+///
+/// # Safety
+///
+/// `name` must be a null-terminated string, or null.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn vector_text_find_font(name: *const c_char) -> isize {
+    if name.is_null() {
+        return VECTOR_TEXT_INVALID_FONT;
+    }
+
+    // SAFETY: the caller guarantees `name` is null-terminated
+    let Ok(name) = unsafe { CStr::from_ptr(name) }.to_str() else {
+        return VECTOR_TEXT_INVALID_FONT;
+    };
+
+    let Some(found) = VectorFont::by_name(name) else {
+        return VECTOR_TEXT_INVALID_FONT;
+    };
+
+    VectorFont::iter()
+        .position(|font| font.name() == found.name())
+        .map_or(VECTOR_TEXT_INVALID_FONT, |index| index as isize)
+}
+
+/// Render UTF-8 text in the font with the given index, writing the points to `points`.
+///
+/// At most `capacity` points are written. Returns the number of points in the whole
+/// rendered text, `VECTOR_TEXT_INVALID_FONT` if there is no such font, or
+/// `VECTOR_TEXT_INVALID_TEXT` if the text is null or not valid UTF-8. Characters the font
+/// cannot draw are left out.
+///
+/// # Safety
+///
+/// `text` must be a null-terminated string, or null. `points` must be valid for writing
+/// `capacity` points, or null if `capacity` is 0. They don't need to be initialized.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn vector_text_render(
+    font_index: usize,
+    text: *const c_char,
+    points: *mut VectorTextPoint,
+    capacity: usize,
+) -> isize {
+    let Some(font) = font(font_index) else {
+        return VECTOR_TEXT_INVALID_FONT;
+    };
+
+    if text.is_null() {
+        return VECTOR_TEXT_INVALID_TEXT;
+    }
+
+    // SAFETY: the caller guarantees `text` is null-terminated
+    let Ok(text) = unsafe { CStr::from_ptr(text) }.to_str() else {
+        return VECTOR_TEXT_INVALID_TEXT;
+    };
+
+    let rendered = render_text(text, font);
+
+    for (i, point) in rendered.iter().take(capacity).enumerate() {
+        let point = VectorTextPoint {
+            x: point.x,
+            y: point.y,
+            pen: point.pen,
+        };
+        // SAFETY: the caller guarantees `points` holds `capacity` points, and
+        // `i < capacity`. The points may be uninitialized, so they are only written to.
+        unsafe { points.add(i).write(point) };
+    }
+
+    rendered.len() as isize
+}
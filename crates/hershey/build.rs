@@ -1,47 +1,101 @@
-use std::{collections::HashMap, fs, path::PathBuf};
+use std::{
+    collections::{HashMap, HashSet},
+    fs,
+    path::PathBuf,
+};
+
+use vector_text_build::{
+    StrokeData, check_font, glyph_to_rust, load_link_section, load_subset, metrics_to_rust,
+    place_statics,
+};
+use vector_text_core::{FontMetrics, PackedPoint, Strokes};
 
-#[derive(Debug, Copy, Clone)]
-struct PackedPoint {
-    pub x: i8,
-    pub y: i8,
-    pub pen: bool,
-}
 const NUM_GLYPHS: usize = 4000;
 type FontFile = [Option<Glyph>; NUM_GLYPHS];
 
+/// Mapping files which refer to glyphs in the oriental (Japanese) glyph set,
+/// rather than the occidental one.
+const ORIENTAL_MAPPINGS: &[&str] = &["japan"];
+
 /// Generate the symbol definition Rust code that will be included in the crate.
-fn generate_rust(font: &[Option<Glyph>], mappings: &HashMap<String, FontMapping>) -> String {
+///
+/// With a `subset`, only the glyphs used to draw its characters are included. The
+/// metrics are still measured from every glyph.
+fn generate_rust(
+    font: &[Option<Glyph>],
+    oriental: &[(u16, Glyph)],
+    mappings: &HashMap<String, FontMapping>,
+    subset: Option<&HashSet<char>>,
+    strokes: &mut StrokeData,
+) -> String {
     let mut out = String::new();
 
+    let keep = |c: char| subset.is_none_or(|subset| subset.contains(&c));
+
+    // The IDs of the glyphs used to draw the characters being kept
+    let mut used = HashSet::new();
+    let mut used_oriental = HashSet::new();
+
+    for (name, data) in mappings {
+        let stem = name.split(".").next().unwrap();
+        let used = match ORIENTAL_MAPPINGS.contains(&stem) {
+            true => &mut used_oriental,
+            false => &mut used,
+        };
+
+        let codepoints = data
+            .iter()
+            .enumerate()
+            .map(|(c, &id)| (char::from(c as u8), id));
+
+        for (c, id) in codepoints.chain(unicode_mapping(stem)) {
+            if keep(c) {
+                used.insert(id);
+            }
+        }
+    }
+
+    let keep_id = |id: u16, used: &HashSet<u16>| subset.is_none() || used.contains(&id);
+
     // Write the symbol table
     out.push_str(&format!(
         "static HERSHEY_FONT: [Option<Glyph>; {}] = [\n",
         NUM_GLYPHS
     ));
 
-    for glyph in font {
+    for (id, glyph) in font.iter().enumerate() {
         match glyph {
-            None => out.push_str("    None,\n"),
-            Some(g) => {
-                out.push_str("    Some(Glyph {\n");
-                out.push_str(&format!("        left: {},\n", g.left));
-                out.push_str(&format!("        right: {},\n", g.right));
-                out.push_str("        strokes: &[\n");
-
-                for p in &g.strokes {
-                    out.push_str(&format!(
-                        "            PackedPoint {{ x: {}, y: {}, pen: {} }},\n",
-                        p.x, p.y, p.pen
-                    ));
-                }
-
-                out.push_str("        ],\n    }),\n");
-            }
+            Some(g) if keep_id(id as u16, &used) => out.push_str(&format!(
+                "    Some({}),\n",
+                glyph_to_rust(&g.as_glyph(), strokes)
+            )),
+            _ => out.push_str("    None,\n"),
         }
     }
 
     out.push_str("];\n");
 
+    // The oriental glyph IDs are sparse, so store them sorted by ID instead
+    let oriental_kept: Vec<_> = oriental
+        .iter()
+        .filter(|&&(id, _)| keep_id(id, &used_oriental))
+        .collect();
+
+    out.push_str(&format!(
+        "static JAPANESE_FONT: [(u16, Glyph); {}] = [\n",
+        oriental_kept.len()
+    ));
+
+    for (id, g) in oriental_kept {
+        out.push_str(&format!(
+            "    ({}, {}),\n",
+            id,
+            glyph_to_rust(&g.as_glyph(), strokes)
+        ));
+    }
+
+    out.push_str("];\n");
+
     // Write the font lookup tables
     for (name, data) in mappings {
         let parts: Vec<_> = name.split(".").collect();
@@ -52,6 +106,7 @@ fn generate_rust(font: &[Option<Glyph>], mappings: &HashMap<String, FontMapping>
         ));
 
         for (i, v) in data.iter().enumerate() {
+            let v = if keep(char::from(i as u8)) { *v } else { 0 };
             out.push_str(&format!("{},\t", v));
             if i % 16 == 15 {
                 out.push_str("\n    ");
@@ -59,46 +114,80 @@ fn generate_rust(font: &[Option<Glyph>], mappings: &HashMap<String, FontMapping>
         }
 
         out.push_str("\n];\n\n");
+
+        // Characters beyond the first 256, e.g. Greek letters in the Greek fonts
+        let unicode: Vec<_> = unicode_mapping(parts[0])
+            .into_iter()
+            .filter(|&(c, _)| keep(c))
+            .collect();
+
+        out.push_str(&format!(
+            "static {}_UNICODE: [(char, u16); {}] = [\n",
+            parts[0].to_uppercase(),
+            unicode.len()
+        ));
+
+        for (c, id) in unicode {
+            out.push_str(&format!("    ('\\u{{{:04X}}}', {}),\n", c as u32, id));
+        }
+
+        out.push_str("];\n\n");
     }
 
     // Write an enum
 
     out.push_str("/// A specific Hershey font mapping file which defines a font in terms of symbol ranges (`.hmp` file).\n");
+    out.push_str("#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]\n");
+    out.push_str("#[non_exhaustive]\n");
     out.push_str("pub enum HersheyFont {\n");
 
     for name in mappings.keys() {
         let parts: Vec<_> = name.split(".").collect();
-
-        let title: String = parts[0]
-            .chars()
-            .enumerate()
-            .map(|(i, c)| match i {
-                0 => c.to_ascii_uppercase(),
-                _ => c.to_ascii_lowercase(),
-            })
-            .collect();
+        let title = variant_name(parts[0]);
 
         out.push_str(&format!("    {},\n", title));
     }
 
     out.push_str("}\n");
 
+    let mut stems: Vec<_> = mappings
+        .keys()
+        .map(|name| name.split(".").next().unwrap())
+        .collect();
+    stems.sort();
+
     // Generate implementation mapping to values
     out.push_str("impl HersheyFont {\n");
-    out.push_str("    fn table(self) -> &'static [u16; 256] {\n");
+    out.push_str("    /// Every Hershey font mapping, in alphabetical order.\n");
+    out.push_str(&format!("    pub const ALL: [Self; {}] = [\n", stems.len()));
+
+    for stem in &stems {
+        out.push_str(&format!("        Self::{},\n", variant_name(stem)));
+    }
+
+    out.push_str("    ];\n\n");
+
+    out.push_str("    /// The name of this font mapping, which is the name of its `.hmp` file.\n");
+    out.push_str("    pub fn name(&self) -> &'static str {\n");
+    out.push_str("        match self {\n");
+
+    for stem in &stems {
+        out.push_str(&format!(
+            "            Self::{} => \"{}\",\n",
+            variant_name(stem),
+            stem.to_ascii_lowercase()
+        ));
+    }
+
+    out.push_str("        }\n");
+    out.push_str("    }\n\n");
+
+    out.push_str("    fn table(&self) -> &'static [u16; 256] {\n");
     out.push_str("        match self {\n");
 
     for name in mappings.keys() {
         let parts: Vec<_> = name.split(".").collect();
-
-        let title: String = parts[0]
-            .chars()
-            .enumerate()
-            .map(|(i, c)| match i {
-                0 => c.to_ascii_uppercase(),
-                _ => c.to_ascii_lowercase(),
-            })
-            .collect();
+        let title = variant_name(parts[0]);
         out.push_str(&format!(
             "            Self::{} => &{}_FONT,\n",
             title,
@@ -106,6 +195,70 @@ fn generate_rust(font: &[Option<Glyph>], mappings: &HashMap<String, FontMapping>
         ));
     }
 
+    out.push_str("        }\n");
+    out.push_str("    }\n\n");
+
+    out.push_str("    fn is_oriental(&self) -> bool {\n");
+    out.push_str("        match self {\n");
+
+    for name in mappings.keys() {
+        let parts: Vec<_> = name.split(".").collect();
+        let title = variant_name(parts[0]);
+        out.push_str(&format!(
+            "            Self::{} => {},\n",
+            title,
+            ORIENTAL_MAPPINGS.contains(&parts[0])
+        ));
+    }
+
+    out.push_str("        }\n");
+    out.push_str("    }\n\n");
+
+    out.push_str("    fn unicode(&self) -> &'static [(char, u16)] {\n");
+    out.push_str("        match self {\n");
+
+    for name in mappings.keys() {
+        let parts: Vec<_> = name.split(".").collect();
+        let title = variant_name(parts[0]);
+        out.push_str(&format!(
+            "            Self::{} => &{}_UNICODE,\n",
+            title,
+            parts[0].to_ascii_uppercase()
+        ));
+    }
+
+    out.push_str("        }\n");
+    out.push_str("    }\n\n");
+
+    out.push_str("    fn metrics(&self) -> FontMetrics {\n");
+    out.push_str("        match self {\n");
+
+    for (name, data) in mappings {
+        let parts: Vec<_> = name.split(".").collect();
+        let title = variant_name(parts[0]);
+
+        let oriental_mapping = ORIENTAL_MAPPINGS.contains(&parts[0]);
+        let lookup = |c: char| {
+            let id = *data.get(c as usize)?;
+            if oriental_mapping {
+                let i = oriental.binary_search_by_key(&id, |(id, _)| *id).ok()?;
+                Some(oriental[i].1.as_glyph())
+            } else {
+                Some(font.get(id as usize)?.as_ref()?.as_glyph())
+            }
+        };
+        let metrics = FontMetrics::measure(lookup);
+
+        // Glyphs are looked up by their position in the mapping
+        let codes = (0..=u8::MAX).map(char::from);
+        check_font(&format!("hershey-{}", parts[0]), codes, lookup, &metrics);
+        out.push_str(&format!(
+            "            Self::{} => {},\n",
+            title,
+            metrics_to_rust(&metrics)
+        ));
+    }
+
     out.push_str("        }\n");
     out.push_str("    }\n");
     out.push_str("}\n");
@@ -113,6 +266,108 @@ fn generate_rust(font: &[Option<Glyph>], mappings: &HashMap<String, FontMapping>
     out
 }
 
+/// Get the name of the enum variant for a mapping file, e.g. `Romans` for `romans.hmp`.
+fn variant_name(stem: &str) -> String {
+    stem.chars()
+        .enumerate()
+        .map(|(i, c)| match i {
+            0 => c.to_ascii_uppercase(),
+            _ => c.to_ascii_lowercase(),
+        })
+        .collect()
+}
+
+/// A run of consecutive Unicode characters drawn by a font in addition to its `.hmp`
+/// mapping: the first character, its Hershey glyph ID, and the length of the run.
+type UnicodeRange = (char, u16, u16);
+
+/// The extra Unicode characters drawn by each font.
+const UNICODE_RANGES: &[(&str, &[UnicodeRange])] = &[
+    ("cyrilc", CYRILLIC_COMPLEX),
+    ("japan", JAPANESE_PUNCTUATION),
+    ("greekc", GREEK_COMPLEX),
+    ("greekcs", GREEK_COMPLEX_SMALL),
+    ("greekp", GREEK_PLAIN),
+    ("greeks", GREEK_SIMPLEX),
+];
+
+/// The Hershey Cyrillic letters are in the same order as the basic Russian alphabet
+/// in Unicode (without `Ё`).
+const CYRILLIC_COMPLEX: &[UnicodeRange] = &[('\u{0410}', 2801, 32), ('\u{0430}', 2901, 32)];
+
+/// Greek letters in Hershey order (alpha to omega) line up with Unicode, except that
+/// Unicode has a gap for the final sigma in the capitals, and puts it before sigma
+/// in the lowercase letters.
+const fn greek(capitals: u16, lowercase: u16) -> [UnicodeRange; 5] {
+    [
+        ('\u{0391}', capitals, 17),
+        ('\u{03A3}', capitals + 17, 7),
+        ('\u{03B1}', lowercase, 17),
+        ('\u{03C2}', lowercase + 17, 1),
+        ('\u{03C3}', lowercase + 17, 7),
+    ]
+}
+
+const GREEK_COMPLEX: &[UnicodeRange] = &greek(2027, 2127);
+const GREEK_COMPLEX_SMALL: &[UnicodeRange] = &greek(1027, 1127);
+// The plain font only has capitals
+const GREEK_PLAIN: &[UnicodeRange] = &greek(27, 27);
+const GREEK_SIMPLEX: &[UnicodeRange] = &greek(527, 627);
+
+/// The ideographic full stop and comma, and the long vowel mark.
+const JAPANESE_PUNCTUATION: &[UnicodeRange] = &[
+    ('\u{3002}', 6200, 1),
+    ('\u{3001}', 6201, 1),
+    ('\u{30FC}', 6202, 1),
+];
+
+/// Hiragana in the order of the oriental glyph set: the 51 sounds of the gojūon table
+/// from glyph 6000, then the 25 voiced sounds from glyph 6055. The katakana follow the
+/// same order from glyphs 6100 and 6155.
+///
+/// Slots in the table which repeat another kana (like the second `い`) are left as `_`.
+const KANA: [(&str, u16); 2] = [
+    (
+        "あいうえおかきくけこさしすせそたちつてとなにぬねのはひふへほまみむめもや_ゆ_よらりるれろわゐ_ゑをん",
+        6000,
+    ),
+    ("がぎぐげござじずぜぞだぢづでどばびぶべぼぱぴぷぺぽ", 6055),
+];
+
+/// Offset from each hiragana to the matching katakana, in Unicode and in glyph IDs.
+const KATAKANA_OFFSET: (u32, u16) = (0x60, 100);
+
+/// Build the mapping from Unicode characters to glyph IDs for the characters a font
+/// draws beyond its `.hmp` mapping, sorted by character.
+fn unicode_mapping(stem: &str) -> Vec<(char, u16)> {
+    let ranges = UNICODE_RANGES
+        .iter()
+        .find(|(name, _)| *name == stem)
+        .map_or(&[][..], |&(_, ranges)| ranges);
+
+    let mut mapping: Vec<(char, u16)> = ranges
+        .iter()
+        .flat_map(|&(first, id, len)| {
+            (0..len).map(move |i| (char::from_u32(first as u32 + i as u32).unwrap(), id + i))
+        })
+        .collect();
+
+    if ORIENTAL_MAPPINGS.contains(&stem) {
+        for (kana, first) in KANA {
+            for (i, c) in kana.chars().enumerate().filter(|&(_, c)| c != '_') {
+                let id = first + i as u16;
+                let katakana = char::from_u32(c as u32 + KATAKANA_OFFSET.0).unwrap();
+
+                mapping.push((c, id));
+                mapping.push((katakana, id + KATAKANA_OFFSET.1));
+            }
+        }
+    }
+
+    mapping.sort();
+    mapping
+}
+
 #[derive(Debug, Clone)]
 struct Glyph {
     pub left: i8,
@@ -121,6 +376,16 @@ struct Glyph {
 }
 
 impl Glyph {
+    /// Borrow this glyph's data as a [vector_text_core::Glyph].
+    fn as_glyph(&self) -> vector_text_core::Glyph<'_> {
+        vector_text_core::Glyph {
+            left: self.left,
+            right: self.right,
+            strokes: Strokes::Points(&self.strokes),
+            anchors: &[],
+        }
+    }
+
     /// Parse a single line of the Hershey format into a glyph.
     fn from_line(line: &str) -> Result<(u16, Self), ()> {
         let mut chars = line.chars();
@@ -169,9 +434,23 @@ impl Glyph {
     }
 }
 
-/// Load a file of glyph definitions in Hershey format.
+/// Load a file of glyph definitions in Hershey format into a table indexed by glyph ID.
 fn load_file(file: &str) -> FontFile {
     let mut result = [const { None }; NUM_GLYPHS];
+
+    for (id, glyph) in read_glyphs(file) {
+        let id = id as usize;
+        if id < NUM_GLYPHS {
+            result[id] = Some(glyph);
+        }
+    }
+
+    result
+}
+
+/// Read every glyph definition in a file in Hershey format, sorted by glyph ID.
+fn read_glyphs(file: &str) -> Vec<(u16, Glyph)> {
+    let mut result = Vec::new();
     let mut lines = file.lines();
 
     loop {
@@ -191,14 +470,14 @@ fn load_file(file: &str) -> FontFile {
 
                 let glyph = Glyph::from_line(&full);
 
-                if let Ok((id, glyph)) = glyph {
-                    let id = id as usize;
-                    if id < NUM_GLYPHS {
-                        result[id] = Some(glyph);
-                    }
+                if let Ok(glyph) = glyph {
+                    result.push(glyph);
                 }
             }
-            None => break result,
+            None => {
+                result.sort_by_key(|&(id, _)| id);
+                break result;
+            }
         }
     }
 }
@@ -243,6 +522,9 @@ fn main() {
 
     let glyphs = load_file(&hershey);
 
+    let japanese = fs::read_to_string("data/japanese.jhf").unwrap();
+    let oriental = read_glyphs(&japanese);
+
     let mut mappings: HashMap<String, FontMapping> = HashMap::new();
 
     for file in fs::read_dir("data/mappings").unwrap() {
@@ -255,7 +537,16 @@ fn main() {
     let out_dir = PathBuf::from(std::env::var("OUT_DIR").unwrap());
     let out_file = out_dir.join("hershey_font.rs");
 
-    fs::write(out_file, generate_rust(&glyphs, &mappings)).unwrap();
+    let subset = load_subset();
+    let mut strokes = StrokeData::new();
+
+    let mut code = generate_rust(&glyphs, &oriental, &mappings, subset.as_ref(), &mut strokes);
+    code.push_str(&strokes.to_rust("hershey_strokes.bin"));
+
+    let code = place_statics(&code, load_link_section().as_deref());
+    fs::write(out_file, code).unwrap();
+    strokes.write(&out_dir, "hershey_strokes.bin");
 
     println!("cargo:rerun-if-changed=data/hershey.jhf");
+    println!("cargo:rerun-if-changed=data/japanese.jhf");
 }
@@ -0,0 +1,89 @@
+use alloc::boxed::Box;
+
+use vector_text_core::{Font, FontMetrics, Glyph};
+
+use crate::lookup_glyph;
+
+/// A Hershey font assembled from a mapping given at runtime, instead of one of the
+/// `.hmp` files built into [crate::HersheyFont].
+///
+/// This allows glyphs from different Hershey fonts to be mixed, for example to draw
+/// the simplex digits alongside script letters:
+///
+/// ```
+/// use vector_text_core::Font;
+/// use vector_text_hershey::CustomHersheyFont;
+///
+/// let font = CustomHersheyFont::from_fn(|c| match c {
+///     ' ' => Some(2199),
+///     '0'..='9' => Some(700 + (c as u16 - '0' as u16)),
+///     'A'..='Z' => Some(551 + (c as u16 - 'A' as u16)),
+///     _ => None,
+/// });
+///
+/// let points = font.render_text("ROOM 101");
+/// assert!(font.supports_char('7'));
+/// assert!(!font.supports_char('a'));
+/// ```
+pub struct CustomHersheyFont {
+    mapping: Mapping,
+    oriental: bool,
+}
+
+/// How characters are mapped to Hershey glyph IDs.
+enum Mapping {
+    /// A table of the glyph IDs for the first 256 codepoints, like a `.hmp` file
+    Table(Box<[u16; 256]>),
+    /// A function giving the glyph ID for any character
+    Function(Box<dyn Fn(char) -> Option<u16>>),
+}
+
+impl CustomHersheyFont {
+    /// Create a font from a table of the Hershey glyph IDs used to draw the first
+    /// 256 codepoints, in the same form as a `.hmp` file. An ID of 0 means the
+    /// character has no glyph.
+    pub fn from_table(table: [u16; 256]) -> Self {
+        Self::new(Mapping::Table(Box::new(table)))
+    }
+
+    /// Create a font from a function giving the Hershey glyph ID used to draw
+    /// each character, or `None` if it has no glyph.
+    pub fn from_fn(mapping: impl Fn(char) -> Option<u16> + 'static) -> Self {
+        Self::new(Mapping::Function(Box::new(mapping)))
+    }
+
+    fn new(mapping: Mapping) -> Self {
+        Self {
+            mapping,
+            oriental: false,
+        }
+    }
+
+    /// Look the glyph IDs up in the oriental glyph set (as used by
+    /// [crate::HersheyFont::Japan]) instead of the main one.
+    pub fn with_oriental_glyphs(mut self) -> Self {
+        self.oriental = true;
+        self
+    }
+
+    /// The Hershey glyph ID used to draw a character, if it has one.
+    pub fn hershey_id(&self, character: char) -> Option<u16> {
+        let id = match &self.mapping {
+            Mapping::Table(table) => *table.get(character as usize)?,
+            Mapping::Function(mapping) => mapping(character)?,
+        };
+
+        Some(id).filter(|&id| id != 0)
+    }
+}
+
+impl Font for CustomHersheyFont {
+    fn glyph(&self, character: char) -> Option<Glyph<'_>> {
+        lookup_glyph(self.hershey_id(character)?, self.oriental)
+    }
+
+    /// Measured from the glyphs the mapping uses, since they may come from different fonts.
+    fn metrics(&self) -> FontMetrics {
+        FontMetrics::measure(|c| self.glyph(c))
+    }
+}
@@ -4,45 +4,204 @@
 //! renders Hershey fonts.
 //!
 //! It includes Hershey font data sourced from [Paul Bourke's compilation](https://paulbourke.net/dataformats/hershey/).
+//!
+//! To save space, set the `VECTOR_TEXT_SUBSET` environment variable when building to
+//! compile in only the glyphs for some characters: either `ascii`, `latin1`, or the
+//! absolute path of a file containing the characters. Glyphs which no font mapping uses
+//! for those characters, including the [HersheySymbol]s, are left out.
+//!
+//! The `compress` feature stores the glyphs' points delta-encoded, in about half the
+//! space, at the cost of decoding them while rendering.
+//!
+//! To place the font tables in a particular linker section (such as a region of external
+//! flash), set `VECTOR_TEXT_LINK_SECTION` to its name when building. The tables are
+//! still read like any other memory, so on AVR, where program memory can only be read
+//! with special instructions, use the `progmem` feature instead, which keeps the glyphs'
+//! points there and reads them with those instructions.
 
 extern crate alloc;
 
-use alloc::vec::Vec;
-use vector_text_core::{Glyph, PackedPoint, Point, Renderer};
+mod custom;
+mod symbol;
+
+use alloc::{string::String, vec::Vec};
+
+pub use custom::CustomHersheyFont;
+pub use symbol::{HersheySymbol, SymbolSet, render_symbols};
+use vector_text_core::{
+    Font, FontMetrics, Glyph, ParseFontError, Point, RenderError, RenderOptions, Renderer,
+    render_glyphs,
+};
 
 include!(concat!(env!("OUT_DIR"), "/hershey_font.rs"));
 
 /// A [Renderer] which draws text using Hershey fonts.
+///
+/// Each font maps the first 256 codepoints according to its `.hmp` file. The Greek
+/// fonts (e.g. [HersheyFont::Greeks]) also draw the Unicode Greek letters, and
+/// [HersheyFont::Cyrilc] draws the Russian alphabet from the Unicode Cyrillic block.
+///
+/// [HersheyFont::Japan] uses the oriental glyph set, and draws hiragana and katakana.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Hash)]
 pub struct HersheyRenderer;
 
 impl Renderer<HersheyFont> for HersheyRenderer {
-    fn render_text(text: &str, font: HersheyFont) -> Vec<Point> {
-        let mut result = Vec::new();
-        let mut x_idx = 0;
-
-        let mapping = font.table();
+    fn glyph(font: &HersheyFont, character: char) -> Result<Glyph<'_>, RenderError> {
+        let hershey_id = match u8::try_from(character) {
+            Ok(c) => font.table()[c as usize],
+            Err(_) => {
+                let unicode = font.unicode();
 
-        for character in text.chars() {
-            if character > 255 as char {
-                continue;
+                match unicode.binary_search_by_key(&character, |&(c, _)| c) {
+                    Ok(i) => unicode[i].1,
+                    Err(_) => return Err(RenderError::CodepointOutOfRange(character)),
+                }
             }
+        };
 
-            let hershey_id = mapping[character as usize] as usize;
+        lookup_glyph(hershey_id, font.is_oriental())
+            .ok_or(RenderError::UnsupportedCharacter(character))
+    }
 
-            if hershey_id == 0 || hershey_id >= HERSHEY_FONT.len() {
-                continue;
-            }
+    fn metrics(font: &HersheyFont) -> FontMetrics {
+        font.metrics()
+    }
 
-            if let Some(glyph) = HERSHEY_FONT[hershey_id] {
-                result.extend(glyph.strokes.iter().map(|point| Point {
-                    x: point.x as i16 - glyph.left as i16 + x_idx,
-                    y: point.y as i16,
-                    pen: point.pen,
-                }));
-                x_idx += glyph.right as i16 - glyph.left as i16;
-            }
-        }
+    fn candidates(font: &HersheyFont) -> impl Iterator<Item = char> {
+        (0..=255u8)
+            .map(char::from)
+            .chain(font.unicode().iter().map(|&(c, _)| c))
+    }
+}
+
+/// Look up a glyph by its Hershey ID, in either the oriental or the main glyph set.
+///
+/// The ID 0 is used for characters without a glyph.
+fn lookup_glyph(hershey_id: u16, oriental: bool) -> Option<Glyph<'static>> {
+    if hershey_id == 0 {
+        None
+    } else if oriental {
+        JAPANESE_FONT
+            .binary_search_by_key(&hershey_id, |&(id, _)| id)
+            .ok()
+            .map(|i| JAPANESE_FONT[i].1)
+    } else {
+        HERSHEY_FONT.get(hershey_id as usize).copied().flatten()
+    }
+}
+
+/// Codepoint which stands for the glyph with ID 0 when laying out glyphs by ID, in
+/// Supplementary Private Use Area-A, which has room for every ID
+const GLYPH_ID_BASE: u32 = 0xF0000;
+
+/// Render a sequence of glyphs from the main Hershey glyph set, given by their IDs, to a
+/// series of points.
+///
+/// This reaches the symbols which no font mapping draws, such as the zodiac signs or
+/// the meteorological symbols. IDs without a glyph are skipped.
+///
+/// ```
+/// use vector_text_hershey::render_hershey_ids;
+///
+/// // The signs for Aries, Taurus, and Gemini
+/// let points = render_hershey_ids(&[2301, 2302, 2303]);
+/// assert!(!points.is_empty());
+///
+/// assert!(render_hershey_ids(&[]).is_empty());
+/// ```
+pub fn render_hershey_ids(hershey_ids: &[u16]) -> Vec<Point> {
+    // Stand a private use character in for each ID, which the lookup turns back into it
+    let text: String = hershey_ids
+        .iter()
+        .filter_map(|&id| char::from_u32(GLYPH_ID_BASE + id as u32))
+        .collect();
+    let mut result = Vec::new();
+
+    // On overflow, keep whatever fit before it
+    let _ = render_glyphs(
+        &text,
+        |c| {
+            (c as u32)
+                .checked_sub(GLYPH_ID_BASE)
+                .and_then(|id| HersheyFont::glyph_by_id(u16::try_from(id).ok()?))
+                .ok_or(RenderError::UnsupportedCharacter(c))
+        },
+        &HersheyFont::Romans.metrics(),
+        &RenderOptions::default(),
+        &mut result,
+    );
+
+    result
+}
+
+impl Font for HersheyFont {
+    fn glyph(&self, character: char) -> Option<Glyph<'_>> {
+        HersheyRenderer::glyph(self, character).ok()
+    }
+
+    fn metrics(&self) -> FontMetrics {
+        HersheyRenderer::metrics(self)
+    }
+}
+
+impl HersheyFont {
+    /// Iterate over every font mapping, in alphabetical order.
+    pub fn iter() -> impl Iterator<Item = Self> {
+        Self::ALL.into_iter()
+    }
+
+    /// Look up a glyph in the main Hershey glyph set by its ID.
+    pub fn glyph_by_id(hershey_id: u16) -> Option<Glyph<'static>> {
+        lookup_glyph(hershey_id, false)
+    }
+
+    /// Look up a glyph in the oriental Hershey glyph set (used by [HersheyFont::Japan])
+    /// by its ID.
+    pub fn oriental_glyph_by_id(hershey_id: u16) -> Option<Glyph<'static>> {
+        lookup_glyph(hershey_id, true)
+    }
+
+    /// The main Hershey glyph set, indexed by glyph ID.
+    pub fn glyphs() -> &'static [Option<Glyph<'static>>] {
+        &HERSHEY_FONT
+    }
+
+    /// The oriental Hershey glyph set, as pairs of glyph ID and glyph sorted by ID.
+    pub fn oriental_glyphs() -> &'static [(u16, Glyph<'static>)] {
+        &JAPANESE_FONT
+    }
+
+    /// The IDs of the glyphs this font mapping uses to draw the first 256 codepoints,
+    /// from its `.hmp` file. Characters without a glyph have the ID 0.
+    pub fn mapping(&self) -> &'static [u16; 256] {
+        self.table()
+    }
+
+    /// The IDs of the glyphs this font mapping uses to draw characters beyond the first
+    /// 256 codepoints, as pairs of character and glyph ID sorted by character.
+    pub fn unicode_mapping(&self) -> &'static [(char, u16)] {
+        self.unicode()
+    }
+
+    /// Whether this font mapping's glyph IDs refer to the oriental glyph set.
+    pub fn uses_oriental_glyphs(&self) -> bool {
+        self.is_oriental()
+    }
+}
+
+impl core::fmt::Display for HersheyFont {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl core::str::FromStr for HersheyFont {
+    type Err = ParseFontError;
 
-        result
+    /// Parse the name of a font mapping (as given by [HersheyFont::name]), ignoring case.
+    fn from_str(name: &str) -> Result<Self, ParseFontError> {
+        Self::iter()
+            .find(|font| font.name().eq_ignore_ascii_case(name))
+            .ok_or(ParseFontError)
     }
 }
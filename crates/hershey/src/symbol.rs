@@ -0,0 +1,215 @@
+use alloc::vec::Vec;
+
+use vector_text_core::{Glyph, Point};
+
+use crate::{lookup_glyph, render_hershey_ids};
+
+/// A group of related [HersheySymbol]s.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum SymbolSet {
+    /// Mathematical operators, relations, and large delimiters
+    Math,
+    /// Musical notation
+    Music,
+    /// The Sun, Moon, planets, and signs of the zodiac
+    Astronomy,
+}
+
+/// A symbol from the Hershey glyph set which the font mappings don't otherwise reach,
+/// such as `∫` or `♃`.
+///
+/// ```
+/// use vector_text_hershey::{HersheySymbol, render_symbols};
+///
+/// assert_eq!(HersheySymbol::Integral.hershey_id(), 2268);
+/// assert_eq!(HersheySymbol::Integral.character(), '∫');
+///
+/// let points = render_symbols(&[HersheySymbol::Sun, HersheySymbol::Aries]);
+/// assert!(!points.is_empty());
+///
+/// assert!(render_symbols(&[]).is_empty());
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum HersheySymbol {
+    // Math
+    PlusMinus,
+    MinusPlus,
+    Times,
+    Divide,
+    NotEqual,
+    Identical,
+    LessOrEqual,
+    GreaterOrEqual,
+    Proportional,
+    SquareRoot,
+    Subset,
+    Union,
+    Superset,
+    Intersection,
+    ElementOf,
+    Exists,
+    RightArrow,
+    UpArrow,
+    LeftArrow,
+    DownArrow,
+    PartialDerivative,
+    Nabla,
+    Integral,
+    ContourIntegral,
+    Infinity,
+    Product,
+    Summation,
+    // Music
+    Sharp,
+    Natural,
+    Flat,
+    TrebleClef,
+    BassClef,
+    AltoClef,
+    WholeNote,
+    QuarterRest,
+    EighthRest,
+    // Astronomy
+    Sun,
+    Mercury,
+    Venus,
+    Earth,
+    Mars,
+    Jupiter,
+    Saturn,
+    Uranus,
+    Neptune,
+    Pluto,
+    Moon,
+    Comet,
+    AscendingNode,
+    DescendingNode,
+    Aries,
+    Taurus,
+    Gemini,
+    Cancer,
+    Leo,
+    Virgo,
+    Libra,
+    Scorpio,
+    Sagittarius,
+    Capricorn,
+    Aquarius,
+    Pisces,
+}
+
+/// The Hershey glyph ID and Unicode character of each symbol, in the order of the enum
+#[rustfmt::skip]
+static SYMBOLS: [(HersheySymbol, u16, char); 62] = [
+    (HersheySymbol::PlusMinus, 2233, '±'),
+    (HersheySymbol::MinusPlus, 2234, '∓'),
+    (HersheySymbol::Times, 2235, '×'),
+    (HersheySymbol::Divide, 2237, '÷'),
+    (HersheySymbol::NotEqual, 2239, '≠'),
+    (HersheySymbol::Identical, 2240, '≡'),
+    (HersheySymbol::LessOrEqual, 2243, '≤'),
+    (HersheySymbol::GreaterOrEqual, 2244, '≥'),
+    (HersheySymbol::Proportional, 2245, '∝'),
+    (HersheySymbol::SquareRoot, 2255, '√'),
+    (HersheySymbol::Subset, 2256, '⊂'),
+    (HersheySymbol::Union, 2257, '∪'),
+    (HersheySymbol::Superset, 2258, '⊃'),
+    (HersheySymbol::Intersection, 2259, '∩'),
+    (HersheySymbol::ElementOf, 2260, '∈'),
+    (HersheySymbol::Exists, 2279, '∃'),
+    (HersheySymbol::RightArrow, 2261, '→'),
+    (HersheySymbol::UpArrow, 2262, '↑'),
+    (HersheySymbol::LeftArrow, 2263, '←'),
+    (HersheySymbol::DownArrow, 2264, '↓'),
+    (HersheySymbol::PartialDerivative, 2265, '∂'),
+    (HersheySymbol::Nabla, 2266, '∇'),
+    (HersheySymbol::Integral, 2268, '∫'),
+    (HersheySymbol::ContourIntegral, 2269, '∮'),
+    (HersheySymbol::Infinity, 2270, '∞'),
+    (HersheySymbol::Product, 2401, '∏'),
+    (HersheySymbol::Summation, 2402, '∑'),
+    (HersheySymbol::Sharp, 2323, '♯'),
+    (HersheySymbol::Natural, 2324, '♮'),
+    (HersheySymbol::Flat, 2325, '♭'),
+    (HersheySymbol::TrebleClef, 2330, '𝄞'),
+    (HersheySymbol::BassClef, 2381, '𝄢'),
+    (HersheySymbol::AltoClef, 2382, '𝄡'),
+    (HersheySymbol::WholeNote, 2370, '𝅝'),
+    (HersheySymbol::QuarterRest, 2378, '𝄽'),
+    (HersheySymbol::EighthRest, 2379, '𝄾'),
+    (HersheySymbol::Sun, 2281, '☉'),
+    (HersheySymbol::Mercury, 2282, '☿'),
+    (HersheySymbol::Venus, 2283, '♀'),
+    (HersheySymbol::Earth, 2284, '♁'),
+    (HersheySymbol::Mars, 2285, '♂'),
+    (HersheySymbol::Jupiter, 2286, '♃'),
+    (HersheySymbol::Saturn, 2287, '♄'),
+    (HersheySymbol::Uranus, 2288, '♅'),
+    (HersheySymbol::Neptune, 2289, '♆'),
+    (HersheySymbol::Pluto, 2290, '♇'),
+    (HersheySymbol::Moon, 2291, '☾'),
+    (HersheySymbol::Comet, 2292, '☄'),
+    (HersheySymbol::AscendingNode, 2294, '☊'),
+    (HersheySymbol::DescendingNode, 2295, '☋'),
+    (HersheySymbol::Aries, 2301, '♈'),
+    (HersheySymbol::Taurus, 2302, '♉'),
+    (HersheySymbol::Gemini, 2303, '♊'),
+    (HersheySymbol::Cancer, 2304, '♋'),
+    (HersheySymbol::Leo, 2305, '♌'),
+    (HersheySymbol::Virgo, 2306, '♍'),
+    (HersheySymbol::Libra, 2307, '♎'),
+    (HersheySymbol::Scorpio, 2308, '♏'),
+    (HersheySymbol::Sagittarius, 2309, '♐'),
+    (HersheySymbol::Capricorn, 2310, '♑'),
+    (HersheySymbol::Aquarius, 2311, '♒'),
+    (HersheySymbol::Pisces, 2312, '♓'),
+];
+
+impl HersheySymbol {
+    /// Iterate over every symbol, grouped by [SymbolSet].
+    pub fn iter() -> impl Iterator<Item = Self> {
+        SYMBOLS.iter().map(|&(symbol, _, _)| symbol)
+    }
+
+    /// Find the symbol drawn for a Unicode character, if there is one.
+    pub fn from_char(character: char) -> Option<Self> {
+        SYMBOLS
+            .iter()
+            .find(|&&(_, _, c)| c == character)
+            .map(|&(symbol, _, _)| symbol)
+    }
+
+    /// The ID of this symbol's glyph in the main Hershey glyph set.
+    pub fn hershey_id(&self) -> u16 {
+        SYMBOLS[*self as usize].1
+    }
+
+    /// The Unicode character this symbol represents.
+    pub fn character(&self) -> char {
+        SYMBOLS[*self as usize].2
+    }
+
+    /// The group of symbols this one belongs to.
+    pub fn set(&self) -> SymbolSet {
+        if *self <= Self::Summation {
+            SymbolSet::Math
+        } else if *self <= Self::EighthRest {
+            SymbolSet::Music
+        } else {
+            SymbolSet::Astronomy
+        }
+    }
+
+    /// The glyph used to draw this symbol, or `None` if it was left out of the build
+    /// by `VECTOR_TEXT_SUBSET`.
+    pub fn glyph(&self) -> Option<Glyph<'static>> {
+        lookup_glyph(self.hershey_id(), false)
+    }
+}
+
+/// Render a sequence of Hershey symbols to a series of points.
+pub fn render_symbols(symbols: &[HersheySymbol]) -> Vec<Point> {
+    let hershey_ids: Vec<u16> = symbols.iter().map(HersheySymbol::hershey_id).collect();
+
+    render_hershey_ids(&hershey_ids)
+}
@@ -0,0 +1,582 @@
+//! Parsing KiCad symbol libraries, and composing the symbols in them into glyphs.
+//!
+//! This is shared by the build script, which uses it to generate the built-in NewStroke
+//! font, and by [crate::KicadFont], which uses it to load fonts at runtime.
+
+use alloc::collections::BTreeMap;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+
+use vector_text_core::{Anchor, AnchorKind, FontMetrics, LoadedGlyph, PackedPoint, ParseError};
+
+#[derive(Debug, Clone)]
+pub(crate) struct Symbol {
+    /// Vector strokes: strokes → points
+    pub strokes: Vec<Vec<(i8, i8)>>,
+
+    /// Left side bearing
+    pub left: i8,
+
+    /// Right side bearing
+    pub right: i8,
+
+    /// Anchor points (e.g. ABOVE, BELOW, MIDBOTTOM, etc)
+    pub anchors: BTreeMap<String, (i8, i8)>,
+}
+
+impl Symbol {
+    pub(crate) fn new() -> Self {
+        Self {
+            strokes: Vec::new(),
+            left: 0,
+            right: 0,
+            anchors: BTreeMap::new(),
+        }
+    }
+
+    /// Add a pin to this symbol, which is either an anchor or marks the side bearings.
+    pub(crate) fn add_pin(&mut self, pin: &str, x: i8, y: i8) {
+        self.anchors.insert(pin.to_string(), (x, y));
+
+        // detect if this pin represents the left or right boundary
+        match pin {
+            "~" => {
+                // ~ (unnamed) indicates either left or right based on sign
+                if x > 0 {
+                    self.right = x;
+                } else {
+                    self.left = x;
+                }
+            }
+            "P" => {
+                // P always means left
+                self.left = x;
+            }
+            "S" => {
+                // S always means right
+                self.right = x;
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Symbols by name.
+pub(crate) type Symbols = BTreeMap<String, Symbol>;
+
+/// Glyphs by the character they draw.
+pub(crate) type Glyphs = BTreeMap<char, LoadedGlyph>;
+
+/// Size of one glyph unit, in mils.
+const SCALE: i32 = 50;
+
+/// Convert a coordinate in glyph units to an `i8`, as needed by line `lineno`.
+fn to_i8(value: i32, lineno: usize) -> Result<i8, ParseError> {
+    i8::try_from(value).map_err(|_| ParseError::new(lineno, "coordinate out of range"))
+}
+
+/// Convert an x coordinate in mils to glyph units.
+pub(crate) fn conv_x(x: i32, lineno: usize) -> Result<i8, ParseError> {
+    to_i8(x / SCALE, lineno)
+}
+
+/// Convert a y coordinate in mils (increasing upward) to glyph units (increasing downward).
+pub(crate) fn conv_y(y: i32, lineno: usize) -> Result<i8, ParseError> {
+    to_i8(-(y / SCALE), lineno)
+}
+
+/// Parse a coordinate from a legacy .lib file.
+fn coordinate(parts: &[&str], index: usize, lineno: usize) -> Result<i32, ParseError> {
+    parts
+        .get(index)
+        .ok_or_else(|| ParseError::new(lineno, "missing coordinate"))?
+        .parse()
+        .map_err(|_| ParseError::new(lineno, "invalid coordinate"))
+}
+
+/// Load a legacy (.lib) KiCad symbol library defining NewStroke font symbols.
+pub(crate) fn parse_lib_file(input: &str, symbols: &mut Symbols) -> Result<(), ParseError> {
+    let mut current: Option<(String, Symbol)> = None;
+
+    for (lineno, line) in input.lines().enumerate() {
+        let parts: Vec<&str> = line.split_whitespace().collect();
+        if parts.is_empty() {
+            continue;
+        }
+
+        match parts[0] {
+            "DEF" => {
+                if current.is_some() {
+                    return Err(ParseError::new(lineno, "nested DEF"));
+                }
+
+                let name = parts
+                    .get(1)
+                    .ok_or_else(|| ParseError::new(lineno, "malformed DEF"))?
+                    .to_string();
+
+                current = Some((name, Symbol::new()));
+            }
+
+            "P" => {
+                let (_, symbol) = current
+                    .as_mut()
+                    .ok_or_else(|| ParseError::new(lineno, "P outside DEF"))?;
+
+                let n: usize = parts
+                    .get(1)
+                    .ok_or_else(|| ParseError::new(lineno, "malformed P"))?
+                    .parse()
+                    .map_err(|_| ParseError::new(lineno, "invalid P count"))?;
+
+                // The count can't be trusted to reserve space with, so it is capped
+                let mut stroke = Vec::with_capacity(n.min(parts.len()));
+
+                for i in 0..n {
+                    let x = coordinate(&parts, 5 + i * 2, lineno)?;
+                    let y = coordinate(&parts, 6 + i * 2, lineno)?;
+
+                    stroke.push((conv_x(x, lineno)?, conv_y(y, lineno)?));
+                }
+
+                symbol.strokes.push(stroke);
+            }
+
+            "X" => {
+                let (_, symbol) = current
+                    .as_mut()
+                    .ok_or_else(|| ParseError::new(lineno, "X outside DEF"))?;
+
+                let pin = parts
+                    .get(1)
+                    .ok_or_else(|| ParseError::new(lineno, "malformed X"))?;
+
+                let x = coordinate(&parts, 3, lineno)?;
+                let y = coordinate(&parts, 4, lineno)?;
+
+                symbol.add_pin(pin, conv_x(x, lineno)?, conv_y(y, lineno)?);
+            }
+
+            "ENDDEF" => {
+                let (name, symbol) = current
+                    .take()
+                    .ok_or_else(|| ParseError::new(lineno, "ENDDEF without DEF"))?;
+
+                symbols.insert(name, symbol);
+            }
+
+            _ => {}
+        }
+    }
+
+    if current.is_some() {
+        return Err(ParseError::new(
+            input.lines().count(),
+            "unterminated DEF block",
+        ));
+    }
+
+    Ok(())
+}
+
+/// A transformation that may be applied to a symbol when being composed into a glyph.
+struct Transform {
+    /// X-direction scale
+    scale_x: i8,
+    /// Y-direction scale
+    scale_y: i8,
+    /// Y-direction offset
+    offset_y: i8,
+}
+
+const BASE: i8 = 9;
+const CAP_HEIGHT: i8 = -21;
+const X_HEIGHT: i8 = -14;
+const SYM_HEIGHT: i8 = -16;
+const SUP_OFFSET: i8 = -13;
+const SUB_OFFSET: i8 = 6;
+
+/// Split the transform prefix from a symbol name.
+/// For instance, "!PARENTHESIS" becomes "!" and "PARENTHESIS".
+fn split_transform(name: &str) -> (Transform, &str) {
+    let first = name.chars().next();
+
+    if let Some(c) = first {
+        let transform = match c {
+            '!' => Some(Transform {
+                scale_x: -1,
+                scale_y: 1,
+                offset_y: 0,
+            }),
+            '-' => Some(Transform {
+                scale_x: 1,
+                scale_y: -1,
+                offset_y: X_HEIGHT,
+            }),
+            '=' => Some(Transform {
+                scale_x: 1,
+                scale_y: -1,
+                offset_y: CAP_HEIGHT,
+            }),
+            '~' => Some(Transform {
+                scale_x: 1,
+                scale_y: -1,
+                offset_y: SYM_HEIGHT,
+            }),
+            '+' => Some(Transform {
+                scale_x: -1,
+                scale_y: -1,
+                offset_y: X_HEIGHT,
+            }),
+            '%' => Some(Transform {
+                scale_x: -1,
+                scale_y: -1,
+                offset_y: CAP_HEIGHT,
+            }),
+            '*' => Some(Transform {
+                scale_x: -1,
+                scale_y: -1,
+                offset_y: SYM_HEIGHT,
+            }),
+            '^' => Some(Transform {
+                scale_x: 1,
+                scale_y: 1,
+                offset_y: SUP_OFFSET,
+            }),
+            '`' => Some(Transform {
+                scale_x: -1,
+                scale_y: 1,
+                offset_y: SUP_OFFSET,
+            }),
+            '.' => Some(Transform {
+                scale_x: 1,
+                scale_y: 1,
+                offset_y: SUB_OFFSET,
+            }),
+            ',' => Some(Transform {
+                scale_x: -1,
+                scale_y: 1,
+                offset_y: SUB_OFFSET,
+            }),
+            _ => None,
+        };
+
+        if let Some(t) = transform {
+            // first char is a transform symbol → strip it
+            return (t, &name[c.len_utf8()..]);
+        }
+    }
+
+    // first char is not a transform → return identity transform, full string
+    (
+        Transform {
+            scale_x: 1,
+            scale_y: 1,
+            offset_y: 0,
+        },
+        name,
+    )
+}
+
+/// Apply a transform to a single point of a symbol, giving glyph coordinates.
+fn transform_point(
+    (x, y): (i8, i8),
+    tr: &Transform,
+    lineno: usize,
+) -> Result<(i8, i8), ParseError> {
+    let x = x as i32 * tr.scale_x as i32;
+    let y = y as i32 * tr.scale_y as i32 + tr.offset_y as i32 + BASE as i32;
+    Ok((to_i8(x, lineno)?, to_i8(y, lineno)?))
+}
+
+/// Render the given symbol, with provided transform and offset applied
+fn render_glyph(
+    raw: &Symbol,
+    tr: &Transform,
+    (offset_x, offset_y): (i32, i32),
+    lineno: usize,
+) -> Result<Vec<PackedPoint>, ParseError> {
+    let mut out = Vec::new();
+
+    for stroke in &raw.strokes {
+        let mut first_point = true;
+
+        for &(x, y) in stroke {
+            let px = x as i32 * tr.scale_x as i32 + offset_x;
+            let py = y as i32 * tr.scale_y as i32 + offset_y + BASE as i32;
+
+            out.push(PackedPoint {
+                x: to_i8(px, lineno)?,
+                y: to_i8(py, lineno)?,
+                pen: !first_point,
+            });
+
+            first_point = false;
+        }
+    }
+
+    Ok(out)
+}
+
+/// Transform the left and right metrics of the given symbol.
+fn transform_metrics(raw: &Symbol, tr: &Transform) -> (i32, i32) {
+    let (l, r) = (raw.left as i32, raw.right as i32);
+
+    if tr.scale_x >= 0 { (l, r) } else { (-r, -l) }
+}
+
+/// Look up a symbol by name, as needed by line `lineno` of the character list.
+fn symbol<'a>(raw: &'a Symbols, name: &str, lineno: usize) -> Result<&'a Symbol, ParseError> {
+    raw.get(name)
+        .ok_or_else(|| ParseError::new(lineno, format!("no symbol named {}", name)))
+}
+
+/// Build a glyph from a single symbol name.
+fn build_single(raw: &Symbols, name: &str, lineno: usize) -> Result<LoadedGlyph, ParseError> {
+    let (tr, base_name) = split_transform(name);
+    let base = symbol(raw, base_name, lineno)?;
+
+    let strokes = render_glyph(base, &tr, (0, 0), lineno)?;
+    let (left, right) = transform_metrics(base, &tr);
+
+    // Mirrored or shifted symbols may have their anchors in the wrong place
+    let mut anchors = Vec::new();
+    if base_name == name {
+        for (pin, kind) in [("ABOVE", AnchorKind::Above), ("BELOW", AnchorKind::Below)] {
+            if let Some(&pin) = base.anchors.get(pin) {
+                let (x, y) = transform_point(pin, &tr, lineno)?;
+                anchors.push(Anchor { kind, x, y });
+            }
+        }
+    }
+
+    Ok(LoadedGlyph {
+        left: to_i8(left, lineno)?,
+        right: to_i8(right, lineno)?,
+        strokes,
+        anchors,
+    })
+}
+
+/// Compute the offset used when placing an accent character on a base character.
+fn anchor_offset(
+    base: &Symbol,
+    accent: &Symbol,
+    anchor: Option<&str>,
+    base_tr: &Transform,
+    accent_tr: &Transform,
+) -> Option<(i32, i32)> {
+    // No anchor → zero offset
+    let anchor = match anchor {
+        Some(a) => a,
+        None => return Some((0, 0)),
+    };
+
+    // Split at '=' if present: left = base key, right = accent key (modifier)
+    let mut parts = anchor.splitn(2, '=');
+    let base_key = parts.next()?; // e.g., "ABOVE"
+    let accent_key = parts.next().unwrap_or(base_key); // e.g., "X" or fallback to base_key
+
+    // Lookup coordinates in glyph anchors
+    let (bx, by) = base.anchors.get(base_key).copied()?;
+    let (ax, ay) = accent.anchors.get(accent_key).copied().unwrap_or((0, 0));
+
+    // Apply transforms
+    let scaled = |value: i8, scale: i8| value as i32 * scale as i32;
+    let ox = scaled(bx, base_tr.scale_x) - scaled(ax, accent_tr.scale_x);
+    let oy = scaled(by, base_tr.scale_y) + base_tr.offset_y as i32
+        - scaled(ay, accent_tr.scale_y)
+        - accent_tr.offset_y as i32;
+
+    Some((ox, oy))
+}
+
+/// Create a glyph by composing two characters.
+fn compose_two(raw: &Symbols, a: &str, b: &str, lineno: usize) -> Result<LoadedGlyph, ParseError> {
+    let (ta, a_name) = split_transform(a);
+
+    // Split B into accent and optional anchor
+    let mut b_parts = b.splitn(2, ' ');
+    let acc_glyph_name = b_parts.next().unwrap_or_default();
+    let anchor = b_parts.next(); // e.g. Some("ABOVE=X")
+
+    let (tb, b_name) = split_transform(acc_glyph_name);
+
+    let base = symbol(raw, a_name, lineno)?;
+    let acc = symbol(raw, b_name, lineno)?;
+
+    // Pass anchor to anchor_offset
+    let (ox, oy) = anchor_offset(base, acc, anchor, &ta, &tb)
+        .ok_or_else(|| ParseError::new(lineno, format!("{} has no anchor for {}", a_name, b)))?;
+
+    let mut strokes = render_glyph(base, &ta, (0, 0), lineno)?;
+    strokes.extend(render_glyph(acc, &tb, (ox, oy), lineno)?);
+
+    let (l1, r1) = transform_metrics(base, &ta);
+    let (l2, r2) = transform_metrics(acc, &tb);
+
+    Ok(LoadedGlyph {
+        left: to_i8(l1.min(l2 + ox), lineno)?,
+        right: to_i8(r1.max(r2 + ox), lineno)?,
+        strokes,
+        anchors: Vec::new(),
+    })
+}
+
+/// Combining marks which can be attached to a preceding base glyph at render time:
+/// codepoint, symbol name, and the kind of anchor given by the symbol's `X` pin.
+///
+/// Marks without an anchor kind are centred on their base instead.
+const COMBINING_MARKS: &[(char, &str, Option<AnchorKind>)] = &[
+    ('\u{0300}', "GRAVE", Some(AnchorKind::MarkAbove)),
+    ('\u{0301}', "ACUTE", Some(AnchorKind::MarkAbove)),
+    ('\u{0302}', "CIRCUMFLEX", Some(AnchorKind::MarkAbove)),
+    ('\u{0303}', "TILDE", Some(AnchorKind::MarkAbove)),
+    ('\u{0304}', "MACRON", Some(AnchorKind::MarkAbove)),
+    ('\u{0306}', "BREVE", Some(AnchorKind::MarkAbove)),
+    ('\u{0307}', "DOT", Some(AnchorKind::MarkAbove)),
+    ('\u{0308}', "DIAERESIS", Some(AnchorKind::MarkAbove)),
+    ('\u{030A}', "RING", Some(AnchorKind::MarkAbove)),
+    ('\u{030B}', "ACUTE_DBL", Some(AnchorKind::MarkAbove)),
+    ('\u{030C}', "CARON", Some(AnchorKind::MarkAbove)),
+    ('\u{030F}', "GRAVE_DBL", Some(AnchorKind::MarkAbove)),
+    ('\u{0311}', "BREVE_INV", Some(AnchorKind::MarkAbove)),
+    ('\u{0323}', "+DOT", Some(AnchorKind::MarkBelow)),
+    ('\u{0324}', "+DIAERESIS", Some(AnchorKind::MarkBelow)),
+    ('\u{0325}', "+RING", Some(AnchorKind::MarkBelow)),
+    ('\u{0327}', "CEDILLA", None),
+    ('\u{0328}', "OGONEK", None),
+];
+
+/// Build the glyphs for the combining marks in [COMBINING_MARKS],
+/// skipping any whose symbols are missing from the library.
+pub(crate) fn build_marks(raw: &Symbols, font: &mut Glyphs) {
+    for &(codepoint, name, kind) in COMBINING_MARKS {
+        let (tr, base_name) = split_transform(name);
+
+        let (Some(symbol), Ok(mut glyph)) = (raw.get(base_name), build_single(raw, name, 0)) else {
+            continue;
+        };
+
+        // A mark's only anchor is the point where it attaches to its base
+        glyph.anchors = match (kind, symbol.anchors.get("X")) {
+            (Some(kind), Some(&pin)) => match transform_point(pin, &tr, 0) {
+                Ok((x, y)) => vec![Anchor { kind, x, y }],
+                Err(_) => continue,
+            },
+            _ => Vec::new(),
+        };
+
+        font.insert(codepoint, glyph);
+    }
+}
+
+/// Parse a character list (such as "charlist.txt"), containing the mapping from
+/// Unicode codepoint to KiCad symbol, and build the glyph for each character.
+pub(crate) fn parse_charlist(input: &str, font: &Symbols) -> Result<Glyphs, ParseError> {
+    let mut out = Glyphs::new();
+
+    let mut codepoint: u32 = 0;
+    let overflow = |lineno| ParseError::new(lineno, "codepoint out of range");
+
+    for (lineno, line) in input.lines().enumerate() {
+        let line = line.trim();
+
+        if line.is_empty() || line.starts_with('#') || line.starts_with("//") {
+            continue;
+        }
+
+        // remove trailing comment if present
+        let line = line.split('#').next().unwrap_or_default().trim();
+
+        let parts: Vec<&str> = line.split_whitespace().collect();
+        if parts.is_empty() {
+            continue;
+        }
+
+        match parts[0] {
+            "startchar" => {
+                codepoint = parts
+                    .get(1)
+                    .ok_or_else(|| ParseError::new(lineno, "missing startchar value"))?
+                    .parse()
+                    .map_err(|_| ParseError::new(lineno, "invalid startchar value"))?;
+            }
+
+            "font" => {
+                // ignore, only one font output
+            }
+
+            "+" => {
+                let rest = line[1..].trim_start();
+
+                // Split on any amount of whitespace
+                let parts: Vec<&str> = rest.split_whitespace().collect();
+
+                let first_glyph = parts
+                    .first()
+                    .ok_or_else(|| ParseError::new(lineno, "missing base glyph"))?;
+                let second_glyph = if parts.len() > 1 {
+                    Some(parts[1..].join(" ")) // join everything after the first
+                } else {
+                    None
+                };
+
+                let glyph = match second_glyph {
+                    Some(second) => compose_two(font, first_glyph, &second, lineno)?,
+                    None => build_single(font, first_glyph, lineno)?,
+                };
+
+                let character = char::from_u32(codepoint).ok_or_else(|| overflow(lineno))?;
+
+                out.insert(character, glyph);
+                codepoint = codepoint.checked_add(1).ok_or_else(|| overflow(lineno))?;
+            }
+
+            "+w" | "+p" => {
+                // unsupported form, leave this codepoint empty
+                codepoint = codepoint.checked_add(1).ok_or_else(|| overflow(lineno))?;
+            }
+
+            "+(" => {
+                // opening group (?)
+                codepoint = codepoint.checked_add(1).ok_or_else(|| overflow(lineno))?;
+            }
+
+            "+|" | "+)" => {
+                // continuing/closing group (?)
+            }
+
+            "//" => {
+                // ignore, this is a comment
+            }
+
+            "skipcodes" => {
+                let skipped = parts
+                    .get(1)
+                    .ok_or_else(|| ParseError::new(lineno, "missing skipcodes value"))?
+                    .parse::<u32>()
+                    .map_err(|_| ParseError::new(lineno, "invalid skipcodes value"))?;
+                codepoint = codepoint
+                    .checked_add(skipped)
+                    .ok_or_else(|| overflow(lineno))?;
+            }
+
+            _ => {
+                return Err(ParseError::new(
+                    lineno,
+                    format!("unsupported command: {}", line),
+                ));
+            }
+        }
+    }
+
+    Ok(out)
+}
+
+/// Measure the overall metrics of a font from the shapes of a few reference glyphs.
+pub(crate) fn measure<'a>(lookup: impl Fn(char) -> Option<&'a LoadedGlyph>) -> FontMetrics {
+    FontMetrics::measure(|c| lookup(c).map(LoadedGlyph::as_glyph))
+}
@@ -0,0 +1,397 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use vector_text_core::{Font, FontMetrics, Glyph, LoadedGlyph, PackedPoint, ParseError};
+
+use crate::compose::{self, Glyphs, Symbol, Symbols};
+
+/// A stroke font in KiCad's format, loaded at runtime.
+///
+/// This allows updated or customized builds of NewStroke, or KiCad's own font files,
+/// to be drawn without regenerating this crate. The font can be loaded from the symbol
+/// libraries and character list it is built from (the same files this crate's built-in
+/// font is generated from), or from KiCad's compiled `newstroke_font.cpp`:
+///
+/// ```
+/// use vector_text_core::Font;
+/// use vector_text_newstroke::KicadFont;
+///
+/// let source = r#"
+///     const char* const newstroke_font[] = {
+///         "JZ",          /* space */
+///         "JZRFRT RRVRX", /* ! */
+///     };
+/// "#;
+///
+/// let font = KicadFont::from_cpp(source).unwrap();
+/// assert!(font.supports_char('!'));
+/// let points = font.render_text("! !");
+/// ```
+#[derive(Debug, Clone)]
+pub struct KicadFont {
+    glyphs: Glyphs,
+    metrics: FontMetrics,
+}
+
+impl KicadFont {
+    /// Load a font from its symbol libraries and character list (`charlist.txt`).
+    ///
+    /// Each library can be either in KiCad's legacy `.lib` format or its newer
+    /// `.kicad_sym` format. The symbols for the combining marks (such as `ACUTE`) are also
+    /// used to draw combining characters, if the libraries include them.
+    pub fn from_libraries(libraries: &[&str], charlist: &str) -> Result<Self, ParseError> {
+        let mut symbols = Symbols::new();
+
+        for library in libraries {
+            if library.trim_start().starts_with('(') {
+                parse_kicad_sym(library, &mut symbols)?;
+            } else {
+                compose::parse_lib_file(library, &mut symbols)?;
+            }
+        }
+
+        let mut glyphs = compose::parse_charlist(charlist, &symbols)?;
+        compose::build_marks(&symbols, &mut glyphs);
+
+        Ok(Self::new(glyphs))
+    }
+
+    /// Load a font from the C++ source defining KiCad's built-in stroke font
+    /// (`newstroke_font.cpp`).
+    ///
+    /// The source contains an array of strings, one for each character starting from
+    /// the space, in the same encoding as the Hershey fonts.
+    pub fn from_cpp(source: &str) -> Result<Self, ParseError> {
+        let mut glyphs = Glyphs::new();
+
+        for (i, (lineno, shape)) in cpp_strings(source)?.into_iter().enumerate() {
+            let character = char::from_u32(' ' as u32 + i as u32)
+                .ok_or_else(|| ParseError::new(lineno, "too many glyphs"))?;
+
+            if let Some(glyph) = decode_shape(&shape) {
+                glyphs.insert(character, glyph);
+            }
+        }
+
+        Ok(Self::new(glyphs))
+    }
+
+    fn new(glyphs: Glyphs) -> Self {
+        let metrics = compose::measure(|c| glyphs.get(&c));
+        Self { glyphs, metrics }
+    }
+
+    /// Iterate over the characters this font has glyphs for, in increasing order.
+    pub fn chars(&self) -> impl Iterator<Item = char> + '_ {
+        self.glyphs.keys().copied()
+    }
+}
+
+impl Font for KicadFont {
+    fn glyph(&self, character: char) -> Option<Glyph<'_>> {
+        self.glyphs.get(&character).map(LoadedGlyph::as_glyph)
+    }
+
+    fn metrics(&self) -> FontMetrics {
+        self.metrics
+    }
+}
+
+/// Decode a glyph from KiCad's compiled font format, where each pair of characters
+/// is a coordinate offset from `R`. The first pair gives the left and right boundaries,
+/// and a pair of ` R` lifts the pen.
+fn decode_shape(shape: &str) -> Option<LoadedGlyph> {
+    let coord = |c: u8| (c as i16 - b'R' as i16).clamp(-128, 127) as i8;
+
+    let mut pairs = shape.as_bytes().chunks_exact(2);
+    let bounds = pairs.next()?;
+
+    let mut strokes = Vec::new();
+    let mut pen = false;
+
+    for pair in pairs {
+        if pair == b" R" {
+            pen = false;
+            continue;
+        }
+
+        strokes.push(PackedPoint {
+            x: coord(pair[0]),
+            y: coord(pair[1]),
+            pen,
+        });
+        pen = true;
+    }
+
+    Some(LoadedGlyph {
+        left: coord(bounds[0]),
+        right: coord(bounds[1]),
+        strokes,
+        anchors: Vec::new(),
+    })
+}
+
+/// Find the string literals in the first array initializer of some C++ source,
+/// with the line each one starts on.
+///
+/// Adjacent literals are joined together, as the compiler would.
+fn cpp_strings(source: &str) -> Result<Vec<(usize, String)>, ParseError> {
+    let mut strings: Vec<(usize, String)> = Vec::new();
+    let mut chars = source.chars().peekable();
+    let mut lineno = 0;
+    let mut in_array = false;
+    // Was the last token a string literal, which the next one would be joined to?
+    let mut after_string = false;
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\n' => {
+                lineno += 1;
+                continue;
+            }
+            '/' if chars.next_if_eq(&'/').is_some() => {
+                while chars.next_if(|&c| c != '\n').is_some() {}
+                continue;
+            }
+            '/' if chars.next_if_eq(&'*').is_some() => {
+                let mut last = ' ';
+
+                loop {
+                    match chars.next() {
+                        Some('/') if last == '*' => break,
+                        Some(c) => {
+                            lineno += (c == '\n') as usize;
+                            last = c;
+                        }
+                        None => return Err(ParseError::new(lineno, "unterminated comment")),
+                    }
+                }
+                continue;
+            }
+            '{' if !in_array => in_array = true,
+            '}' if in_array => return Ok(strings),
+            '"' => {
+                let mut literal = String::new();
+
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some('\\') => match chars.next() {
+                            Some('n') => literal.push('\n'),
+                            Some('t') => literal.push('\t'),
+                            Some(c) => literal.push(c),
+                            None => break,
+                        },
+                        Some('\n') | None => {
+                            return Err(ParseError::new(lineno, "unterminated string"));
+                        }
+                        Some(c) => literal.push(c),
+                    }
+                }
+
+                if in_array {
+                    match strings.last_mut() {
+                        Some((_, last)) if after_string => last.push_str(&literal),
+                        _ => strings.push((lineno, literal)),
+                    }
+                    after_string = true;
+                }
+                continue;
+            }
+            c if c.is_whitespace() => continue,
+            _ => {}
+        }
+
+        after_string = false;
+    }
+
+    Err(ParseError::new(lineno, "no array of glyphs found"))
+}
+
+/// A parsed S-expression, as used in `.kicad_sym` files.
+enum Sexp {
+    Atom(String),
+    List(usize, Vec<Sexp>),
+}
+
+impl Sexp {
+    /// The items of this list, if it is a list whose first item is the given atom.
+    fn tagged(&self, tag: &str) -> Option<&[Sexp]> {
+        match self {
+            Sexp::List(_, items) => match items.first() {
+                Some(Sexp::Atom(head)) if head == tag => Some(&items[1..]),
+                _ => None,
+            },
+            Sexp::Atom(_) => None,
+        }
+    }
+
+    /// Find the first child of this list with the given tag.
+    fn child(&self, tag: &str) -> Option<&[Sexp]> {
+        match self {
+            Sexp::List(_, items) => items.iter().find_map(|item| item.tagged(tag)),
+            Sexp::Atom(_) => None,
+        }
+    }
+
+    fn atom(&self) -> Option<&str> {
+        match self {
+            Sexp::Atom(atom) => Some(atom),
+            Sexp::List(..) => None,
+        }
+    }
+
+    fn line(&self) -> usize {
+        match self {
+            Sexp::List(lineno, _) => *lineno,
+            Sexp::Atom(_) => 0,
+        }
+    }
+}
+
+/// Parse the S-expressions in some text, returning the top-level expression.
+fn parse_sexp(input: &str) -> Result<Sexp, ParseError> {
+    let mut stack: Vec<(usize, Vec<Sexp>)> = Vec::new();
+    let mut chars = input.chars().peekable();
+    let mut lineno = 0;
+
+    while let Some(c) = chars.next() {
+        let item = match c {
+            '\n' => {
+                lineno += 1;
+                continue;
+            }
+            c if c.is_whitespace() => continue,
+            '(' => {
+                stack.push((lineno, Vec::new()));
+                continue;
+            }
+            ')' => {
+                let (start, items) = stack
+                    .pop()
+                    .ok_or_else(|| ParseError::new(lineno, "unbalanced parentheses"))?;
+                Sexp::List(start, items)
+            }
+            '"' => {
+                let mut atom = String::new();
+
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some('\\') => atom.extend(chars.next()),
+                        Some(c) => {
+                            lineno += (c == '\n') as usize;
+                            atom.push(c);
+                        }
+                        None => return Err(ParseError::new(lineno, "unterminated string")),
+                    }
+                }
+
+                Sexp::Atom(atom)
+            }
+            c => {
+                let mut atom = String::from(c);
+
+                while let Some(c) = chars.next_if(|&c| !c.is_whitespace() && c != '(' && c != ')') {
+                    atom.push(c);
+                }
+
+                Sexp::Atom(atom)
+            }
+        };
+
+        match stack.last_mut() {
+            Some((_, items)) => items.push(item),
+            None => return Ok(item),
+        }
+    }
+
+    Err(ParseError::new(lineno, "unexpected end of file"))
+}
+
+/// Convert a coordinate in a `.kicad_sym` file (in millimetres) to mils,
+/// as used in the legacy `.lib` format.
+fn mm_to_mils(item: Option<&Sexp>, lineno: usize) -> Result<i32, ParseError> {
+    let mm: f32 = item
+        .and_then(Sexp::atom)
+        .and_then(|atom| atom.parse().ok())
+        .ok_or_else(|| ParseError::new(lineno, "invalid coordinate"))?;
+
+    // Round to the nearest mil, since the cast truncates towards zero
+    let mils = mm / 0.0254;
+    let rounded = if mils < 0.0 { mils - 0.5 } else { mils + 0.5 };
+    Ok(rounded as i32)
+}
+
+/// Load a `.kicad_sym` symbol library defining NewStroke font symbols.
+fn parse_kicad_sym(input: &str, symbols: &mut Symbols) -> Result<(), ParseError> {
+    let library = parse_sexp(input)?;
+
+    let Sexp::List(_, items) = &library else {
+        return Err(ParseError::new(0, "expected a symbol library"));
+    };
+
+    for item in items {
+        let Some([name, ..]) = item.tagged("symbol") else {
+            continue;
+        };
+
+        let name = name
+            .atom()
+            .ok_or_else(|| ParseError::new(item.line(), "malformed symbol name"))?;
+
+        let mut symbol = Symbol::new();
+        add_graphics(item, &mut symbol)?;
+        symbols.insert(String::from(name), symbol);
+    }
+
+    Ok(())
+}
+
+/// Add the polylines and pins in a symbol (including in its units) to `symbol`.
+fn add_graphics(item: &Sexp, symbol: &mut Symbol) -> Result<(), ParseError> {
+    let Sexp::List(_, children) = item else {
+        return Ok(());
+    };
+
+    for child in children {
+        let lineno = child.line();
+
+        if child.tagged("symbol").is_some() {
+            add_graphics(child, symbol)?;
+        } else if child.tagged("polyline").is_some() {
+            let points = child.child("pts").unwrap_or_default();
+            let mut stroke = Vec::with_capacity(points.len());
+
+            for point in points {
+                let xy = point
+                    .tagged("xy")
+                    .ok_or_else(|| ParseError::new(lineno, "malformed point"))?;
+
+                let x = mm_to_mils(xy.first(), lineno)?;
+                let y = mm_to_mils(xy.get(1), lineno)?;
+                stroke.push((compose::conv_x(x, lineno)?, compose::conv_y(y, lineno)?));
+            }
+
+            symbol.strokes.push(stroke);
+        } else if child.tagged("pin").is_some() {
+            let at = child
+                .child("at")
+                .ok_or_else(|| ParseError::new(lineno, "pin without position"))?;
+            let name = child
+                .child("name")
+                .and_then(|name| name.first()?.atom())
+                .ok_or_else(|| ParseError::new(lineno, "pin without name"))?;
+
+            let x = mm_to_mils(at.first(), lineno)?;
+            let y = mm_to_mils(at.get(1), lineno)?;
+            symbol.add_pin(
+                name,
+                compose::conv_x(x, lineno)?,
+                compose::conv_y(y, lineno)?,
+            );
+        }
+    }
+
+    Ok(())
+}
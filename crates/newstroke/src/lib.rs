@@ -4,33 +4,77 @@
 //! renders the NewStroke font (originally created for KiCAD).
 //!
 //! Data for the NewStroke font was sourced from the project page: <https://vovanium.ru/sledy/newstroke/en>
+//!
+//! Other fonts in KiCad's stroke font format can be loaded at runtime with [KicadFont].
+//!
+//! To save space, set the `VECTOR_TEXT_SUBSET` environment variable when building to
+//! compile in only the glyphs for some characters: either `ascii`, `latin1`, or the
+//! absolute path of a file containing the characters. Enabling the `compress` feature
+//! also delta-encodes the glyphs' points, which are then decoded as they are drawn.
+//! The glyph table can be placed in a particular linker section by setting
+//! `VECTOR_TEXT_LINK_SECTION` to its name, or the glyphs' points kept in AVR program
+//! memory with the `progmem` feature.
 
 extern crate alloc;
 
-use alloc::vec::Vec;
-use vector_text_core::{Glyph, PackedPoint, Point, Renderer};
+mod compose;
+mod kicad;
+
+pub use kicad::KicadFont;
+pub use vector_text_core::ParseError;
+use vector_text_core::{Anchor, AnchorKind, Font, FontMetrics, Glyph, RenderError, Renderer};
 
 include!(concat!(env!("OUT_DIR"), "/newstroke_font.rs"));
 
 /// A [Renderer] which draws text using the NewStroke font.
+///
+/// Characters beyond the end of the glyph table (e.g. emoji) are treated like any
+/// other missing glyph, according to the [vector_text_core::MissingGlyph] policy.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Hash)]
 pub struct NewstrokeRenderer;
 
 impl Renderer<()> for NewstrokeRenderer {
-    fn render_text(text: &str, _mapping: ()) -> Vec<Point> {
-        let mut result = Vec::new();
-        let mut x_idx = 0;
-
-        for character in text.chars() {
-            if let Some(glyph) = NEWSTROKE_FONT[character as usize] {
-                result.extend(glyph.strokes.iter().map(|point| Point {
-                    x: point.x as i16 - glyph.left as i16 + x_idx,
-                    y: point.y as i16,
-                    pen: point.pen,
-                }));
-                x_idx += glyph.right as i16 - glyph.left as i16;
+    fn glyph(_mapping: &(), character: char) -> Result<Glyph<'_>, RenderError> {
+        match NEWSTROKE_FONT.binary_search_by_key(&character, |&(c, _)| c) {
+            Ok(index) => Ok(NEWSTROKE_FONT[index].1),
+            Err(index) if index == NEWSTROKE_FONT.len() => {
+                Err(RenderError::CodepointOutOfRange(character))
             }
+            Err(_) => Err(RenderError::UnsupportedCharacter(character)),
         }
+    }
+
+    fn metrics(_mapping: &()) -> FontMetrics {
+        NEWSTROKE_METRICS
+    }
+
+    fn candidates(_mapping: &()) -> impl Iterator<Item = char> {
+        NEWSTROKE_FONT.iter().map(|&(c, _)| c)
+    }
+}
+
+/// The NewStroke font, for use through the [Font] trait.
+///
+/// There is only one NewStroke font, so [NewstrokeRenderer] uses `()` as its mapping.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Hash)]
+pub struct NewstrokeFont;
+
+impl NewstrokeFont {
+    /// The glyphs of the NewStroke font, as pairs of character and glyph sorted by
+    /// character.
+    ///
+    /// To look up the glyph for a character, use [Font::glyph].
+    pub fn glyphs() -> &'static [(char, Glyph<'static>)] {
+        &NEWSTROKE_FONT
+    }
+}
+
+impl Font for NewstrokeFont {
+    fn glyph(&self, character: char) -> Option<Glyph<'_>> {
+        NewstrokeRenderer::glyph(&(), character).ok()
+    }
 
-        result
+    fn metrics(&self) -> FontMetrics {
+        NewstrokeRenderer::metrics(&())
     }
 }
@@ -0,0 +1,109 @@
+extern crate alloc;
+
+use std::{collections::HashSet, env, fs, path::PathBuf};
+
+#[path = "src/parse.rs"]
+mod parse;
+
+use parse::LoadedFont;
+use vector_text_build::{
+    StrokeData, check_font, glyph_to_rust, load_link_section, load_subset, metrics_to_rust,
+    place_statics,
+};
+
+/// Environment variable naming a directory of fonts to compile in
+const FONTS_VAR: &str = "VECTOR_TEXT_SVG_FONTS";
+
+/// Generate the Rust code defining the embedded fonts.
+///
+/// With a `subset`, only the glyphs for its characters are included. The metrics are
+/// still measured from every glyph.
+fn generate_rust(
+    fonts: &[(String, LoadedFont)],
+    subset: Option<&HashSet<char>>,
+    strokes: &mut StrokeData,
+) -> String {
+    let mut out = String::new();
+
+    let keep = |c: &char| subset.is_none_or(|subset| subset.contains(c));
+
+    out.push_str(&format!(
+        "static EMBEDDED_FONTS: [EmbeddedSvgFont; {}] = [\n",
+        fonts.len()
+    ));
+
+    for (name, font) in fonts {
+        out.push_str("    EmbeddedSvgFont {\n");
+        out.push_str(&format!("        name: {:?},\n", name));
+        out.push_str(&format!("        family: {:?},\n", font.family));
+        out.push_str("        glyphs: &[\n");
+
+        for (c, g) in font.glyphs.iter().filter(|(c, _)| keep(c)) {
+            out.push_str(&format!(
+                "            ({:?}, {}),\n",
+                c,
+                glyph_to_rust(&g.as_glyph(), strokes)
+            ));
+        }
+
+        let metrics = parse::measure(&font.glyphs);
+        check_font(
+            name,
+            font.glyphs.keys().copied(),
+            |c| font.glyphs.get(&c).map(|g| g.as_glyph()),
+            &metrics,
+        );
+        out.push_str("        ],\n");
+        out.push_str(&format!(
+            "        metrics: {},\n",
+            metrics_to_rust(&metrics)
+        ));
+        out.push_str("    },\n");
+    }
+
+    out.push_str("];\n");
+
+    out
+}
+
+fn main() {
+    let mut fonts = Vec::new();
+
+    if let Some(dir) = env::var_os(FONTS_VAR).map(PathBuf::from) {
+        let mut paths: Vec<PathBuf> = fs::read_dir(&dir)
+            .unwrap_or_else(|e| panic!("{} ({}): {}", FONTS_VAR, dir.display(), e))
+            .map(|entry| entry.unwrap().path())
+            .filter(|path| {
+                path.extension()
+                    .is_some_and(|ext| ext.eq_ignore_ascii_case("svg"))
+            })
+            .collect();
+        paths.sort();
+
+        for path in paths {
+            let source = fs::read_to_string(&path).unwrap();
+            let font = parse::parse_svg_font(&source)
+                .unwrap_or_else(|e| panic!("{}: {}", path.display(), e));
+            let name = path.file_stem().unwrap().to_string_lossy().to_lowercase();
+
+            fonts.push((name, font));
+            println!("cargo:rerun-if-changed={}", path.display());
+        }
+
+        println!("cargo:rerun-if-changed={}", dir.display());
+    }
+
+    let out_dir = PathBuf::from(env::var("OUT_DIR").unwrap());
+    let out_file = out_dir.join("svg_fonts.rs");
+
+    let mut strokes = StrokeData::new();
+
+    let mut code = generate_rust(&fonts, load_subset().as_ref(), &mut strokes);
+    code.push_str(&strokes.to_rust("svg_strokes.bin"));
+
+    let code = place_statics(&code, load_link_section().as_deref());
+    fs::write(out_file, code).unwrap();
+    strokes.write(&out_dir, "svg_strokes.bin");
+
+    println!("cargo:rerun-if-env-changed={}", FONTS_VAR);
+}
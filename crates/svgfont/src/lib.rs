@@ -0,0 +1,186 @@
+#![no_std]
+
+//! `vector-text-svgfont` is a backend for the `vector-text` crate that
+//! renders single-line SVG fonts.
+//!
+//! These are SVG fonts where each glyph is an open path to be stroked, rather than an
+//! outline to be filled. They were popularized by Inkscape's Hershey Text extension,
+//! and many community-made fonts are available in this format.
+//!
+//! Fonts can be loaded at runtime with [SvgFont]. They can also be compiled into the
+//! program by setting the `VECTOR_TEXT_SVG_FONTS` environment variable to the absolute
+//! path of a directory of `.svg` font files when building. Each file is then available as
+//! an [EmbeddedSvgFont] named after the file. To save space, `VECTOR_TEXT_SUBSET` can be
+//! set to compile in only the glyphs for some characters: either `ascii`, `latin1`, or
+//! the absolute path of a file containing the characters, and the `compress` feature
+//! makes the embedded glyphs' points delta-encoded. `VECTOR_TEXT_LINK_SECTION` names a
+//! linker section to put the embedded fonts' tables in, and the `progmem` feature keeps
+//! their points in AVR program memory.
+//!
+//! This includes the EMS family of engraving fonts (EMS Allure, EMS Elfin, EMS Tech, and
+//! so on) from Evil Mad Scientist, which are distributed as single-line SVG fonts in the
+//! `svg_fonts` directory of Inkscape's Hershey Text extension. For example, building with
+//! `VECTOR_TEXT_SVG_FONTS` set to that directory makes EMS Allure available as the
+//! embedded font named `emsallure`.
+
+extern crate alloc;
+
+mod parse;
+
+use alloc::string::String;
+
+use parse::Glyphs;
+pub use vector_text_core::ParseError;
+use vector_text_core::{Font, FontMetrics, Glyph, LoadedGlyph, ParseFontError};
+
+include!(concat!(env!("OUT_DIR"), "/svg_fonts.rs"));
+
+/// A single-line SVG font, loaded at runtime.
+///
+/// Glyphs are scaled so that an em is 32 units (about the size of the Hershey fonts),
+/// with the baseline at 0. Curves are approximated with line segments, and elliptical
+/// arcs are drawn as straight lines.
+///
+/// ```
+/// use vector_text_core::Font;
+/// use vector_text_svgfont::SvgFont;
+///
+/// let source = r#"
+///     <svg xmlns="http://www.w3.org/2000/svg">
+///         <defs>
+///             <font horiz-adv-x="500">
+///                 <font-face font-family="Example" units-per-em="1000" />
+///                 <glyph unicode=" " />
+///                 <glyph unicode="V" d="M 100 700 L 250 0 L 400 700" />
+///                 <glyph unicode="O" d="M 250 0 C 50 0 50 700 250 700 C 450 700 450 0 250 0 Z" />
+///             </font>
+///         </defs>
+///     </svg>
+/// "#;
+///
+/// let font = SvgFont::parse(source).unwrap();
+/// assert_eq!(font.family(), Some("Example"));
+/// assert!(font.supports_char('V'));
+/// let points = font.render_text("V O");
+/// ```
+#[derive(Debug, Clone)]
+pub struct SvgFont {
+    family: Option<String>,
+    glyphs: Glyphs,
+    metrics: FontMetrics,
+}
+
+impl SvgFont {
+    /// Load a font from the source of an SVG file containing a `<font>` element.
+    pub fn parse(source: &str) -> Result<Self, ParseError> {
+        let font = parse::parse_svg_font(source)?;
+        let metrics = parse::measure(&font.glyphs);
+
+        Ok(Self {
+            family: font.family,
+            glyphs: font.glyphs,
+            metrics,
+        })
+    }
+
+    /// The name of the font family, if the font gives one.
+    pub fn family(&self) -> Option<&str> {
+        self.family.as_deref()
+    }
+
+    /// Iterate over the characters this font has glyphs for, in increasing order.
+    pub fn chars(&self) -> impl Iterator<Item = char> + '_ {
+        self.glyphs.keys().copied()
+    }
+}
+
+impl Font for SvgFont {
+    fn glyph(&self, character: char) -> Option<Glyph<'_>> {
+        self.glyphs.get(&character).map(LoadedGlyph::as_glyph)
+    }
+
+    fn metrics(&self) -> FontMetrics {
+        self.metrics
+    }
+}
+
+/// A single-line SVG font compiled into the program.
+///
+/// Fonts are compiled in from the directory named by the `VECTOR_TEXT_SVG_FONTS`
+/// environment variable at build time, and drawn the same way as an [SvgFont].
+#[derive(Debug, Copy, Clone)]
+pub struct EmbeddedSvgFont {
+    name: &'static str,
+    family: Option<&'static str>,
+    glyphs: &'static [(char, Glyph<'static>)],
+    metrics: FontMetrics,
+}
+
+impl EmbeddedSvgFont {
+    /// Iterate over every embedded font, in alphabetical order.
+    pub fn iter() -> impl Iterator<Item = Self> {
+        EMBEDDED_FONTS.iter().copied()
+    }
+
+    /// The name of this font, which is the name of its file (without the extension)
+    /// in lowercase.
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// The name of the font family, if the font gives one.
+    pub fn family(&self) -> Option<&'static str> {
+        self.family
+    }
+
+    /// The glyphs of this font, as pairs of character and glyph sorted by character.
+    pub fn glyphs(&self) -> &'static [(char, Glyph<'static>)] {
+        self.glyphs
+    }
+}
+
+impl Font for EmbeddedSvgFont {
+    fn glyph(&self, character: char) -> Option<Glyph<'_>> {
+        self.glyphs
+            .binary_search_by_key(&character, |&(c, _)| c)
+            .ok()
+            .map(|i| self.glyphs[i].1)
+    }
+
+    fn metrics(&self) -> FontMetrics {
+        self.metrics
+    }
+}
+
+/// Embedded fonts are told apart by their names, which are unique.
+impl PartialEq for EmbeddedSvgFont {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name
+    }
+}
+
+impl Eq for EmbeddedSvgFont {}
+
+impl core::hash::Hash for EmbeddedSvgFont {
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        self.name.hash(state);
+    }
+}
+
+impl core::fmt::Display for EmbeddedSvgFont {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(self.name)
+    }
+}
+
+impl core::str::FromStr for EmbeddedSvgFont {
+    type Err = ParseFontError;
+
+    /// Parse the name of an embedded font (as given by [EmbeddedSvgFont::name]),
+    /// ignoring case.
+    fn from_str(name: &str) -> Result<Self, ParseFontError> {
+        Self::iter()
+            .find(|font| font.name.eq_ignore_ascii_case(name))
+            .ok_or(ParseFontError)
+    }
+}
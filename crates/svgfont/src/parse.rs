@@ -0,0 +1,593 @@
+//! Parsing single-line SVG fonts into glyphs.
+//!
+//! This is shared by the build script, which uses it to compile fonts into the crate,
+//! and by [crate::SvgFont], which uses it to load fonts at runtime.
+
+use alloc::collections::BTreeMap;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use vector_text_core::{FontMetrics, LoadedGlyph, PackedPoint, ParseError};
+
+/// Size of an em after scaling, in the units of a [PackedPoint]
+///
+/// This is roughly the size of an em in the Hershey fonts.
+const EM: f32 = 32.0;
+
+/// Length (after scaling) of each line segment used to approximate a curve
+const CURVE_STEP: f32 = 2.0;
+
+/// Maximum number of line segments used to approximate a curve
+const MAX_CURVE_SEGMENTS: usize = 16;
+
+pub(crate) type Glyphs = BTreeMap<char, LoadedGlyph>;
+
+/// The contents of an SVG font file.
+#[derive(Debug, Clone)]
+pub(crate) struct LoadedFont {
+    /// Name of the font family, from its `<font-face>`
+    pub family: Option<String>,
+    pub glyphs: Glyphs,
+}
+
+/// Measure the overall metrics of a font from the shapes of a few reference glyphs.
+pub(crate) fn measure(glyphs: &Glyphs) -> FontMetrics {
+    FontMetrics::measure(|c| glyphs.get(&c).map(LoadedGlyph::as_glyph))
+}
+
+/// An XML start tag (or empty-element tag), with its attributes.
+struct Tag<'a> {
+    name: &'a str,
+    attributes: Vec<(&'a str, String)>,
+    /// Line (starting from 0) the tag starts on
+    line: usize,
+}
+
+impl Tag<'_> {
+    fn attribute(&self, name: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|(key, _)| *key == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Parse a numeric attribute, if it is present.
+    fn number(&self, name: &str) -> Result<Option<f32>, ParseError> {
+        self.attribute(name)
+            .map(|value| {
+                value
+                    .trim()
+                    .parse()
+                    .map_err(|_| ParseError::new(self.line, alloc::format!("invalid {}", name)))
+            })
+            .transpose()
+    }
+}
+
+/// Reads through an XML document.
+struct Scanner<'a> {
+    input: &'a str,
+    pos: usize,
+    line: usize,
+}
+
+impl<'a> Scanner<'a> {
+    fn rest(&self) -> &'a str {
+        &self.input[self.pos..]
+    }
+
+    fn advance(&mut self, len: usize) {
+        self.line += self.rest()[..len].matches('\n').count();
+        self.pos += len;
+    }
+
+    /// Skip past a string, if it comes next.
+    fn eat(&mut self, s: &str) -> bool {
+        let found = self.rest().starts_with(s);
+        if found {
+            self.advance(s.len());
+        }
+        found
+    }
+
+    /// Take the text up to a delimiter, skipping past the delimiter.
+    fn take_until(&mut self, delimiter: &str) -> Option<&'a str> {
+        let end = self.rest().find(delimiter)?;
+        let text = &self.rest()[..end];
+        self.advance(end + delimiter.len());
+        Some(text)
+    }
+
+    fn take_while(&mut self, predicate: impl Fn(char) -> bool) -> &'a str {
+        let end = self
+            .rest()
+            .find(|c| !predicate(c))
+            .unwrap_or(self.rest().len());
+        let text = &self.rest()[..end];
+        self.advance(end);
+        text
+    }
+
+    fn skip_whitespace(&mut self) {
+        self.take_while(char::is_whitespace);
+    }
+}
+
+/// Find the start tags in an XML document, skipping over text, comments, declarations,
+/// and end tags.
+fn tags(input: &str) -> Result<Vec<Tag<'_>>, ParseError> {
+    let mut scanner = Scanner {
+        input,
+        pos: 0,
+        line: 0,
+    };
+    let mut tags = Vec::new();
+
+    while scanner.take_until("<").is_some() {
+        let line = scanner.line;
+
+        let skipped = [
+            ("!--", "-->"),
+            ("![CDATA[", "]]>"),
+            ("?", "?>"),
+            ("!", ">"),
+            ("/", ">"),
+        ];
+
+        if let Some((_, end)) = skipped.iter().find(|(start, _)| scanner.eat(start)) {
+            scanner
+                .take_until(end)
+                .ok_or_else(|| ParseError::new(line, "unterminated markup"))?;
+            continue;
+        }
+
+        let name = scanner.take_while(|c| !c.is_whitespace() && c != '/' && c != '>');
+        let mut attributes = Vec::new();
+
+        loop {
+            scanner.skip_whitespace();
+
+            if scanner.eat("/>") || scanner.eat(">") {
+                break;
+            }
+
+            let key = scanner.take_while(|c| !c.is_whitespace() && c != '=' && c != '>');
+            scanner.skip_whitespace();
+
+            if key.is_empty() || !scanner.eat("=") {
+                return Err(ParseError::new(scanner.line, "malformed attribute"));
+            }
+
+            scanner.skip_whitespace();
+
+            let quote = if scanner.eat("\"") {
+                "\""
+            } else if scanner.eat("'") {
+                "'"
+            } else {
+                return Err(ParseError::new(scanner.line, "unquoted attribute value"));
+            };
+
+            let value = scanner
+                .take_until(quote)
+                .ok_or_else(|| ParseError::new(line, "unterminated attribute value"))?;
+
+            attributes.push((key, decode_entities(value, scanner.line)?));
+        }
+
+        tags.push(Tag {
+            name,
+            attributes,
+            line,
+        });
+    }
+
+    Ok(tags)
+}
+
+/// Replace the entity and character references in an attribute value.
+fn decode_entities(value: &str, lineno: usize) -> Result<String, ParseError> {
+    let mut out = String::new();
+    let mut rest = value;
+
+    while let Some(start) = rest.find('&') {
+        out.push_str(&rest[..start]);
+        rest = &rest[start + 1..];
+
+        let end = rest
+            .find(';')
+            .ok_or_else(|| ParseError::new(lineno, "unterminated entity"))?;
+        let entity = &rest[..end];
+
+        let character = match entity {
+            "amp" => Some('&'),
+            "lt" => Some('<'),
+            "gt" => Some('>'),
+            "quot" => Some('"'),
+            "apos" => Some('\''),
+            _ => match entity.strip_prefix('#') {
+                Some(hex) if hex.starts_with(['x', 'X']) => u32::from_str_radix(&hex[1..], 16)
+                    .ok()
+                    .and_then(char::from_u32),
+                Some(decimal) => decimal.parse().ok().and_then(char::from_u32),
+                None => None,
+            },
+        };
+
+        out.push(character.ok_or_else(|| ParseError::new(lineno, "unknown entity"))?);
+        rest = &rest[end + 1..];
+    }
+
+    out.push_str(rest);
+    Ok(out)
+}
+
+/// Round a scaled coordinate to the nearest integer, clamped to the range of a [PackedPoint].
+fn to_coord(value: f32) -> i8 {
+    // Round to the nearest integer, since the cast truncates towards zero
+    let rounded = if value < 0.0 {
+        value - 0.5
+    } else {
+        value + 0.5
+    };
+    rounded.clamp(i8::MIN as f32, i8::MAX as f32) as i8
+}
+
+/// Reads the commands and numbers in SVG path data.
+struct PathData<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl PathData<'_> {
+    fn skip_separators(&mut self) {
+        while self
+            .bytes
+            .get(self.pos)
+            .is_some_and(|b| b.is_ascii_whitespace() || *b == b',')
+        {
+            self.pos += 1;
+        }
+    }
+
+    fn at_end(&mut self) -> bool {
+        self.skip_separators();
+        self.pos == self.bytes.len()
+    }
+
+    /// Read a command letter, if one comes next.
+    fn command(&mut self) -> Option<u8> {
+        self.skip_separators();
+        let command = *self
+            .bytes
+            .get(self.pos)
+            .filter(|b| b.is_ascii_alphabetic())?;
+        self.pos += 1;
+        Some(command)
+    }
+
+    /// Check whether a number comes next.
+    fn has_number(&mut self) -> bool {
+        self.skip_separators();
+        self.bytes
+            .get(self.pos)
+            .is_some_and(|b| b.is_ascii_digit() || matches!(b, b'.' | b'-' | b'+'))
+    }
+
+    fn number(&mut self) -> Result<f32, &'static str> {
+        self.skip_separators();
+        let start = self.pos;
+
+        let digits = |data: &mut Self| {
+            while data.bytes.get(data.pos).is_some_and(u8::is_ascii_digit) {
+                data.pos += 1;
+            }
+        };
+
+        if matches!(self.bytes.get(self.pos), Some(b'-' | b'+')) {
+            self.pos += 1;
+        }
+
+        digits(self);
+
+        if self.bytes.get(self.pos) == Some(&b'.') {
+            self.pos += 1;
+            digits(self);
+        }
+
+        if matches!(self.bytes.get(self.pos), Some(b'e' | b'E')) {
+            self.pos += 1;
+
+            if matches!(self.bytes.get(self.pos), Some(b'-' | b'+')) {
+                self.pos += 1;
+            }
+
+            digits(self);
+        }
+
+        core::str::from_utf8(&self.bytes[start..self.pos])
+            .ok()
+            .and_then(|number| number.parse().ok())
+            .ok_or("invalid number in path data")
+    }
+
+    /// Read an arc flag, which is a single `0` or `1` that needn't be separated from
+    /// whatever follows it.
+    fn flag(&mut self) -> Result<bool, &'static str> {
+        self.skip_separators();
+        let flag = match self.bytes.get(self.pos) {
+            Some(b'0') => false,
+            Some(b'1') => true,
+            _ => return Err("invalid flag in path data"),
+        };
+        self.pos += 1;
+        Ok(flag)
+    }
+
+    /// Read a pair of coordinates, relative to `origin`.
+    fn point(&mut self, origin: (f32, f32)) -> Result<(f32, f32), &'static str> {
+        Ok((self.number()? + origin.0, self.number()? + origin.1))
+    }
+}
+
+/// Builds the strokes of a glyph, converting from font units into its coordinates.
+struct Outline {
+    strokes: Vec<PackedPoint>,
+    scale: f32,
+}
+
+impl Outline {
+    fn push(&mut self, (x, y): (f32, f32), pen: bool) {
+        // Font units have Y increasing upwards, from the baseline
+        let point = PackedPoint {
+            x: to_coord(x * self.scale),
+            y: to_coord(-y * self.scale),
+            pen,
+        };
+
+        match self.strokes.last_mut() {
+            // Skip segments which are too short to see
+            Some(last) if pen && last.x == point.x && last.y == point.y => {}
+            // Only the last of several moves in a row matters
+            Some(last) if !pen && !last.pen => *last = point,
+            _ => self.strokes.push(point),
+        }
+    }
+
+    /// Number of line segments to approximate a curve with, given its control points.
+    fn segments(&self, points: &[(f32, f32)]) -> usize {
+        let length: f32 = points
+            .windows(2)
+            .map(|w| {
+                let dx = (w[1].0 - w[0].0).abs();
+                let dy = (w[1].1 - w[0].1).abs();
+                dx.max(dy)
+            })
+            .sum();
+
+        ((length * self.scale / CURVE_STEP) as usize + 1).min(MAX_CURVE_SEGMENTS)
+    }
+
+    fn cubic(&mut self, p0: (f32, f32), p1: (f32, f32), p2: (f32, f32), p3: (f32, f32)) {
+        let n = self.segments(&[p0, p1, p2, p3]);
+
+        for i in 1..=n {
+            let t = i as f32 / n as f32;
+            let u = 1.0 - t;
+            let (a, b, c, d) = (u * u * u, 3.0 * u * u * t, 3.0 * u * t * t, t * t * t);
+
+            self.push(
+                (
+                    a * p0.0 + b * p1.0 + c * p2.0 + d * p3.0,
+                    a * p0.1 + b * p1.1 + c * p2.1 + d * p3.1,
+                ),
+                true,
+            );
+        }
+    }
+
+    fn quadratic(&mut self, p0: (f32, f32), p1: (f32, f32), p2: (f32, f32)) {
+        let n = self.segments(&[p0, p1, p2]);
+
+        for i in 1..=n {
+            let t = i as f32 / n as f32;
+            let u = 1.0 - t;
+            let (a, b, c) = (u * u, 2.0 * u * t, t * t);
+
+            self.push(
+                (
+                    a * p0.0 + b * p1.0 + c * p2.0,
+                    a * p0.1 + b * p1.1 + c * p2.1,
+                ),
+                true,
+            );
+        }
+    }
+
+    fn finish(mut self) -> Vec<PackedPoint> {
+        // A trailing move doesn't draw anything
+        if self.strokes.last().is_some_and(|p| !p.pen) {
+            self.strokes.pop();
+        }
+
+        self.strokes
+    }
+}
+
+/// Reflect a control point about the current point, for the smooth curve commands.
+fn reflect(control: Option<(f32, f32)>, current: (f32, f32)) -> (f32, f32) {
+    match control {
+        Some((x, y)) => (2.0 * current.0 - x, 2.0 * current.1 - y),
+        None => current,
+    }
+}
+
+/// Convert SVG path data into strokes, approximating curves with line segments.
+///
+/// Elliptical arcs are drawn as a straight line to their endpoint.
+fn parse_path(d: &str, scale: f32) -> Result<Vec<PackedPoint>, &'static str> {
+    let mut data = PathData {
+        bytes: d.as_bytes(),
+        pos: 0,
+    };
+    let mut outline = Outline {
+        strokes: Vec::new(),
+        scale,
+    };
+
+    let mut command = None;
+    let mut current = (0.0, 0.0);
+    let mut start = current;
+    // Last control point of the previous command, if it was a cubic or quadratic curve
+    let mut last_cubic = None;
+    let mut last_quadratic = None;
+
+    while !data.at_end() {
+        // A command letter may be left out when it is repeated
+        if let Some(letter) = data.command() {
+            command = Some(letter);
+        } else if !data.has_number() {
+            return Err("invalid path data");
+        }
+
+        let letter = command.ok_or("path data must start with a command")?;
+        let origin = if letter.is_ascii_lowercase() {
+            current
+        } else {
+            (0.0, 0.0)
+        };
+
+        let (mut cubic, mut quadratic) = (None, None);
+
+        match letter.to_ascii_uppercase() {
+            b'M' => {
+                current = data.point(origin)?;
+                start = current;
+                outline.push(current, false);
+
+                // Coordinates after a move are implicit lines
+                command = Some(if letter == b'm' { b'l' } else { b'L' });
+            }
+            b'L' => {
+                current = data.point(origin)?;
+                outline.push(current, true);
+            }
+            b'H' => {
+                current.0 = data.number()? + origin.0;
+                outline.push(current, true);
+            }
+            b'V' => {
+                current.1 = data.number()? + origin.1;
+                outline.push(current, true);
+            }
+            b'C' | b'S' => {
+                let p1 = if letter.eq_ignore_ascii_case(&b'C') {
+                    data.point(origin)?
+                } else {
+                    reflect(last_cubic, current)
+                };
+                let p2 = data.point(origin)?;
+                let p3 = data.point(origin)?;
+
+                outline.cubic(current, p1, p2, p3);
+                cubic = Some(p2);
+                current = p3;
+            }
+            b'Q' | b'T' => {
+                let p1 = if letter.eq_ignore_ascii_case(&b'Q') {
+                    data.point(origin)?
+                } else {
+                    reflect(last_quadratic, current)
+                };
+                let p2 = data.point(origin)?;
+
+                outline.quadratic(current, p1, p2);
+                quadratic = Some(p1);
+                current = p2;
+            }
+            b'A' => {
+                let _radii = (data.number()?, data.number()?);
+                let _rotation = data.number()?;
+                let _flags = (data.flag()?, data.flag()?);
+
+                current = data.point(origin)?;
+                outline.push(current, true);
+            }
+            b'Z' => {
+                current = start;
+                outline.push(current, true);
+
+                // Close path takes no coordinates, so it can't be repeated
+                command = None;
+            }
+            _ => return Err("unknown command in path data"),
+        }
+
+        last_cubic = cubic;
+        last_quadratic = quadratic;
+    }
+
+    Ok(outline.finish())
+}
+
+/// Parse an SVG font file, taking the glyphs from each of its `<glyph>` elements.
+///
+/// Glyphs are scaled so that an em is 32 units, with the baseline at 0. Glyphs for
+/// ligatures (more than one character) are ignored.
+pub(crate) fn parse_svg_font(input: &str) -> Result<LoadedFont, ParseError> {
+    let tags = tags(input)?;
+
+    let font = tags
+        .iter()
+        .find(|tag| tag.name == "font")
+        .ok_or_else(|| ParseError::new(0, "no <font> element found"))?;
+    let default_advance = font.number("horiz-adv-x")?.unwrap_or(0.0);
+
+    let face = tags.iter().find(|tag| tag.name == "font-face");
+    let units_per_em = match face {
+        Some(face) => face.number("units-per-em")?.unwrap_or(1000.0),
+        None => 1000.0,
+    };
+
+    if units_per_em <= 0.0 {
+        return Err(ParseError::new(
+            face.map_or(0, |f| f.line),
+            "invalid units-per-em",
+        ));
+    }
+
+    let family = face
+        .and_then(|face| face.attribute("font-family"))
+        .map(String::from);
+    let scale = EM / units_per_em;
+
+    let mut glyphs = Glyphs::new();
+
+    for tag in tags.iter().filter(|tag| tag.name == "glyph") {
+        let mut chars = tag.attribute("unicode").unwrap_or_default().chars();
+
+        let (Some(character), None) = (chars.next(), chars.next()) else {
+            continue;
+        };
+
+        let advance = tag.number("horiz-adv-x")?.unwrap_or(default_advance);
+        let strokes = match tag.attribute("d") {
+            Some(d) => {
+                parse_path(d, scale).map_err(|message| ParseError::new(tag.line, message))?
+            }
+            None => Vec::new(),
+        };
+
+        glyphs.insert(
+            character,
+            LoadedGlyph {
+                left: 0,
+                right: to_coord(advance * scale),
+                strokes,
+                anchors: Vec::new(),
+            },
+        );
+    }
+
+    Ok(LoadedFont { family, glyphs })
+}
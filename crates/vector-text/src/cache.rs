@@ -0,0 +1,124 @@
+use alloc::collections::BTreeMap;
+use alloc::vec::Vec;
+
+use vector_text_core::{LoadedGlyph, Point, RenderError, RenderOptions, render_glyphs};
+
+use crate::VectorFont;
+
+/// A cache of glyphs for drawing the same text over and over, such as the labels on a
+/// laser display which is redrawn every frame.
+///
+/// Each cache belongs to a single font and set of [RenderOptions]. The first time a
+/// character is drawn, its glyph is looked up in the font and stored with its points
+/// decoded, so later frames skip looking it up again (and decoding the font's
+/// compressed data, with the `compress` feature). The output is exactly the same as
+/// [crate::render_text_with_options] gives.
+///
+/// ```
+/// # #[cfg(feature = "hershey")] {
+/// use vector_text::{render_text, HersheyFont, RenderCache, RenderOptions, VectorFont};
+///
+/// let font = VectorFont::HersheyFont(HersheyFont::Romans);
+/// let mut cache = RenderCache::new(font, RenderOptions::default());
+///
+/// for _frame in 0..3 {
+///     let points = cache.render_text("12:34").unwrap();
+///     assert_eq!(points.len(), render_text("12:34", font).len());
+/// }
+/// # }
+/// ```
+pub struct RenderCache<'a> {
+    font: VectorFont<'a>,
+    options: RenderOptions,
+    /// Glyph for each character looked up so far, or why the font couldn't draw it
+    glyphs: BTreeMap<char, Result<LoadedGlyph, RenderError>>,
+}
+
+impl<'a> RenderCache<'a> {
+    /// Create an empty cache for drawing text in the given font with the given options.
+    pub fn new(font: VectorFont<'a>, options: RenderOptions) -> Self {
+        Self {
+            font,
+            options,
+            glyphs: BTreeMap::new(),
+        }
+    }
+
+    /// The font this cache draws text with.
+    pub fn font(&self) -> VectorFont<'a> {
+        self.font
+    }
+
+    /// The options this cache draws text with.
+    pub fn options(&self) -> &RenderOptions {
+        &self.options
+    }
+
+    /// Number of characters looked up so far, including those the font has no glyph for.
+    pub fn len(&self) -> usize {
+        self.glyphs.len()
+    }
+
+    /// Has nothing been looked up yet?
+    pub fn is_empty(&self) -> bool {
+        self.glyphs.is_empty()
+    }
+
+    /// Forget every cached glyph.
+    pub fn clear(&mut self) {
+        self.glyphs.clear();
+    }
+
+    /// Render the given text string to a list of points, using the cached glyphs where
+    /// possible.
+    pub fn render_text(&mut self, text: &str) -> Result<Vec<Point>, RenderError> {
+        let mut result = Vec::new();
+        self.render_text_into(text, &mut result)?;
+        Ok(result)
+    }
+
+    /// Render the given text string, appending the points to `out`.
+    ///
+    /// Reusing the same `out` for every frame avoids allocating a new list each time.
+    pub fn render_text_into(
+        &mut self,
+        text: &str,
+        out: &mut Vec<Point>,
+    ) -> Result<(), RenderError> {
+        let start = out.len();
+
+        loop {
+            let mut missing = Vec::new();
+
+            let result = render_glyphs(
+                text,
+                |c| match self.glyphs.get(&c) {
+                    Some(Ok(glyph)) => Ok(glyph.as_glyph()),
+                    Some(Err(e)) => Err(*e),
+                    None => {
+                        missing.push(c);
+                        Err(RenderError::UnsupportedCharacter(c))
+                    }
+                },
+                &self.font.metrics(),
+                &self.options,
+                out,
+            );
+
+            if missing.is_empty() {
+                return result;
+            }
+
+            // Look up the glyphs which weren't cached, and try again. Finding them can lead
+            // to others being looked up (such as a fallback for a missing glyph), so this
+            // repeats until every glyph drawn came from the cache.
+            out.truncate(start);
+
+            for c in missing {
+                let glyph = self.font.glyph(c).map(LoadedGlyph::from);
+
+                self.glyphs.insert(c, glyph);
+            }
+        }
+    }
+}
@@ -0,0 +1,94 @@
+use vector_text_core::RenderError;
+
+use crate::{Layout, TextLayout};
+
+/// Columns to flow the text of a [TextLayout] through, side by side, like the page of
+/// a newspaper.
+///
+/// ```
+/// # #[cfg(feature = "hershey")] {
+/// use vector_text::{Columns, HersheyFont, RenderOptions, TextLayout, VectorFont};
+///
+/// let mut layout = TextLayout::new();
+/// layout.push_text(
+///     "The quick brown fox jumps over the lazy dog",
+///     VectorFont::HersheyFont(HersheyFont::Romans),
+///     RenderOptions::default(),
+/// );
+///
+/// let columns = Columns { count: 2, width: 200, height: 80, gutter: 20 };
+/// let built = layout.build_columns(&columns).unwrap();
+///
+/// // The second column starts at the top, to the right of the first
+/// let second = built.glyphs.iter().find(|glyph| glyph.x >= 220).unwrap();
+/// assert_eq!(second.y, 0);
+/// # }
+/// ```
+#[derive(Debug, Copy, Clone)]
+pub struct Columns {
+    /// Number of columns
+    pub count: u16,
+    /// Width of each column, which lines are wrapped to
+    pub width: i16,
+    /// Height of each column
+    pub height: i16,
+    /// Distance between one column and the next
+    pub gutter: i16,
+}
+
+impl TextLayout<'_> {
+    /// Lay out the text in columns, instead of wrapping it to
+    /// [TextLayout::with_max_width].
+    ///
+    /// Lines are wrapped to the width of the columns, then moved into the first column
+    /// until it is full, then the next, and so on. A line fits in a column if it is a
+    /// whole [FontMetrics::line_height](crate::FontMetrics::line_height) from the bottom,
+    /// and each column starts with the top of its first line at 0. Lines which don't
+    /// fit in the last column are left out, so comparing the offset of the last glyph
+    /// with the length of the text shows whether everything fit.
+    pub fn build_columns(&self, columns: &Columns) -> Result<Layout, RenderError> {
+        let mut layout = self.build_with_width(Some(columns.width))?;
+
+        let Some(line_height) = self.font_at(0).map(|font| font.metrics().line_height()) else {
+            return Ok(layout);
+        };
+
+        let mut column: i32 = 0;
+        // Top of the first line in the current column
+        let mut top = None;
+
+        for i in 0..layout.glyphs.len() {
+            let y = layout.glyphs[i].y as i32;
+            let column_top = *top.get_or_insert(y);
+
+            if y - column_top + line_height as i32 > columns.height as i32 && y > column_top {
+                column += 1;
+                top = Some(y);
+            }
+
+            if column >= columns.count as i32 {
+                layout.keep(i);
+                break;
+            }
+
+            let dx = column * (columns.width as i32 + columns.gutter as i32);
+            let dy = -top.unwrap_or(y);
+            let overflow = RenderError::AdvanceOverflow {
+                offset: layout.glyphs[i].offset,
+            };
+            let moved =
+                |value: i16, by: i32| i16::try_from(value as i32 + by).map_err(|_| overflow);
+
+            let glyph = &mut layout.glyphs[i];
+            glyph.x = moved(glyph.x, dx)?;
+            glyph.y = moved(glyph.y, dy)?;
+            for point in &mut layout.points[glyph.points.clone()] {
+                point.x = moved(point.x, dx)?;
+                point.y = moved(point.y, dy)?;
+            }
+        }
+
+        self.finish(&mut layout, None)?;
+        Ok(layout)
+    }
+}
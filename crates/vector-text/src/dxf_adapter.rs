@@ -0,0 +1,78 @@
+use alloc::vec::Vec;
+
+use dxf::entities::{LwPolyline, Polyline, Vertex};
+use dxf::{Drawing, LwPolylineVertex};
+use vector_text_core::{Point, split_strokes};
+
+/// Convert rendered text to [LwPolyline]s, one for each stroke, to add to a DXF drawing.
+///
+/// DXF has y increasing upward, so render with [YAxis::Up](crate::YAxis::Up) (or
+/// [apply](crate::YAxis::apply) it afterwards) to keep the text the right way up. Glyph
+/// units become drawing units; scale the points or the entities to size them.
+///
+/// ```
+/// # #[cfg(feature = "hershey")] {
+/// use dxf::Drawing;
+/// use dxf::entities::{Entity, EntityType};
+/// use vector_text::{render_text, to_lw_polylines, HersheyFont, VectorFont, YAxis};
+///
+/// let mut points = render_text("Hi", VectorFont::HersheyFont(HersheyFont::Romans));
+/// YAxis::Up.apply(&mut points);
+///
+/// let mut drawing = Drawing::new();
+/// for polyline in to_lw_polylines(&points) {
+///     drawing.add_entity(Entity::new(EntityType::LwPolyline(polyline)));
+/// }
+/// assert_eq!(drawing.entities().count(), 5);
+/// # }
+/// ```
+pub fn to_lw_polylines(points: &[Point]) -> Vec<LwPolyline> {
+    split_strokes(points)
+        .map(|stroke| LwPolyline {
+            vertices: stroke
+                .iter()
+                .map(|point| LwPolylineVertex {
+                    x: point.x as f64,
+                    y: point.y as f64,
+                    ..Default::default()
+                })
+                .collect(),
+            ..Default::default()
+        })
+        .collect()
+}
+
+/// Convert rendered text to [Polyline]s, one for each stroke, to add to `drawing`.
+///
+/// A [Polyline]'s vertices are entities of their own, so they take their handles from
+/// the drawing. Otherwise this is like [to_lw_polylines], for programs which read only
+/// the older entity.
+///
+/// ```
+/// # #[cfg(feature = "hershey")] {
+/// use dxf::Drawing;
+/// use dxf::entities::{Entity, EntityType};
+/// use vector_text::{render_text, to_polylines, HersheyFont, VectorFont, YAxis};
+///
+/// let mut points = render_text("Hi", VectorFont::HersheyFont(HersheyFont::Romans));
+/// YAxis::Up.apply(&mut points);
+///
+/// let mut drawing = Drawing::new();
+/// for polyline in to_polylines(&mut drawing, &points) {
+///     drawing.add_entity(Entity::new(EntityType::Polyline(polyline)));
+/// }
+/// assert_eq!(drawing.entities().count(), 5);
+/// # }
+/// ```
+pub fn to_polylines(drawing: &mut Drawing, points: &[Point]) -> Vec<Polyline> {
+    split_strokes(points)
+        .map(|stroke| {
+            let mut polyline = Polyline::default();
+            for point in stroke {
+                let location = dxf::Point::new(point.x as f64, point.y as f64, 0.0);
+                polyline.add_vertex(drawing, Vertex::new(location));
+            }
+            polyline
+        })
+        .collect()
+}
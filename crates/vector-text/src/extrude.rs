@@ -0,0 +1,77 @@
+use alloc::vec::Vec;
+
+use vector_text_core::{Point, split_strokes};
+
+/// A point of a stroke in three dimensions, from [extrude].
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Hash)]
+pub struct Point3 {
+    pub x: i16,
+    pub y: i16,
+    /// Distance from the front face of the text, away from the viewer
+    pub z: i16,
+    /// Should a line be drawn (i.e., "pen down") when moving to this point?
+    pub pen: bool,
+}
+
+impl Point3 {
+    /// Place a point of rendered text at the given depth.
+    pub fn at_depth(point: Point, z: i16) -> Self {
+        Self {
+            x: point.x,
+            y: point.y,
+            z,
+            pen: point.pen,
+        }
+    }
+}
+
+/// Extrude rendered text into a wireframe of three-dimensional strokes, for vector
+/// displays which draw in perspective or for export to 3D CAD.
+///
+/// The strokes are drawn twice: once as the front face, at a `z` of 0, then again as
+/// the back face, at `depth`. Each distinct point of the strokes is then joined from the
+/// front face to the back by a line along `z`, so curves are drawn as ribbons of lines.
+///
+/// ```
+/// use vector_text::{extrude, Point, Point3};
+///
+/// let point = |x, y, pen| Point { x, y, pen };
+/// let stroke = [point(0, 0, false), point(10, 0, true), point(10, 10, true)];
+///
+/// let wireframe = extrude(&stroke, 4);
+/// assert_eq!(wireframe.len(), 3 + 3 + 3 * 2);
+/// assert_eq!(wireframe[3], Point3 { x: 0, y: 0, z: 4, pen: false });
+/// ```
+pub fn extrude(points: &[Point], depth: i16) -> Vec<Point3> {
+    let mut result = Vec::with_capacity(points.len() * 4);
+
+    for z in [0, depth] {
+        result.extend(points.iter().map(|&point| Point3::at_depth(point, z)));
+    }
+
+    // Only one line through each point, even where strokes meet or cross themselves
+    let mut corners: Vec<(i16, i16)> = split_strokes(points)
+        .flat_map(|stroke| stroke.iter().map(|p| (p.x, p.y)))
+        .collect();
+    corners.sort_unstable();
+    corners.dedup();
+
+    // Lines along z alternate in direction, to save moving back to the front each time
+    for (i, (x, y)) in corners.into_iter().enumerate() {
+        let (from, to) = if i % 2 == 0 { (0, depth) } else { (depth, 0) };
+        result.push(Point3 {
+            x,
+            y,
+            z: from,
+            pen: false,
+        });
+        result.push(Point3 {
+            x,
+            y,
+            z: to,
+            pen: true,
+        });
+    }
+
+    result
+}
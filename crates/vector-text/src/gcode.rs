@@ -0,0 +1,170 @@
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt;
+
+use vector_text_core::{Point, YAxis};
+
+/// The kind of a G-code command, as the letter it starts with.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Mnemonic {
+    /// Preparatory commands, such as moves (`G`)
+    General,
+    /// Machine commands, such as turning a tool on or off (`M`)
+    Miscellaneous,
+}
+
+impl Mnemonic {
+    /// The letter commands of this kind start with.
+    pub fn letter(&self) -> char {
+        match self {
+            Self::General => 'G',
+            Self::Miscellaneous => 'M',
+        }
+    }
+}
+
+/// An argument to a G-code command, such as `X10.5`.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Word {
+    pub letter: char,
+    pub value: f32,
+}
+
+/// A single G-code command, such as `G1 X10 Y20`.
+///
+/// These follow the model of the [gcode](https://crates.io/crates/gcode) crate, so that
+/// CAM pipelines can post-process the moves (offsetting them, adding feed rates, and so
+/// on) before writing them out. [Display](fmt::Display) writes the command as a line of
+/// G-code.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GCode {
+    pub mnemonic: Mnemonic,
+    pub number: u32,
+    pub arguments: Vec<Word>,
+}
+
+impl GCode {
+    /// Create a command with the given arguments.
+    pub fn new(mnemonic: Mnemonic, number: u32, arguments: &[(char, f32)]) -> Self {
+        Self {
+            mnemonic,
+            number,
+            arguments: arguments
+                .iter()
+                .map(|&(letter, value)| Word { letter, value })
+                .collect(),
+        }
+    }
+
+    /// The value of the argument with the given letter, if the command has one.
+    pub fn value_for(&self, letter: char) -> Option<f32> {
+        self.arguments
+            .iter()
+            .find(|word| word.letter == letter)
+            .map(|word| word.value)
+    }
+}
+
+impl fmt::Display for GCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}", self.mnemonic.letter(), self.number)?;
+        for word in &self.arguments {
+            write!(f, " {}{}", word.letter, word.value)?;
+        }
+        Ok(())
+    }
+}
+
+/// Options controlling how [to_gcode] turns rendered text into moves.
+#[derive(Debug, Copy, Clone)]
+pub struct GCodeOptions {
+    /// Millimetres per unit of the rendered points
+    pub scale: f32,
+    /// Height to raise the pen to (Z) before moving without drawing
+    pub pen_up: f32,
+    /// Height to lower the pen to (Z) before drawing
+    pub pen_down: f32,
+    /// Speed to draw lines at (F), in millimetres per minute, or `None` to leave it unset
+    pub feed_rate: Option<f32>,
+    /// Which way y increases in the points given. G-code has y increasing upward, so
+    /// points with y increasing downward are flipped.
+    pub y_axis: YAxis,
+}
+
+impl Default for GCodeOptions {
+    fn default() -> Self {
+        Self {
+            scale: 1.0,
+            pen_up: 5.0,
+            pen_down: 0.0,
+            feed_rate: None,
+            y_axis: YAxis::Down,
+        }
+    }
+}
+
+/// Turn rendered text into G-code commands for a pen plotter.
+///
+/// The commands select millimetres and absolute positioning, then move with `G0` and
+/// draw with `G1`, raising and lowering the pen along Z in between. Points with y
+/// increasing downward (see [GCodeOptions::y_axis]) are flipped, so that the text isn't
+/// drawn upside down.
+///
+/// ```
+/// # #[cfg(feature = "hershey")] {
+/// use vector_text::{render_text, to_gcode, GCodeOptions, HersheyFont, VectorFont};
+///
+/// let points = render_text("Hi", VectorFont::HersheyFont(HersheyFont::Romans));
+/// let commands = to_gcode(&points, &GCodeOptions::default());
+///
+/// for command in &commands {
+///     println!("{}", command);
+/// }
+/// assert_eq!(commands[0].to_string(), "G21");
+/// # }
+/// ```
+pub fn to_gcode(points: &[Point], options: &GCodeOptions) -> Vec<GCode> {
+    use Mnemonic::General;
+
+    let mut commands = vec![
+        GCode::new(General, 21, &[]),
+        GCode::new(General, 90, &[]),
+        GCode::new(General, 0, &[('Z', options.pen_up)]),
+    ];
+
+    let flip = match options.y_axis {
+        YAxis::Down => -1.0,
+        YAxis::Up => 1.0,
+    };
+
+    let mut drawing = false;
+    for point in points {
+        if point.pen != drawing {
+            let z = if point.pen {
+                options.pen_down
+            } else {
+                options.pen_up
+            };
+            commands.push(GCode::new(General, 0, &[('Z', z)]));
+            drawing = point.pen;
+        }
+
+        let mut arguments = vec![
+            ('X', point.x as f32 * options.scale),
+            ('Y', point.y as f32 * flip * options.scale),
+        ];
+        if point.pen
+            && let Some(feed_rate) = options.feed_rate
+        {
+            arguments.push(('F', feed_rate));
+        }
+
+        commands.push(GCode::new(General, point.pen as u32, &arguments));
+    }
+
+    if drawing {
+        commands.push(GCode::new(General, 0, &[('Z', options.pen_up)]));
+    }
+
+    commands
+}
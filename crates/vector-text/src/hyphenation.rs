@@ -0,0 +1,46 @@
+use alloc::vec::Vec;
+
+use hypher::{Lang, hyphenate};
+
+/// Find where an English word may be hyphenated, using the TeX hyphenation patterns
+/// embedded by [hypher](https://crates.io/crates/hypher).
+///
+/// This is meant for [Hyphenation::Custom](crate::Hyphenation::Custom), so that narrow
+/// columns of wrapped text break long words instead of leaving large gaps. Each
+/// language's patterns take up space, so only English is included; other languages
+/// can be used in the same way with `hypher` directly.
+///
+/// ```
+/// # #[cfg(feature = "hershey")] {
+/// use vector_text::{
+///     hyphenate_english, render_text_with_options, HersheyFont, Hyphenation, RenderOptions,
+///     VectorFont,
+/// };
+///
+/// let mut breaks = Vec::new();
+/// hyphenate_english("extensive", &mut breaks);
+/// assert_eq!(breaks, [2, 5]);
+///
+/// let options = RenderOptions {
+///     max_width: Some(150),
+///     hyphenation: Hyphenation::Custom(hyphenate_english),
+///     ..Default::default()
+/// };
+/// let result = render_text_with_options(
+///     "An extraordinarily extensive vocabulary",
+///     VectorFont::HersheyFont(HersheyFont::Romans),
+///     &options,
+/// );
+/// # }
+/// ```
+pub fn hyphenate_english(word: &str, breaks: &mut Vec<usize>) {
+    let mut at = 0;
+    let mut syllables = hyphenate(word, Lang::English).peekable();
+
+    while let Some(syllable) = syllables.next() {
+        at += syllable.len();
+        if syllables.peek().is_some() {
+            breaks.push(at);
+        }
+    }
+}
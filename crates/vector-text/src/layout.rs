@@ -0,0 +1,516 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use vector_text_core::{
+    Origin, PlacedGlyph, Point, RenderError, RenderOptions, Scale, YAxis, layout_glyphs,
+};
+
+use crate::VectorFont;
+
+/// How the lines of a [TextLayout] are lined up.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub enum Align {
+    /// Line up the left edges of the lines.
+    #[default]
+    Left,
+    /// Centre each line.
+    Center,
+    /// Line up the right edges of the lines.
+    Right,
+}
+
+/// How a [TextLayout] splits its text into paragraphs and spaces them out.
+#[derive(Debug, Copy, Clone)]
+pub struct ParagraphOptions<'a> {
+    /// Text which separates paragraphs, and isn't drawn itself
+    pub separator: &'a str,
+    /// Distance to indent the first line of each paragraph by
+    pub first_line_indent: i16,
+    /// Extra distance above each paragraph, except the first
+    pub space_before: i16,
+    /// Extra distance below each paragraph, except the last
+    pub space_after: i16,
+}
+
+impl Default for ParagraphOptions<'_> {
+    fn default() -> Self {
+        Self {
+            separator: "\n\n",
+            first_line_indent: 0,
+            space_before: 0,
+            space_after: 0,
+        }
+    }
+}
+
+/// Builds a block of text out of runs with different fonts and options, such as a
+/// document mixing several styles, then lays it all out together.
+///
+/// Lines are wrapped across the runs as if they were one piece of text, and lined up
+/// according to the [Align]ment. The baselines of all the fonts are lined up with that
+/// of the first run's font, whose metrics are used for the spacing of the lines.
+///
+/// ```
+/// # #[cfg(feature = "hershey")] {
+/// use vector_text::{Align, HersheyFont, RenderOptions, TextLayout, VectorFont};
+///
+/// let mut layout = TextLayout::new().with_max_width(200).with_align(Align::Center);
+/// layout.push_text("Dear ", VectorFont::HersheyFont(HersheyFont::Romans), RenderOptions::default());
+/// layout.push_text("Reader", VectorFont::HersheyFont(HersheyFont::Scripts), RenderOptions::default());
+///
+/// let built = layout.build().unwrap();
+/// assert_eq!(built.glyphs.len(), 11);
+/// assert_eq!(layout.span_at(built.glyphs[5].offset), 1);
+/// # }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct TextLayout<'a> {
+    text: String,
+    spans: Vec<Span<'a>>,
+    max_width: Option<i16>,
+    align: Align,
+    paragraphs: Option<ParagraphOptions<'a>>,
+}
+
+/// A run of the text of a [TextLayout], with the font and options it's drawn with.
+#[derive(Debug, Clone)]
+struct Span<'a> {
+    /// Byte offset where this span starts in the text
+    start: usize,
+    font: VectorFont<'a>,
+    options: RenderOptions,
+}
+
+/// Text laid out by a [TextLayout].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Layout {
+    /// The points of all the glyphs, after those of any shadow
+    pub points: Vec<Point>,
+    /// Where each glyph was placed, with the range of its points in `points`
+    pub glyphs: Vec<PlacedGlyph>,
+}
+
+impl<'a> TextLayout<'a> {
+    /// Create an empty layout.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Wrap lines between words so that none is wider than this.
+    pub fn with_max_width(mut self, max_width: i16) -> Self {
+        self.max_width = Some(max_width);
+        self
+    }
+
+    /// Line up the lines like this.
+    pub fn with_align(mut self, align: Align) -> Self {
+        self.align = align;
+        self
+    }
+
+    /// Split the text into paragraphs, which are laid out one below another.
+    ///
+    /// Lines within a paragraph are still broken at line breaks, so a single line break
+    /// can separate the lines of a poem while a blank line separates its verses. The
+    /// space between two paragraphs is the `space_after` of the first plus the
+    /// `space_before` of the second, on top of the usual distance between lines.
+    ///
+    /// ```
+    /// # #[cfg(feature = "hershey")] {
+    /// use vector_text::{HersheyFont, ParagraphOptions, RenderOptions, TextLayout, VectorFont};
+    ///
+    /// let mut layout = TextLayout::new().with_paragraphs(ParagraphOptions {
+    ///     first_line_indent: 20,
+    ///     space_after: 10,
+    ///     ..Default::default()
+    /// });
+    /// layout.push_text(
+    ///     "Dear Reader,\n\nThank you.",
+    ///     VectorFont::HersheyFont(HersheyFont::Romans),
+    ///     RenderOptions::default(),
+    /// );
+    ///
+    /// let built = layout.build().unwrap();
+    /// let thank = &built.glyphs[12];
+    /// assert_eq!((thank.character, thank.x), ('T', 20));
+    /// # }
+    /// ```
+    pub fn with_paragraphs(mut self, paragraphs: ParagraphOptions<'a>) -> Self {
+        self.paragraphs = Some(paragraphs);
+        self
+    }
+
+    /// Add some text to the end of the layout, drawn with the given font and options.
+    ///
+    /// The options which apply to the text as a whole are taken from the first run:
+    /// `tab_size`, `hyphenation`, `scale`, `shadow`, `origin`, `y_axis`, and `normalize`
+    /// from its options, and the wrapping width from [TextLayout::with_max_width]
+    /// (instead of `max_width`). Every other option applies to this run only. Widths and
+    /// heights given to the layout, such as the wrapping width, are in the font's own
+    /// units, before scaling.
+    pub fn push_text(&mut self, text: &str, font: VectorFont<'a>, options: RenderOptions) {
+        self.spans.push(Span {
+            start: self.text.len(),
+            font,
+            options,
+        });
+        self.text.push_str(text);
+    }
+
+    /// All of the text in the layout.
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Index of the run (in the order they were added) containing the byte at `offset` of
+    /// the text.
+    pub fn span_at(&self, offset: usize) -> usize {
+        self.spans
+            .partition_point(|span| span.start <= offset)
+            .saturating_sub(1)
+    }
+
+    /// Lay out the text.
+    ///
+    /// The text is moved to its [Origin] as if it were as wide as its widest line, or as
+    /// the wrapping width if its lines are centred or lined up on the right within it.
+    pub fn build(&self) -> Result<Layout, RenderError> {
+        let mut layout = self.build_with_width(self.max_width)?;
+        self.finish(&mut layout, self.max_width)?;
+        Ok(layout)
+    }
+
+    /// Lay out the text with its top left at the origin and y increasing downward,
+    /// wrapping it to `max_width` instead of the layout's own
+    pub(crate) fn build_with_width(&self, max_width: Option<i16>) -> Result<Layout, RenderError> {
+        let Some(first) = self.spans.first() else {
+            return Ok(Layout::default());
+        };
+
+        let options = RenderOptions {
+            max_width,
+            scale: Scale::ONE,
+            shadow: None,
+            origin: Origin::TopLeft,
+            y_axis: YAxis::Down,
+            ..first.options
+        };
+
+        let mut layout = match &self.paragraphs {
+            Some(paragraphs) => self.paragraphs(paragraphs, options)?,
+            None => self.lay_out(0, &self.text, &options)?,
+        };
+
+        layout.align(self.align, max_width);
+        Ok(layout)
+    }
+
+    /// Scale a layout from [TextLayout::build_with_width] as the first run's options say,
+    /// move it to their origin, add their shadow, and flip it to their y axis. `width` is
+    /// what the lines were lined up within, if anything.
+    pub(crate) fn finish(
+        &self,
+        layout: &mut Layout,
+        width: Option<i16>,
+    ) -> Result<(), RenderError> {
+        let Some(first) = self.spans.first() else {
+            return Ok(());
+        };
+        let metrics = first.font.metrics();
+        let scale = first.options.scale;
+        let scaled = |value: i16| {
+            let value = scale.apply(value as i32);
+            value.clamp(i16::MIN as i32, i16::MAX as i32) as i16
+        };
+
+        self.resize(layout)?;
+
+        let right = layout
+            .glyphs
+            .iter()
+            .map(|glyph| glyph.x.saturating_add(glyph.advance))
+            .max()
+            .unwrap_or(0);
+        let width = match (self.align, width) {
+            (Align::Center | Align::Right, Some(width)) => scaled(width),
+            _ => right,
+        };
+        // Each glyph's y is the top of its line, so the first line's is the top of the text
+        let top = layout.glyphs.iter().map(|glyph| glyph.y).min().unwrap_or(0);
+        let bottom = layout.glyphs.iter().map(|glyph| glyph.y).max().unwrap_or(0);
+        let bottom =
+            bottom.saturating_add(scaled(metrics.ascender.saturating_add(metrics.descender)));
+        let baseline = top.saturating_add(scaled(metrics.ascender));
+
+        let (x, y) = first.options.origin.locate(width, top, bottom, baseline);
+        for point in &mut layout.points {
+            point.x = point.x.saturating_sub(x);
+            point.y = point.y.saturating_sub(y);
+        }
+        for glyph in &mut layout.glyphs {
+            glyph.x = glyph.x.saturating_sub(x);
+            glyph.y = glyph.y.saturating_sub(y);
+        }
+
+        self.shadow_and_flip(layout);
+        Ok(())
+    }
+
+    /// Scale a layout from [TextLayout::build_with_width] as the first run's options say
+    pub(crate) fn resize(&self, layout: &mut Layout) -> Result<(), RenderError> {
+        let scale = self
+            .spans
+            .first()
+            .map_or(Scale::ONE, |first| first.options.scale);
+        if scale == Scale::ONE {
+            return Ok(());
+        }
+
+        for glyph in &mut layout.glyphs {
+            let overflow = RenderError::AdvanceOverflow {
+                offset: glyph.offset,
+            };
+            let scaled =
+                |value: i16| i16::try_from(scale.apply(value as i32)).map_err(|_| overflow);
+
+            (glyph.x, glyph.y) = (scaled(glyph.x)?, scaled(glyph.y)?);
+            glyph.advance = scaled(glyph.advance)?;
+            for point in &mut layout.points[glyph.points.clone()] {
+                (point.x, point.y) = (scaled(point.x)?, scaled(point.y)?);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Add the shadow given by the first run's options to a layout, and flip it to their
+    /// y axis
+    pub(crate) fn shadow_and_flip(&self, layout: &mut Layout) {
+        let Some(first) = self.spans.first() else {
+            return;
+        };
+
+        if let Some(shadow) = first.options.shadow {
+            let len = layout.points.len();
+            shadow.apply(&mut layout.points, 0);
+            for glyph in &mut layout.glyphs {
+                glyph.points = glyph.points.start + len..glyph.points.end + len;
+            }
+        }
+
+        layout.flip(first.options.y_axis);
+    }
+
+    /// The font of the run containing the byte at `offset` of the text, or `None` if
+    /// the layout is empty
+    pub(crate) fn font_at(&self, offset: usize) -> Option<VectorFont<'a>> {
+        self.spans.get(self.span_at(offset)).map(|span| span.font)
+    }
+
+    /// Lay out each paragraph on its own, then stack them up
+    fn paragraphs(
+        &self,
+        paragraphs: &ParagraphOptions,
+        options: RenderOptions,
+    ) -> Result<Layout, RenderError> {
+        let options = RenderOptions {
+            first_line_indent: paragraphs.first_line_indent,
+            ..options
+        };
+        let line_height = self.spans[0].font.metrics().line_height();
+
+        let mut layout = Layout::default();
+        // Top of the next paragraph
+        let mut y: i16 = 0;
+        let mut start = 0;
+
+        // An empty separator would split between every character
+        let split: Vec<&str> = match paragraphs.separator {
+            "" => alloc::vec![&self.text],
+            separator => self.text.split(separator).collect(),
+        };
+
+        for (i, paragraph) in split.into_iter().enumerate() {
+            let overflow = RenderError::AdvanceOverflow { offset: start };
+
+            if i > 0 {
+                let space = paragraphs.space_after as i32 + paragraphs.space_before as i32;
+                y = i16::try_from(y as i32 + space).map_err(|_| overflow)?;
+            }
+
+            let mut laid_out = self.lay_out(start, paragraph, &options)?;
+            let height = laid_out.glyphs.iter().map(|g| g.y).max().unwrap_or(0);
+
+            let first = layout.points.len();
+            for glyph in &mut laid_out.glyphs {
+                glyph.offset += start;
+                glyph.y = glyph.y.checked_add(y).ok_or(overflow)?;
+                glyph.points = glyph.points.start + first..glyph.points.end + first;
+            }
+            for point in &mut laid_out.points {
+                point.y = point.y.checked_add(y).ok_or(overflow)?;
+            }
+
+            layout.points.append(&mut laid_out.points);
+            layout.glyphs.append(&mut laid_out.glyphs);
+
+            y = i16::try_from(y as i32 + height as i32 + line_height as i32)
+                .map_err(|_| overflow)?;
+            start += paragraph.len() + paragraphs.separator.len();
+        }
+
+        Ok(layout)
+    }
+
+    /// Lay out `text`, which starts at byte offset `start` of the whole text, as if it
+    /// were on its own
+    fn lay_out(
+        &self,
+        start: usize,
+        text: &str,
+        options: &RenderOptions,
+    ) -> Result<Layout, RenderError> {
+        let metrics = self.spans[0].font.metrics();
+        // How far each span's glyphs move down to share the first span's baseline
+        let shifts: Vec<i16> = self
+            .spans
+            .iter()
+            .map(|span| {
+                metrics
+                    .baseline
+                    .saturating_sub(span.font.metrics().baseline)
+            })
+            .collect();
+
+        let mut points = Vec::new();
+        let glyphs = layout_glyphs(
+            text,
+            |offset, c| {
+                let span = self.span_at(start + offset);
+                let glyph = self.spans[span].font.glyph(c)?;
+                Ok((glyph, shifts[span]))
+            },
+            |offset| &self.spans[self.span_at(start + offset)].options,
+            &metrics,
+            options,
+            &mut points,
+        )
+        .map_err(|error| match error {
+            RenderError::AdvanceOverflow { offset } => RenderError::AdvanceOverflow {
+                offset: start + offset,
+            },
+            error => error,
+        })?;
+
+        Ok(Layout { points, glyphs })
+    }
+}
+
+impl Layout {
+    /// Split the points up by the pen each glyph is drawn with (see
+    /// [RenderOptions::pen]), in order of pen, so that a plotter changes pens as few
+    /// times as it can. Shadows are drawn with the pen of their glyphs, before the text.
+    ///
+    /// ```
+    /// # #[cfg(feature = "hershey")] {
+    /// use vector_text::{HersheyFont, RenderOptions, TextLayout, VectorFont};
+    ///
+    /// let font = VectorFont::HersheyFont(HersheyFont::Romans);
+    /// let mut layout = TextLayout::new();
+    /// layout.push_text("Red ", font, RenderOptions { pen: 1, ..Default::default() });
+    /// layout.push_text("black", font, RenderOptions::default());
+    ///
+    /// let pens = layout.build().unwrap().by_pen();
+    /// assert_eq!(pens.iter().map(|(pen, _)| *pen).collect::<Vec<_>>(), [0, 1]);
+    /// # }
+    /// ```
+    pub fn by_pen(&self) -> Vec<(u8, Vec<Point>)> {
+        let shadow = self.shadow_len();
+
+        let mut pens: Vec<u8> = self.glyphs.iter().map(|glyph| glyph.pen).collect();
+        pens.sort_unstable();
+        pens.dedup();
+
+        pens.into_iter()
+            .map(|pen| {
+                let glyphs = self.glyphs.iter().filter(|glyph| glyph.pen == pen);
+                let shadows = glyphs
+                    .clone()
+                    .map(|glyph| glyph.points.start - shadow..glyph.points.end - shadow);
+                let text = glyphs.map(|glyph| glyph.points.clone());
+
+                let points = shadows
+                    .take_while(|_| shadow > 0)
+                    .chain(text)
+                    .flat_map(|range| self.points[range].iter().copied())
+                    .collect();
+                (pen, points)
+            })
+            .collect()
+    }
+
+    /// Number of points in the shadow, which comes before all of the glyphs, one for one
+    pub(crate) fn shadow_len(&self) -> usize {
+        self.glyphs
+            .iter()
+            .map(|glyph| glyph.points.start)
+            .min()
+            .unwrap_or(0)
+    }
+
+    /// Convert the layout from y increasing downward to `y_axis`
+    pub(crate) fn flip(&mut self, y_axis: YAxis) {
+        y_axis.apply(&mut self.points);
+        if y_axis == YAxis::Up {
+            for glyph in &mut self.glyphs {
+                glyph.y = glyph.y.saturating_neg();
+            }
+        }
+    }
+
+    /// Move each line across to line up as `align` says, within `max_width` if given or
+    /// else the widest line.
+    fn align(&mut self, align: Align, max_width: Option<i16>) {
+        if align == Align::Left {
+            return;
+        }
+
+        // Lines are found by the y of their glyphs, and measured without trailing spaces
+        let mut lines: Vec<(i16, i32)> = Vec::new();
+        for glyph in &self.glyphs {
+            let right = if glyph.character.is_whitespace() {
+                0
+            } else {
+                glyph.x as i32 + glyph.advance as i32
+            };
+
+            match lines.iter_mut().find(|(y, _)| *y == glyph.y) {
+                Some((_, width)) => *width = (*width).max(right),
+                None => lines.push((glyph.y, right)),
+            }
+        }
+
+        let container = max_width.map_or_else(
+            || lines.iter().map(|&(_, w)| w).max().unwrap_or(0),
+            i32::from,
+        );
+
+        for glyph in &mut self.glyphs {
+            let width = lines
+                .iter()
+                .find(|(y, _)| *y == glyph.y)
+                .map_or(0, |&(_, w)| w);
+            let dx = match align {
+                Align::Left => 0,
+                Align::Center => (container - width) / 2,
+                Align::Right => container - width,
+            };
+            let dx = dx.clamp(i16::MIN as i32, i16::MAX as i32) as i16;
+
+            glyph.x = glyph.x.saturating_add(dx);
+            for point in &mut self.points[glyph.points.clone()] {
+                point.x = point.x.saturating_add(dx);
+            }
+        }
+    }
+}
@@ -6,47 +6,490 @@
 //! This can be used for drawing text to plotters, with laser displays, on
 //! XY oscilloscopes, or for other purposes!
 //!
-//! The library supports `no_std` environments but requires an allocator.
+//! The library supports `no_std` environments but requires an allocator. Without one,
+//! `vector-text-core` can still draw text with its default features disabled.
+//! Enable the `normalize` feature to normalize text to NFC before drawing it, so that
+//! decomposed accented letters use the fonts' precomposed glyphs, and the `defmt`
+//! feature to log points, metrics, and errors with [defmt](https://defmt.ferrous-systems.com).
+//! The `mint` feature converts points to [mint](https://docs.rs/mint) types, as
+//! used by many maths and graphics crates.
+//!
+//! Each font backend has a cargo feature (`hershey`, `borland`, `newstroke`, and
+//! `svgfont`), enabled by default along with every Borland font. To save space on small
+//! targets, disable the default features and enable only the backends you use. The
+//! Borland fonts are chosen one at a time, with features like `borland-litt` (each of
+//! which enables the backend), or all at once with `borland-all`. [MinimalFont], [SegmentFont],
+//! and [CustomFont] are always available. The `compress` feature stores the built-in
+//! fonts' glyphs delta-encoded, in about half the space, and decodes them while drawing.
+//! To choose where the tables go in flash, set the `VECTOR_TEXT_LINK_SECTION`
+//! environment variable to the name of a linker section when building. This works on
+//! targets which map flash into memory (like most ARM microcontrollers), but not on AVR,
+//! where reading program memory needs special instructions. There, the `progmem` feature
+//! keeps the glyphs' points (the bulk of each font) in program memory and reads them with
+//! those instructions, as [avr-progmem](https://docs.rs/avr-progmem) does; the smaller
+//! glyph tables are still copied to RAM, so subset the fonts with `VECTOR_TEXT_SUBSET`.
+//!
+//! Other features add ways to use the rendered text:
+//! - `cache`: `RenderCache`, which keeps the glyphs it has already looked up, for text
+//!   which is drawn over and over (such as every frame of a laser display)
+//! - `raster`: `Raster`, which draws text into a grayscale image buffer, to preview
+//!   output without writing a file
+//! - `piet`: `stroke_piet`, which draws text onto any `piet::RenderContext` (and so
+//!   into druid and other piet-based GUIs)
+//! - `dxf`: `to_lw_polylines` and `to_polylines`, which turn text into entities for
+//!   drawings made with the [dxf](https://docs.rs/dxf) crate
+//! - `gcode`: `to_gcode`, which turns text into G-code commands for a pen plotter
+//! - `markup`: `parse_markup`, which reads text styled like `*bold*` or
+//!   `{font=gotheng}...{/font}` into spans for [render_spans]
+//! - `hyphenation`: `hyphenate_english`, which lets wrapped lines break English words
+//!   where TeX would hyphenate them
+//! - `extrude`: `extrude`, which turns text into a 3D wireframe
 //!
 //! Supported fonts include:
 //! - [BGI (Borland)](https://moddingwiki.shikadi.net/wiki/BGI_Stroked_Font) fonts including `LITT.CHR`, via [vector_text_borland]
 //! - [Hershey](https://paulbourke.net/dataformats/hershey/) fonts, via [vector_text_hershey]
 //! - The [NewStroke](https://vovanium.ru/sledy/newstroke/en) font, via [vector_text_newstroke]
+//! - A tiny built-in font of capitals and digits, [MinimalFont]
+//! - Seven- and sixteen-segment display fonts, [SegmentFont]
+//! - Single-line SVG fonts (like the EMS engraving fonts used by Inkscape's Hershey Text extension), via [vector_text_svgfont]
+//! - Your own fonts defined in code, via [CustomFont]
 //!
 //! This library provides the render_text function which you can use to render text, e.g.:
 //!
 //! ```
+//! # #[cfg(feature = "hershey")] {
 //! use vector_text::{render_text, VectorFont, HersheyFont};
 //!
 //! let result = render_text("Hello World!", VectorFont::HersheyFont(HersheyFont::Romans));
+//! # }
 //! ```
 
+use alloc::boxed::Box;
 use alloc::vec::Vec;
+use core::ops::RangeInclusive;
+#[cfg(feature = "borland")]
 pub use vector_text_borland::BorlandFont;
-pub use vector_text_core::Point;
+use vector_text_core::Coverage;
+#[cfg(feature = "progmem")]
+pub use vector_text_core::ProgmemBytes;
+#[cfg(any(feature = "hershey", feature = "borland", feature = "newstroke"))]
 use vector_text_core::Renderer;
-pub use vector_text_hershey::HersheyFont;
+pub use vector_text_core::{
+    Anchor, AnchorKind, BBox, ControlChars, CustomFont, Font, FontMetrics, FontReport, Glyph,
+    GlyphProblem, HatchOptions, Hyphenation, Join, LaserOptions, LoadedGlyph, MinimalFont,
+    MissingGlyph, Origin, OutlineOptions, OverBudget, PackedPoint, ParseError, ParseFontError,
+    PathStats, PlacedGlyph, Point, RenderError, RenderOptions, Scale, SegmentFont, ShadedPoint,
+    Shadow, Strokes, StrokesIter, TravelOptions, YAxis, compensate_tool, condition_for_laser,
+    double_strokes, expand_strokes, fit_to_budget, hatch_fill, optimize_travel,
+    remove_duplicate_segments, resample, shade, simplify, smooth, split_strokes, validate,
+};
+#[cfg(feature = "hershey")]
+pub use vector_text_hershey::{
+    HersheyFont, HersheySymbol, SymbolSet, render_hershey_ids, render_symbols,
+};
+#[cfg(feature = "svgfont")]
+pub use vector_text_svgfont::{EmbeddedSvgFont, SvgFont};
 
 extern crate alloc;
 
+#[cfg(feature = "cache")]
+mod cache;
+mod columns;
+#[cfg(feature = "dxf")]
+mod dxf_adapter;
+#[cfg(feature = "extrude")]
+mod extrude;
+#[cfg(feature = "gcode")]
+mod gcode;
+#[cfg(feature = "hyphenation")]
+mod hyphenation;
+mod layout;
+#[cfg(feature = "markup")]
+mod markup;
+mod marquee;
+#[cfg(feature = "piet")]
+mod piet_adapter;
+#[cfg(feature = "raster")]
+mod raster;
+mod registry;
+mod reveal;
+mod spans;
+mod specimen;
+mod spiral;
+mod stack;
+mod text_box;
+
+#[cfg(feature = "cache")]
+pub use cache::RenderCache;
+pub use columns::Columns;
+#[cfg(feature = "dxf")]
+pub use dxf_adapter::{to_lw_polylines, to_polylines};
+#[cfg(feature = "extrude")]
+pub use extrude::{Point3, extrude};
+#[cfg(feature = "gcode")]
+pub use gcode::{GCode, GCodeOptions, Mnemonic, Word, to_gcode};
+#[cfg(feature = "hyphenation")]
+pub use hyphenation::hyphenate_english;
+pub use layout::{Align, Layout, ParagraphOptions, TextLayout};
+#[cfg(feature = "markup")]
+pub use markup::{MarkupError, parse_markup};
+pub use marquee::{Marquee, MarqueeFrames};
+#[cfg(feature = "piet")]
+pub use piet_adapter::{stroke_piet, to_bez_path};
+#[cfg(feature = "raster")]
+pub use raster::Raster;
+pub use reveal::{Reveal, RevealStep};
+pub use spans::{ScriptPosition, TextSpan, render_spans};
+pub use specimen::{specimen, waterfall};
+pub use spiral::Spiral;
+pub use stack::{FontStack, Run};
+pub use text_box::{Overflow, TextBox, TextBoxError};
+
 /// A font using any of the supported vector font formats.
-pub enum VectorFont {
+///
+/// Fonts held by reference, such as [VectorFont::Custom], are only equal to one another
+/// if they refer to the same font, rather than to two fonts with the same glyphs.
+///
+/// The default font is [HersheyFont::Romans], or [MinimalFont] without the `hershey`
+/// feature.
+#[derive(Debug, Copy, Clone)]
+#[non_exhaustive]
+pub enum VectorFont<'a> {
+    #[cfg(feature = "hershey")]
     HersheyFont(HersheyFont),
+    #[cfg(feature = "borland")]
     BorlandFont(BorlandFont),
+    #[cfg(feature = "newstroke")]
     NewstrokeFont(()),
+    /// The tiny built-in font, see [MinimalFont]
+    MinimalFont(MinimalFont),
+    /// A font imitating a segment display, see [SegmentFont]
+    SegmentFont(SegmentFont),
+    /// A font defined in code, see [CustomFont]
+    Custom(&'a CustomFont),
+    /// A single-line SVG font loaded at runtime, see [SvgFont]
+    #[cfg(feature = "svgfont")]
+    SvgFont(&'a SvgFont),
+    /// A single-line SVG font compiled into the program, see [EmbeddedSvgFont]
+    #[cfg(feature = "svgfont")]
+    EmbeddedSvgFont(EmbeddedSvgFont),
+}
+
+impl VectorFont<'_> {
+    /// Check whether this font has a glyph for the given character.
+    pub fn supports_char(&self, character: char) -> bool {
+        self.glyph(character).is_ok()
+    }
+
+    /// Iterate over the ranges of characters this font has glyphs for, in increasing order.
+    ///
+    /// For example, to check that some text can be drawn before plotting it:
+    ///
+    /// ```
+    /// # #[cfg(feature = "hershey")] {
+    /// use vector_text::{HersheyFont, VectorFont};
+    ///
+    /// let font = VectorFont::HersheyFont(HersheyFont::Romans);
+    /// assert!(font.coverage().any(|range| range.contains(&'A')));
+    /// assert!("Hello".chars().all(|c| font.supports_char(c)));
+    /// # }
+    /// ```
+    pub fn coverage(&self) -> impl Iterator<Item = RangeInclusive<char>> + '_ {
+        let candidates: Box<dyn Iterator<Item = char>> = match self {
+            #[cfg(feature = "hershey")]
+            Self::HersheyFont(font) => {
+                Box::new(vector_text_hershey::HersheyRenderer::candidates(font))
+            }
+            #[cfg(feature = "borland")]
+            Self::BorlandFont(font) => {
+                Box::new(vector_text_borland::BorlandRenderer::candidates(font))
+            }
+            #[cfg(feature = "newstroke")]
+            Self::NewstrokeFont(font) => {
+                Box::new(vector_text_newstroke::NewstrokeRenderer::candidates(font))
+            }
+            Self::MinimalFont(font) => Box::new(font.chars()),
+            Self::SegmentFont(font) => Box::new(font.chars()),
+            Self::Custom(font) => Box::new(font.chars()),
+            #[cfg(feature = "svgfont")]
+            Self::SvgFont(font) => Box::new(font.chars()),
+            #[cfg(feature = "svgfont")]
+            Self::EmbeddedSvgFont(font) => Box::new(font.glyphs().iter().map(|&(c, _)| c)),
+        };
+
+        Coverage::new(candidates, |c| self.supports_char(c))
+    }
+
+    /// Look up the glyph used to draw the given character in this font.
+    fn glyph(&self, character: char) -> Result<Glyph<'_>, RenderError> {
+        match self {
+            #[cfg(feature = "hershey")]
+            Self::HersheyFont(font) => vector_text_hershey::HersheyRenderer::glyph(font, character),
+            #[cfg(feature = "borland")]
+            Self::BorlandFont(font) => vector_text_borland::BorlandRenderer::glyph(font, character),
+            #[cfg(feature = "newstroke")]
+            Self::NewstrokeFont(font) => {
+                vector_text_newstroke::NewstrokeRenderer::glyph(font, character)
+            }
+            Self::MinimalFont(font) => {
+                Font::glyph(font, character).ok_or(RenderError::UnsupportedCharacter(character))
+            }
+            Self::SegmentFont(font) => {
+                Font::glyph(font, character).ok_or(RenderError::UnsupportedCharacter(character))
+            }
+            Self::Custom(font) => {
+                Font::glyph(*font, character).ok_or(RenderError::UnsupportedCharacter(character))
+            }
+            #[cfg(feature = "svgfont")]
+            Self::SvgFont(font) => {
+                Font::glyph(*font, character).ok_or(RenderError::UnsupportedCharacter(character))
+            }
+            #[cfg(feature = "svgfont")]
+            Self::EmbeddedSvgFont(font) => {
+                Font::glyph(font, character).ok_or(RenderError::UnsupportedCharacter(character))
+            }
+        }
+    }
+
+    /// Get the overall metrics of this font.
+    fn metrics(&self) -> FontMetrics {
+        match self {
+            #[cfg(feature = "hershey")]
+            Self::HersheyFont(font) => vector_text_hershey::HersheyRenderer::metrics(font),
+            #[cfg(feature = "borland")]
+            Self::BorlandFont(font) => vector_text_borland::BorlandRenderer::metrics(font),
+            #[cfg(feature = "newstroke")]
+            Self::NewstrokeFont(font) => vector_text_newstroke::NewstrokeRenderer::metrics(font),
+            Self::MinimalFont(font) => Font::metrics(font),
+            Self::SegmentFont(font) => Font::metrics(font),
+            Self::Custom(font) => Font::metrics(*font),
+            #[cfg(feature = "svgfont")]
+            Self::SvgFont(font) => Font::metrics(*font),
+            #[cfg(feature = "svgfont")]
+            Self::EmbeddedSvgFont(font) => Font::metrics(font),
+        }
+    }
+}
+
+impl Default for VectorFont<'_> {
+    fn default() -> Self {
+        #[cfg(feature = "hershey")]
+        let font = Self::HersheyFont(HersheyFont::Romans);
+        #[cfg(not(feature = "hershey"))]
+        let font = Self::MinimalFont(MinimalFont);
+        font
+    }
+}
+
+impl PartialEq for VectorFont<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            #[cfg(feature = "hershey")]
+            (Self::HersheyFont(a), Self::HersheyFont(b)) => a == b,
+            #[cfg(feature = "borland")]
+            (Self::BorlandFont(a), Self::BorlandFont(b)) => a == b,
+            #[cfg(feature = "newstroke")]
+            (Self::NewstrokeFont(()), Self::NewstrokeFont(())) => true,
+            (Self::MinimalFont(a), Self::MinimalFont(b)) => a == b,
+            (Self::SegmentFont(a), Self::SegmentFont(b)) => a == b,
+            (Self::Custom(a), Self::Custom(b)) => core::ptr::eq(*a, *b),
+            #[cfg(feature = "svgfont")]
+            (Self::SvgFont(a), Self::SvgFont(b)) => core::ptr::eq(*a, *b),
+            #[cfg(feature = "svgfont")]
+            (Self::EmbeddedSvgFont(a), Self::EmbeddedSvgFont(b)) => a == b,
+            _ => false,
+        }
+    }
+}
+
+impl Eq for VectorFont<'_> {}
+
+impl core::hash::Hash for VectorFont<'_> {
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        core::mem::discriminant(self).hash(state);
+        match self {
+            #[cfg(feature = "hershey")]
+            Self::HersheyFont(font) => font.hash(state),
+            #[cfg(feature = "borland")]
+            Self::BorlandFont(font) => font.hash(state),
+            #[cfg(feature = "newstroke")]
+            Self::NewstrokeFont(()) => {}
+            Self::MinimalFont(font) => font.hash(state),
+            Self::SegmentFont(font) => font.hash(state),
+            Self::Custom(font) => core::ptr::hash(*font, state),
+            #[cfg(feature = "svgfont")]
+            Self::SvgFont(font) => core::ptr::hash(*font, state),
+            #[cfg(feature = "svgfont")]
+            Self::EmbeddedSvgFont(font) => font.hash(state),
+        }
+    }
+}
+
+impl Font for VectorFont<'_> {
+    fn glyph(&self, character: char) -> Option<Glyph<'_>> {
+        VectorFont::glyph(self, character).ok()
+    }
+
+    fn metrics(&self) -> FontMetrics {
+        VectorFont::metrics(self)
+    }
 }
 
 /// Render the given text string to a list of points using the specified font.
-pub fn render_text(text: &str, font: VectorFont) -> Vec<Point> {
+///
+/// Characters which the font cannot draw are skipped. If the text is too wide to
+/// represent, it is cut off after the last glyph that fits; use [try_render_text]
+/// to detect this.
+pub fn render_text(text: &str, font: VectorFont<'_>) -> Vec<Point> {
     match font {
+        #[cfg(feature = "hershey")]
         VectorFont::HersheyFont(font) => {
             vector_text_hershey::HersheyRenderer::render_text(text, font)
         }
+        #[cfg(feature = "borland")]
         VectorFont::BorlandFont(font) => {
             vector_text_borland::BorlandRenderer::render_text(text, font)
         }
+        #[cfg(feature = "newstroke")]
         VectorFont::NewstrokeFont(font) => {
             vector_text_newstroke::NewstrokeRenderer::render_text(text, font)
         }
+        VectorFont::MinimalFont(font) => font.render_text(text),
+        VectorFont::SegmentFont(font) => font.render_text(text),
+        VectorFont::Custom(font) => font.render_text(text),
+        #[cfg(feature = "svgfont")]
+        VectorFont::SvgFont(font) => font.render_text(text),
+        #[cfg(feature = "svgfont")]
+        VectorFont::EmbeddedSvgFont(font) => font.render_text(text),
+    }
+}
+
+/// Render the given text string to a list of points using the specified font.
+///
+/// Unlike [render_text], this returns an error if any character cannot be drawn
+/// instead of silently leaving it out.
+pub fn try_render_text(text: &str, font: VectorFont<'_>) -> Result<Vec<Point>, RenderError> {
+    match font {
+        #[cfg(feature = "hershey")]
+        VectorFont::HersheyFont(font) => {
+            vector_text_hershey::HersheyRenderer::try_render_text(text, font)
+        }
+        #[cfg(feature = "borland")]
+        VectorFont::BorlandFont(font) => {
+            vector_text_borland::BorlandRenderer::try_render_text(text, font)
+        }
+        #[cfg(feature = "newstroke")]
+        VectorFont::NewstrokeFont(font) => {
+            vector_text_newstroke::NewstrokeRenderer::try_render_text(text, font)
+        }
+        VectorFont::MinimalFont(font) => font.try_render_text(text),
+        VectorFont::SegmentFont(font) => font.try_render_text(text),
+        VectorFont::Custom(font) => font.try_render_text(text),
+        #[cfg(feature = "svgfont")]
+        VectorFont::SvgFont(font) => font.try_render_text(text),
+        #[cfg(feature = "svgfont")]
+        VectorFont::EmbeddedSvgFont(font) => font.try_render_text(text),
+    }
+}
+
+/// Count the points [render_text] would produce for the given text and font, without
+/// drawing them.
+///
+/// ```
+/// # #[cfg(feature = "hershey")] {
+/// use vector_text::{point_count, render_text, HersheyFont, VectorFont};
+///
+/// let font = VectorFont::HersheyFont(HersheyFont::Romans);
+/// assert_eq!(point_count("Hello", font), render_text("Hello", font).len());
+/// # }
+/// ```
+pub fn point_count(text: &str, font: VectorFont<'_>) -> usize {
+    vector_text_core::count_glyphs(
+        text,
+        |c| font.glyph(c),
+        &font.metrics(),
+        &RenderOptions::default(),
+    )
+}
+
+/// Render the given text string to a list of points using the specified font
+/// and rendering options.
+///
+/// For example, to draw a `?` in place of any character the font does not cover:
+///
+/// ```
+/// # #[cfg(feature = "newstroke")] {
+/// use vector_text::{render_text_with_options, MissingGlyph, RenderOptions, VectorFont};
+///
+/// let options = RenderOptions {
+///     missing_glyph: MissingGlyph::Replace('?'),
+///     ..Default::default()
+/// };
+/// let result = render_text_with_options("I \u{1F600} plotters", VectorFont::NewstrokeFont(()), &options);
+/// assert!(result.is_ok());
+/// # }
+/// ```
+///
+/// [RenderOptions::origin] places text by the edges of its lines, whichever y the font
+/// puts its baseline at (Hershey fonts have it at 9, and Borland fonts at 0):
+///
+/// ```
+/// # #[cfg(all(feature = "borland-litt", feature = "hershey"))] {
+/// use vector_text::{
+///     render_text_with_options, BBox, BorlandFont, Font, HersheyFont, Origin, RenderOptions,
+///     VectorFont,
+/// };
+///
+/// let fonts = [
+///     VectorFont::HersheyFont(HersheyFont::Romans),
+///     VectorFont::BorlandFont(BorlandFont::Litt),
+/// ];
+/// for font in fonts {
+///     let metrics = Font::metrics(&font);
+///     let bbox = |origin| {
+///         let options = RenderOptions { origin, ..Default::default() };
+///         BBox::of(&render_text_with_options("Hxp", font, &options).unwrap()).unwrap()
+///     };
+///
+///     // Below the top of the line, with the `p` reaching down to its bottom
+///     let top_left = bbox(Origin::TopLeft);
+///     assert!(top_left.min.1 >= 0);
+///     assert_eq!(top_left.max.1, metrics.ascender + metrics.descender);
+///
+///     // Capitals standing on the baseline, and everything above the bottom of the line
+///     assert_eq!(bbox(Origin::BaselineLeft).min.1, -metrics.cap_height);
+///     assert_eq!(bbox(Origin::BottomLeft).max.1, 0);
+///
+///     // Around the middle of the line
+///     let center = bbox(Origin::Center);
+///     assert!(center.min.1 < 0 && center.max.1 > 0);
+/// }
+/// # }
+/// ```
+pub fn render_text_with_options(
+    text: &str,
+    font: VectorFont<'_>,
+    options: &RenderOptions,
+) -> Result<Vec<Point>, RenderError> {
+    match font {
+        #[cfg(feature = "hershey")]
+        VectorFont::HersheyFont(font) => {
+            vector_text_hershey::HersheyRenderer::render_text_with_options(text, font, options)
+        }
+        #[cfg(feature = "borland")]
+        VectorFont::BorlandFont(font) => {
+            vector_text_borland::BorlandRenderer::render_text_with_options(text, font, options)
+        }
+        #[cfg(feature = "newstroke")]
+        VectorFont::NewstrokeFont(font) => {
+            vector_text_newstroke::NewstrokeRenderer::render_text_with_options(text, font, options)
+        }
+        VectorFont::MinimalFont(font) => font.render_text_with_options(text, options),
+        VectorFont::SegmentFont(font) => font.render_text_with_options(text, options),
+        VectorFont::Custom(font) => font.render_text_with_options(text, options),
+        #[cfg(feature = "svgfont")]
+        VectorFont::SvgFont(font) => font.render_text_with_options(text, options),
+        #[cfg(feature = "svgfont")]
+        VectorFont::EmbeddedSvgFont(font) => font.render_text_with_options(text, options),
     }
 }
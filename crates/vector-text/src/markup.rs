@@ -0,0 +1,259 @@
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::{ScriptPosition, TextSpan, VectorFont};
+
+/// An error in the markup given to [parse_markup].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum MarkupError {
+    /// A `{font=...}` tag names a font which isn't built in.
+    UnknownFont {
+        /// Byte offset in the markup of the tag
+        offset: usize,
+    },
+    /// A tag isn't one of those supported, has an invalid value, or has no closing `}`.
+    InvalidTag {
+        /// Byte offset in the markup of the tag
+        offset: usize,
+    },
+    /// A closing tag, like `{/font}`, has no opening tag to match.
+    UnmatchedClose {
+        /// Byte offset in the markup of the tag
+        offset: usize,
+    },
+    /// A style is still open at the end of the markup.
+    Unclosed {
+        /// Byte offset in the markup of where the style was opened
+        offset: usize,
+    },
+}
+
+impl fmt::Display for MarkupError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnknownFont { offset } => write!(f, "unknown font at byte offset {}", offset),
+            Self::InvalidTag { offset } => write!(f, "invalid tag at byte offset {}", offset),
+            Self::UnmatchedClose { offset } => {
+                write!(f, "closing tag at byte offset {} was never opened", offset)
+            }
+            Self::Unclosed { offset } => {
+                write!(f, "style opened at byte offset {} is never closed", offset)
+            }
+        }
+    }
+}
+
+impl core::error::Error for MarkupError {}
+
+/// Parse text with a few lightweight styles into spans for [render_spans](crate::render_spans).
+///
+/// The markup supports:
+/// - `*bold*` and `_underlined_` text
+/// - `{font=name}...{/font}` to change font, by its name in the registry (see
+///   [VectorFont::by_name]) or just the part after the family, like `gotheng` for
+///   `hershey-gotheng`
+/// - `{scale=1.5}...{/scale}` to change size, relative to the font's own units
+/// - `{sup}...{/sup}` and `{sub}...{/sub}` for superscripts and subscripts
+/// - `{smallcaps}...{/smallcaps}` for small capitals
+/// - `\` before any character to draw it as it is, like `\*`
+///
+/// Text outside of any `{font}` tag is drawn with `font`. Fonts, sizes, and script
+/// positions can be nested, and each closing tag goes back to what was used before its
+/// opening tag.
+///
+/// ```
+/// # #[cfg(feature = "hershey")] {
+/// use vector_text::{parse_markup, render_spans, HersheyFont, RenderOptions, VectorFont};
+///
+/// let font = VectorFont::HersheyFont(HersheyFont::Romans);
+/// let spans = parse_markup("Dear *{font=gotheng}Reader{/font}*, \\_hi\\_", font).unwrap();
+///
+/// assert_eq!(spans[1].text, "Reader");
+/// assert!(spans[1].bold);
+///
+/// let result = render_spans(&spans, &RenderOptions::default()).unwrap();
+/// # }
+/// ```
+pub fn parse_markup<'a>(
+    markup: &'a str,
+    font: VectorFont<'a>,
+) -> Result<Vec<TextSpan<'a>>, MarkupError> {
+    let mut parser = Parser {
+        markup,
+        spans: Vec::new(),
+        start: 0,
+        fonts: Vec::new(),
+        scales: Vec::new(),
+        positions: Vec::new(),
+        bold: None,
+        underline: None,
+        small_caps: None,
+        font,
+    };
+
+    let mut offset = 0;
+    while let Some(c) = markup[offset..].chars().next() {
+        let mut next = offset + c.len_utf8();
+
+        match c {
+            '\\' => {
+                parser.flush(offset);
+                parser.start = next;
+                // The escaped character starts the next run of text, whatever it is
+                if let Some(escaped) = markup[next..].chars().next() {
+                    next += escaped.len_utf8();
+                }
+            }
+            '*' => {
+                parser.flush(offset);
+                parser.bold = toggle(parser.bold, offset);
+                parser.start = next;
+            }
+            '_' => {
+                parser.flush(offset);
+                parser.underline = toggle(parser.underline, offset);
+                parser.start = next;
+            }
+            '{' => {
+                parser.flush(offset);
+                let Some(len) = markup[next..].find('}') else {
+                    return Err(MarkupError::InvalidTag { offset });
+                };
+                parser.tag(offset, &markup[next..next + len])?;
+
+                next += len + 1;
+                parser.start = next;
+            }
+            _ => {}
+        }
+
+        offset = next;
+    }
+
+    parser.flush(markup.len());
+
+    // Report the earliest style left open
+    let unclosed = [parser.bold, parser.underline, parser.small_caps]
+        .into_iter()
+        .flatten()
+        .chain(parser.fonts.first().map(|&(offset, _)| offset))
+        .chain(parser.scales.first().map(|&(offset, _)| offset))
+        .chain(parser.positions.first().map(|&(offset, _)| offset))
+        .min();
+
+    match unclosed {
+        Some(offset) => Err(MarkupError::Unclosed { offset }),
+        None => Ok(parser.spans),
+    }
+}
+
+/// Open a style if it isn't open, or close it if it is
+fn toggle(open: Option<usize>, offset: usize) -> Option<usize> {
+    match open {
+        Some(_) => None,
+        None => Some(offset),
+    }
+}
+
+/// Find a built-in font by its name, or by its name without the family
+fn find_font(name: &str) -> Option<VectorFont<'static>> {
+    VectorFont::by_name(name).or_else(|| {
+        VectorFont::iter().find(|font| {
+            font.name()
+                .and_then(|full| {
+                    let (_, short) = full.split_once('-')?;
+                    Some(short.eq_ignore_ascii_case(name))
+                })
+                .unwrap_or(false)
+        })
+    })
+}
+
+/// State of [parse_markup] as it goes through the markup
+struct Parser<'a> {
+    markup: &'a str,
+    spans: Vec<TextSpan<'a>>,
+    /// Byte offset where the current run of text starts
+    start: usize,
+    /// Fonts and sizes chosen by open tags, with the offsets of the tags
+    fonts: Vec<(usize, VectorFont<'a>)>,
+    scales: Vec<(usize, f32)>,
+    positions: Vec<(usize, ScriptPosition)>,
+    /// Offsets where bold and underlined text were opened, if they are
+    bold: Option<usize>,
+    underline: Option<usize>,
+    small_caps: Option<usize>,
+    /// Font used outside of any tag
+    font: VectorFont<'a>,
+}
+
+impl<'a> Parser<'a> {
+    /// Add the run of text from the start of the current run up to `end` as a span, in
+    /// the current style
+    fn flush(&mut self, end: usize) {
+        if self.start >= end {
+            return;
+        }
+
+        self.spans.push(TextSpan {
+            text: &self.markup[self.start..end],
+            font: self.fonts.last().map_or(self.font, |&(_, font)| font),
+            scale: self.scales.last().map_or(1.0, |&(_, scale)| scale),
+            bold: self.bold.is_some(),
+            underline: self.underline.is_some(),
+            position: self
+                .positions
+                .last()
+                .map_or(ScriptPosition::Normal, |&(_, position)| position),
+            small_caps: self.small_caps.is_some(),
+        });
+    }
+
+    /// Apply the tag at `offset`, with the given contents between its braces
+    fn tag(&mut self, offset: usize, tag: &str) -> Result<(), MarkupError> {
+        let closed = match tag.split_once('=') {
+            Some(("font", name)) => {
+                let font = find_font(name).ok_or(MarkupError::UnknownFont { offset })?;
+                self.fonts.push((offset, font));
+                return Ok(());
+            }
+            Some(("scale", value)) => match value.parse::<f32>() {
+                Ok(scale) if scale.is_finite() && scale > 0.0 => {
+                    self.scales.push((offset, scale));
+                    return Ok(());
+                }
+                _ => return Err(MarkupError::InvalidTag { offset }),
+            },
+            None if tag == "sup" || tag == "sub" => {
+                let position = match tag {
+                    "sup" => ScriptPosition::Superscript,
+                    _ => ScriptPosition::Subscript,
+                };
+                self.positions.push((offset, position));
+                return Ok(());
+            }
+            None if tag == "smallcaps" && self.small_caps.is_none() => {
+                self.small_caps = Some(offset);
+                return Ok(());
+            }
+            None if tag == "/font" => self.fonts.pop().is_some(),
+            None if tag == "/scale" => self.scales.pop().is_some(),
+            None if tag == "/smallcaps" => self.small_caps.take().is_some(),
+            None if tag == "/sup" => self
+                .positions
+                .pop_if(|(_, position)| *position == ScriptPosition::Superscript)
+                .is_some(),
+            None if tag == "/sub" => self
+                .positions
+                .pop_if(|(_, position)| *position == ScriptPosition::Subscript)
+                .is_some(),
+            _ => return Err(MarkupError::InvalidTag { offset }),
+        };
+
+        if closed {
+            Ok(())
+        } else {
+            Err(MarkupError::UnmatchedClose { offset })
+        }
+    }
+}
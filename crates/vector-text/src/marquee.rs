@@ -0,0 +1,127 @@
+use alloc::vec::Vec;
+
+use vector_text_core::{BBox, Point};
+
+/// A window for text to scroll across from right to left, like a news ticker, for
+/// displays too narrow to show it all at once.
+///
+/// ```
+/// # #[cfg(feature = "hershey")] {
+/// use vector_text::{HersheyFont, Marquee, VectorFont, render_text};
+///
+/// let points = render_text("Breaking news", VectorFont::HersheyFont(HersheyFont::Romans));
+/// let marquee = Marquee { width: 100, step: 4 };
+///
+/// for frame in marquee.frames(&points) {
+///     assert!(frame.iter().all(|point| (0..=100).contains(&point.x)));
+/// }
+/// # }
+/// ```
+#[derive(Debug, Copy, Clone)]
+pub struct Marquee {
+    /// Width of the window, which the frames are drawn from an x of 0 to
+    pub width: i16,
+    /// Distance the text moves left from one frame to the next, at least 1
+    pub step: i16,
+}
+
+impl Marquee {
+    /// Iterate over the frames of rendered text scrolling across the window.
+    ///
+    /// The text starts just off the right edge, and the last frame is the one before it
+    /// has scrolled off the left edge. Lines cut by the edges of the window are drawn
+    /// up to them, and anything outside it is left out. To scroll the text again and
+    /// again, [cycle](Iterator::cycle) the frames.
+    pub fn frames<'a>(&self, points: &'a [Point]) -> MarqueeFrames<'a> {
+        // Where the text is moved to, from starting at the right edge
+        let (start, end) = match BBox::of(points) {
+            Some(bbox) => (self.width as i32 - bbox.min.0 as i32, -(bbox.max.0 as i32)),
+            None => (0, 0),
+        };
+
+        MarqueeFrames {
+            points,
+            width: self.width.max(0) as i32,
+            step: self.step.max(1) as i32,
+            shift: start,
+            end,
+        }
+    }
+}
+
+/// An iterator over the frames of a [Marquee], from [Marquee::frames].
+#[derive(Debug, Clone)]
+pub struct MarqueeFrames<'a> {
+    points: &'a [Point],
+    width: i32,
+    step: i32,
+    /// Distance to move the text right for the next frame
+    shift: i32,
+    /// Shift at which the text has scrolled off the left edge
+    end: i32,
+}
+
+impl Iterator for MarqueeFrames<'_> {
+    type Item = Vec<Point>;
+
+    fn next(&mut self) -> Option<Vec<Point>> {
+        if self.shift <= self.end {
+            return None;
+        }
+
+        let mut frame = Vec::new();
+        // Where the pen was left in the frame, to move it only when it has to be
+        let mut at = None;
+        let mut from = (0, 0);
+
+        for point in self.points {
+            let to = (point.x as i32 + self.shift, point.y as i32);
+            if point.pen
+                && let Some((start, end)) = clip(from, to, self.width)
+            {
+                if at != Some(start) {
+                    frame.push(place(start, false));
+                }
+                frame.push(place(end, true));
+                at = Some(end);
+            }
+            from = to;
+        }
+
+        self.shift -= self.step;
+        Some(frame)
+    }
+}
+
+/// Cut the line from `from` to `to` down to the part with an x from 0 to `width`, if any.
+fn clip(from: (i32, i32), to: (i32, i32), width: i32) -> Option<((i32, i32), (i32, i32))> {
+    if from.0.max(to.0) < 0 || from.0.min(to.0) > width {
+        return None;
+    }
+
+    // Where the line crosses a vertical edge at `x`
+    let cross = |x: i32| {
+        let (dx, dy) = (to.0 - from.0, to.1 - from.1);
+        let y = from.1 as i64 + (dy as i64 * (x - from.0) as i64 + dx as i64 / 2) / dx as i64;
+        (x, y as i32)
+    };
+    let inside = |point: (i32, i32)| {
+        if point.0 < 0 {
+            cross(0)
+        } else if point.0 > width {
+            cross(width)
+        } else {
+            point
+        }
+    };
+
+    Some((inside(from), inside(to)))
+}
+
+fn place((x, y): (i32, i32), pen: bool) -> Point {
+    Point {
+        x: x as i16,
+        y: y as i16,
+        pen,
+    }
+}
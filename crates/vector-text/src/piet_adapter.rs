@@ -0,0 +1,53 @@
+use piet::kurbo::BezPath;
+use piet::{IntoBrush, LineCap, LineJoin, RenderContext, StrokeStyle};
+use vector_text_core::Point;
+
+/// Convert rendered text to a [BezPath], with a line for each line drawn.
+pub fn to_bez_path(points: &[Point]) -> BezPath {
+    let mut path = BezPath::new();
+
+    for (i, point) in points.iter().enumerate() {
+        let position = (point.x as f64, point.y as f64);
+        // Lines drawn before the first move start from the origin
+        if i == 0 && point.pen {
+            path.move_to((0.0, 0.0));
+        }
+        if point.pen {
+            path.line_to(position);
+        } else {
+            path.move_to(position);
+        }
+    }
+
+    path
+}
+
+/// Stroke rendered text onto a piet [RenderContext], such as a druid widget's canvas or
+/// an image being exported.
+///
+/// The lines are drawn `width` wide with round ends and joins, in the context's current
+/// transform: glyph units become pixels unless it is scaled.
+///
+/// ```
+/// # #[cfg(feature = "hershey")] {
+/// use piet::{Color, NullRenderContext};
+/// use vector_text::{render_text, stroke_piet, HersheyFont, VectorFont};
+///
+/// let points = render_text("Hello", VectorFont::HersheyFont(HersheyFont::Romans));
+///
+/// let mut ctx = NullRenderContext::new();
+/// stroke_piet(&mut ctx, &points, &Color::BLACK, 1.0);
+/// # }
+/// ```
+pub fn stroke_piet<R: RenderContext>(
+    ctx: &mut R,
+    points: &[Point],
+    brush: &impl IntoBrush<R>,
+    width: f64,
+) {
+    let style = StrokeStyle::new()
+        .line_cap(LineCap::Round)
+        .line_join(LineJoin::Round);
+
+    ctx.stroke_styled(to_bez_path(points), brush, width, &style);
+}
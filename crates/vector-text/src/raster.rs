@@ -0,0 +1,156 @@
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt;
+
+use vector_text_core::Point;
+
+/// A grayscale image of rendered text, for quick previews and for checking output in
+/// tests without writing an SVG file.
+///
+/// Lines are drawn one pixel wide with Bresenham's algorithm, without antialiasing: each
+/// pixel is either 0 (background) or 255 (drawn).
+///
+/// ```
+/// use vector_text::{render_text, MinimalFont, Raster, VectorFont};
+///
+/// let points = render_text("L", VectorFont::MinimalFont(MinimalFont));
+/// let raster = Raster::from_points(&points, 1);
+///
+/// // The corner of the L is drawn, but not the top right
+/// assert_eq!(raster.pixel(1, raster.height() - 2), 255);
+/// assert_eq!(raster.pixel(raster.width() - 2, 1), 0);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Raster {
+    width: usize,
+    height: usize,
+    pixels: Vec<u8>,
+}
+
+impl Raster {
+    /// Create a blank image of the given size.
+    pub fn new(width: usize, height: usize) -> Self {
+        Self {
+            width,
+            height,
+            pixels: vec![0; width * height],
+        }
+    }
+
+    /// Create an image just large enough to hold some points, with a blank `margin` of
+    /// pixels around them, and draw them.
+    pub fn from_points(points: &[Point], margin: usize) -> Self {
+        let (mut left, mut top) = (0, 0);
+        let (mut right, mut bottom) = (-1, -1);
+
+        for (i, point) in points.iter().enumerate() {
+            let (x, y) = (point.x as isize, point.y as isize);
+            if i == 0 {
+                (left, top, right, bottom) = (x, y, x, y);
+            }
+            left = left.min(x);
+            top = top.min(y);
+            right = right.max(x);
+            bottom = bottom.max(y);
+        }
+
+        let margin = margin as isize;
+        let mut raster = Self::new(
+            (right - left + 1 + 2 * margin) as usize,
+            (bottom - top + 1 + 2 * margin) as usize,
+        );
+        raster.draw(points, margin - left, margin - top);
+        raster
+    }
+
+    /// Width of the image, in pixels.
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Height of the image, in pixels.
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// The pixels, row by row from the top left, one byte each.
+    pub fn pixels(&self) -> &[u8] {
+        &self.pixels
+    }
+
+    /// The value of the pixel at the given position (0 outside the image).
+    pub fn pixel(&self, x: usize, y: usize) -> u8 {
+        if x < self.width && y < self.height {
+            self.pixels[y * self.width + x]
+        } else {
+            0
+        }
+    }
+
+    /// The pixels as RGBA, four bytes each, with lines drawn in black on white.
+    pub fn to_rgba(&self) -> Vec<u8> {
+        self.pixels
+            .iter()
+            .flat_map(|&value| {
+                let shade = 255 - value;
+                [shade, shade, shade, 255]
+            })
+            .collect()
+    }
+
+    /// Draw the lines between some points, with the point (0, 0) moved to pixel
+    /// (`dx`, `dy`). Anything outside the image is clipped.
+    pub fn draw(&mut self, points: &[Point], dx: isize, dy: isize) {
+        for pair in points.windows(2) {
+            if pair[1].pen {
+                self.line(
+                    (pair[0].x as isize + dx, pair[0].y as isize + dy),
+                    (pair[1].x as isize + dx, pair[1].y as isize + dy),
+                );
+            }
+        }
+    }
+
+    /// Draw a line between two pixels, with Bresenham's algorithm
+    fn line(&mut self, (mut x, mut y): (isize, isize), (x1, y1): (isize, isize)) {
+        let (dx, dy) = ((x1 - x).abs(), -(y1 - y).abs());
+        let (sx, sy) = ((x1 - x).signum(), (y1 - y).signum());
+        let mut error = dx + dy;
+
+        loop {
+            self.plot(x, y);
+            if x == x1 && y == y1 {
+                break;
+            }
+
+            let e2 = 2 * error;
+            if e2 >= dy {
+                error += dy;
+                x += sx;
+            }
+            if e2 <= dx {
+                error += dx;
+                y += sy;
+            }
+        }
+    }
+
+    fn plot(&mut self, x: isize, y: isize) {
+        if (0..self.width as isize).contains(&x) && (0..self.height as isize).contains(&y) {
+            self.pixels[y as usize * self.width + x as usize] = 255;
+        }
+    }
+}
+
+/// Shows the image as text, with `#` for drawn pixels and `.` for the background.
+impl fmt::Display for Raster {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.pixels.chunks(self.width.max(1)) {
+            for &value in row {
+                f.write_str(if value > 0 { "#" } else { "." })?;
+            }
+            f.write_str("\n")?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,234 @@
+use alloc::string::{String, ToString};
+
+#[cfg(feature = "borland")]
+use crate::BorlandFont;
+#[cfg(feature = "svgfont")]
+use crate::EmbeddedSvgFont;
+#[cfg(feature = "hershey")]
+use crate::HersheyFont;
+use crate::{MinimalFont, ParseFontError, SegmentFont, VectorFont};
+
+/// Prefix of the names of Hershey fonts in the registry
+#[cfg(feature = "hershey")]
+const HERSHEY: &str = "hershey-";
+/// Prefix of the names of Borland fonts in the registry
+#[cfg(feature = "borland")]
+const BORLAND: &str = "borland-";
+/// Name of the NewStroke font in the registry
+#[cfg(feature = "newstroke")]
+const NEWSTROKE: &str = "newstroke";
+/// Name of the minimal font in the registry
+const MINIMAL: &str = "minimal";
+/// Prefix of the names of segment fonts in the registry
+const SEGMENT: &str = "segment-";
+/// Prefix of the names of embedded SVG fonts in the registry
+#[cfg(feature = "svgfont")]
+const SVG: &str = "svg-";
+
+/// Number of built-in fonts: the minimal and segment fonts, plus the fonts of each
+/// enabled backend
+const BUILT_IN: usize = {
+    // Not mutated when no backends are enabled
+    #[allow(unused_mut)]
+    let mut n = 1 + SegmentFont::ALL.len();
+
+    #[cfg(feature = "hershey")]
+    {
+        n += HersheyFont::ALL.len();
+    }
+
+    #[cfg(feature = "borland")]
+    {
+        n += BorlandFont::ALL.len();
+    }
+
+    #[cfg(feature = "newstroke")]
+    {
+        n += 1;
+    }
+
+    n
+};
+
+impl VectorFont<'static> {
+    /// Every built-in font: the Hershey fonts, then the Borland fonts, then NewStroke,
+    /// then the minimal font, then the segment fonts.
+    ///
+    /// Only the fonts of enabled backends are included. This doesn't include the
+    /// embedded SVG fonts, which depend on how the program was built.
+    pub const ALL: [Self; BUILT_IN] = {
+        let mut all = [VectorFont::MinimalFont(MinimalFont); BUILT_IN];
+        let mut n = 0;
+
+        #[cfg(feature = "hershey")]
+        {
+            let mut i = 0;
+            while i < HersheyFont::ALL.len() {
+                all[n] = VectorFont::HersheyFont(HersheyFont::ALL[i]);
+                n += 1;
+                i += 1;
+            }
+        }
+
+        #[cfg(feature = "borland")]
+        {
+            let mut i = 0;
+            while i < BorlandFont::ALL.len() {
+                all[n] = VectorFont::BorlandFont(BorlandFont::ALL[i]);
+                n += 1;
+                i += 1;
+            }
+        }
+
+        #[cfg(feature = "newstroke")]
+        {
+            all[n] = VectorFont::NewstrokeFont(());
+            n += 1;
+        }
+
+        // The minimal font is already in place
+        n += 1;
+
+        let mut i = 0;
+        while i < SegmentFont::ALL.len() {
+            all[n] = VectorFont::SegmentFont(SegmentFont::ALL[i]);
+            n += 1;
+            i += 1;
+        }
+
+        all
+    };
+
+    /// Look up one of the built-in fonts by name, ignoring case.
+    ///
+    /// Fonts are named after their family and file, like `"hershey-romans"`,
+    /// `"borland-litt"`, `"newstroke"`, `"minimal"`, `"segment-seven"`, or
+    /// `"svg-emsallure"` (for an [EmbeddedSvgFont] compiled in from `EMSAllure.svg`).
+    /// This allows fonts to be chosen by command-line arguments or configuration files:
+    ///
+    /// ```
+    /// # #[cfg(feature = "hershey")] {
+    /// use vector_text::{HersheyFont, VectorFont};
+    ///
+    /// let font = VectorFont::by_name("hershey-romans");
+    /// assert!(matches!(font, Some(VectorFont::HersheyFont(HersheyFont::Romans))));
+    /// assert!(VectorFont::by_name("comic-sans").is_none());
+    /// # }
+    /// ```
+    ///
+    /// The same names are used by the [core::fmt::Display] and [core::str::FromStr]
+    /// implementations.
+    ///
+    /// Fonts from backends which are not enabled are not found.
+    pub fn by_name(name: &str) -> Option<Self> {
+        #[cfg(feature = "hershey")]
+        if let Some(rest) = strip_prefix(name, HERSHEY) {
+            return Self::hershey_by_name(rest);
+        }
+
+        #[cfg(feature = "borland")]
+        if let Some(rest) = strip_prefix(name, BORLAND) {
+            return Self::borland_by_name(rest);
+        }
+
+        #[cfg(feature = "svgfont")]
+        if let Some(rest) = strip_prefix(name, SVG) {
+            return rest.parse().ok().map(VectorFont::EmbeddedSvgFont);
+        }
+
+        #[cfg(feature = "newstroke")]
+        if name.eq_ignore_ascii_case(NEWSTROKE) {
+            return Some(VectorFont::NewstrokeFont(()));
+        }
+
+        if let Some(rest) = strip_prefix(name, SEGMENT) {
+            rest.parse().ok().map(VectorFont::SegmentFont)
+        } else if name.eq_ignore_ascii_case(MINIMAL) {
+            Some(VectorFont::MinimalFont(MinimalFont))
+        } else {
+            None
+        }
+    }
+
+    /// Look up a Hershey font by the name of its mapping file, ignoring case, such as
+    /// `"romans"` for [HersheyFont::Romans].
+    ///
+    /// ```
+    /// use vector_text::{HersheyFont, VectorFont};
+    ///
+    /// let font = VectorFont::hershey_by_name("Romans");
+    /// assert_eq!(font, Some(VectorFont::HersheyFont(HersheyFont::Romans)));
+    /// ```
+    #[cfg(feature = "hershey")]
+    pub fn hershey_by_name(name: &str) -> Option<Self> {
+        name.parse().ok().map(VectorFont::HersheyFont)
+    }
+
+    /// Look up a Borland font by the name of its `.CHR` file, ignoring case, such as
+    /// `"litt"` for [BorlandFont::Litt].
+    #[cfg(feature = "borland")]
+    pub fn borland_by_name(name: &str) -> Option<Self> {
+        name.parse().ok().map(VectorFont::BorlandFont)
+    }
+
+    /// Iterate over every built-in font, in the order of [VectorFont::ALL], followed by
+    /// the embedded SVG fonts.
+    pub fn iter() -> impl Iterator<Item = Self> {
+        let all = Self::ALL.into_iter();
+
+        #[cfg(feature = "svgfont")]
+        let all = all.chain(EmbeddedSvgFont::iter().map(VectorFont::EmbeddedSvgFont));
+
+        all
+    }
+}
+
+impl VectorFont<'_> {
+    /// The name of this font in the registry used by [VectorFont::by_name],
+    /// or `None` for a font defined in code or loaded at runtime.
+    pub fn name(&self) -> Option<String> {
+        match self {
+            Self::Custom(_) => None,
+            #[cfg(feature = "svgfont")]
+            Self::SvgFont(_) => None,
+            _ => Some(self.to_string()),
+        }
+    }
+}
+
+impl core::fmt::Display for VectorFont<'_> {
+    /// Write the name of this font in the registry, or `custom` for a font defined in code
+    /// or loaded at runtime.
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            #[cfg(feature = "hershey")]
+            Self::HersheyFont(font) => write!(f, "{}{}", HERSHEY, font),
+            #[cfg(feature = "borland")]
+            Self::BorlandFont(font) => write!(f, "{}{}", BORLAND, font),
+            #[cfg(feature = "newstroke")]
+            Self::NewstrokeFont(()) => f.write_str(NEWSTROKE),
+            Self::MinimalFont(_) => f.write_str(MINIMAL),
+            Self::SegmentFont(font) => write!(f, "{}{}", SEGMENT, font),
+            #[cfg(feature = "svgfont")]
+            Self::EmbeddedSvgFont(font) => write!(f, "{}{}", SVG, font),
+            #[cfg(feature = "svgfont")]
+            Self::SvgFont(_) => f.write_str("custom"),
+            Self::Custom(_) => f.write_str("custom"),
+        }
+    }
+}
+
+impl core::str::FromStr for VectorFont<'static> {
+    type Err = ParseFontError;
+
+    fn from_str(name: &str) -> Result<Self, ParseFontError> {
+        Self::by_name(name).ok_or(ParseFontError)
+    }
+}
+
+/// Remove a prefix from a name, ignoring case.
+fn strip_prefix<'n>(name: &'n str, prefix: &str) -> Option<&'n str> {
+    let head = name.get(..prefix.len())?;
+    head.eq_ignore_ascii_case(prefix)
+        .then(|| &name[prefix.len()..])
+}
@@ -0,0 +1,128 @@
+use core::ops::Range;
+
+use alloc::vec::Vec;
+
+use vector_text_core::Point;
+
+use crate::Layout;
+
+/// How much more of the text each frame of a [Reveal] draws.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub enum RevealStep {
+    /// One more character, like a typewriter. Spaces get frames of their own, so the
+    /// text keeps a steady pace.
+    #[default]
+    Glyph,
+    /// One more stroke, as if the text were being written by hand.
+    Stroke,
+}
+
+/// An iterator over the frames of an animation of a [Layout] being drawn, from
+/// [Layout::reveal].
+///
+/// Each frame is all of the points to draw for it, so it can be sent as it is to a
+/// laser projector or oscilloscope, which redraws the whole frame every time.
+#[derive(Debug, Clone)]
+pub struct Reveal<'a> {
+    layout: &'a Layout,
+    step: RevealStep,
+    /// Index of the glyph being drawn
+    glyph: usize,
+    /// Index in `layout.points` which the glyph has been drawn up to, by stroke
+    drawn: Option<usize>,
+}
+
+impl Layout {
+    /// Animate the text appearing, a character or a stroke at a time.
+    ///
+    /// The frames draw the glyphs in the order of the text, and any shadow of them along
+    /// with them. The last frame draws the whole layout.
+    ///
+    /// ```
+    /// # #[cfg(feature = "hershey")] {
+    /// use vector_text::{HersheyFont, RenderOptions, RevealStep, TextLayout, VectorFont};
+    ///
+    /// let mut layout = TextLayout::new();
+    /// layout.push_text(
+    ///     "Hi there",
+    ///     VectorFont::HersheyFont(HersheyFont::Romans),
+    ///     RenderOptions::default(),
+    /// );
+    /// let built = layout.build().unwrap();
+    ///
+    /// let frames: Vec<_> = built.reveal(RevealStep::Glyph).collect();
+    /// assert_eq!(frames.len(), 8);
+    /// assert_eq!(frames[7].len(), built.points.len());
+    ///
+    /// // "H" has three strokes, and "i" has two
+    /// let strokes: Vec<_> = built.reveal(RevealStep::Stroke).collect();
+    /// assert_eq!(strokes[4].len(), frames[1].len());
+    /// # }
+    /// ```
+    pub fn reveal(&self, step: RevealStep) -> Reveal<'_> {
+        Reveal {
+            layout: self,
+            step,
+            glyph: 0,
+            drawn: None,
+        }
+    }
+}
+
+impl Reveal<'_> {
+    /// Draw the glyphs before `glyph`, then the points of it in `partial`.
+    fn frame(&self, glyph: usize, partial: Range<usize>) -> Vec<Point> {
+        let layout = self.layout;
+        let shadow = layout.shadow_len();
+
+        let ranges = layout.glyphs[..glyph]
+            .iter()
+            .map(|glyph| glyph.points.clone())
+            .chain([partial]);
+        let shadows = ranges
+            .clone()
+            .map(|range| range.start - shadow..range.end - shadow)
+            .take_while(|_| shadow > 0);
+
+        shadows
+            .chain(ranges)
+            .flat_map(|range| layout.points[range].iter().copied())
+            .collect()
+    }
+}
+
+impl Iterator for Reveal<'_> {
+    type Item = Vec<Point>;
+
+    fn next(&mut self) -> Option<Vec<Point>> {
+        while let Some(glyph) = self.layout.glyphs.get(self.glyph) {
+            let range = glyph.points.clone();
+
+            if self.step == RevealStep::Glyph {
+                self.glyph += 1;
+                return Some(self.frame(self.glyph - 1, range));
+            }
+
+            let start = self.drawn.unwrap_or(range.start);
+            if start >= range.end {
+                self.glyph += 1;
+                self.drawn = None;
+                continue;
+            }
+
+            // The stroke runs until the pen is next lifted
+            let points = &self.layout.points;
+            let end = (start + 1..range.end)
+                .find(|&i| !points[i].pen)
+                .unwrap_or(range.end);
+            self.drawn = Some(end);
+
+            // Moves which draw nothing aren't worth a frame
+            if points[start..end].iter().any(|point| point.pen) {
+                return Some(self.frame(self.glyph, range.start..end));
+            }
+        }
+
+        None
+    }
+}
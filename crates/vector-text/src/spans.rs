@@ -0,0 +1,342 @@
+use alloc::vec::Vec;
+
+use vector_text_core::{
+    FontMetrics, Origin, Point, RenderError, RenderOptions, Scale, YAxis, layout_glyphs,
+};
+
+use crate::VectorFont;
+
+/// Where a [TextSpan] sits relative to the baseline.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub enum ScriptPosition {
+    /// On the baseline, at full size.
+    #[default]
+    Normal,
+    /// Raised and smaller, with the tops of its capitals level with those of the font.
+    Superscript,
+    /// Lowered by half the font's descender, and smaller.
+    Subscript,
+}
+
+impl ScriptPosition {
+    /// Size superscripts and subscripts are drawn at, relative to the rest of the span
+    pub const SCALE: f32 = 0.6;
+}
+
+/// A piece of text drawn with its own font and size, for [render_spans].
+#[derive(Debug, Copy, Clone)]
+pub struct TextSpan<'a> {
+    pub text: &'a str,
+    pub font: VectorFont<'a>,
+    /// Size to draw the text at, relative to the font's own units
+    pub scale: f32,
+    /// Draw the text twice, slightly offset, to make its lines thicker
+    pub bold: bool,
+    /// Draw a line under the text, halfway down the font's descenders
+    pub underline: bool,
+    /// Draw the text as a superscript or subscript
+    pub position: ScriptPosition,
+    /// Draw lowercase letters as capitals the size of lowercase letters
+    pub small_caps: bool,
+}
+
+impl<'a> TextSpan<'a> {
+    /// Create a span drawn at the font's own size.
+    pub fn new(text: &'a str, font: VectorFont<'a>) -> Self {
+        Self {
+            text,
+            font,
+            scale: 1.0,
+            bold: false,
+            underline: false,
+            position: ScriptPosition::Normal,
+            small_caps: false,
+        }
+    }
+
+    /// Draw this span at the given size, relative to the font's own units.
+    pub fn with_scale(mut self, scale: f32) -> Self {
+        self.scale = scale;
+        self
+    }
+
+    /// Draw this span in bold, by drawing it twice, a unit of the font apart.
+    pub fn with_bold(mut self, bold: bool) -> Self {
+        self.bold = bold;
+        self
+    }
+
+    /// Draw a line under this span.
+    pub fn with_underline(mut self, underline: bool) -> Self {
+        self.underline = underline;
+        self
+    }
+
+    /// Draw the lowercase letters of this span as small capitals, which suits headings
+    /// in fonts whose lowercase letters are weak.
+    pub fn with_small_caps(mut self, small_caps: bool) -> Self {
+        self.small_caps = small_caps;
+        self
+    }
+
+    /// Draw this span as a superscript or subscript.
+    pub fn with_position(mut self, position: ScriptPosition) -> Self {
+        self.position = position;
+        self
+    }
+}
+
+/// Render a line of text made of spans with different fonts and sizes, such as a label
+/// mixing Hershey text with a name in CJK characters from NewStroke.
+///
+/// The spans are drawn one after another on a shared baseline, that of the first span.
+/// For [RenderOptions::origin], the line reaches as high and as low as a line of the
+/// first span, so by default its top is at 0, like text rendered on its own. Each span
+/// should be a single line, since line breaks only move down within it. Byte offsets in
+/// errors count through the text of all of the spans, one after another, but those given
+/// to [RenderOptions::glyph_rotation] count from the start of each span (or of each run
+/// of lowercase or other letters, in small caps).
+///
+/// ```
+/// # #[cfg(all(feature = "hershey", feature = "newstroke"))] {
+/// use vector_text::{render_spans, HersheyFont, RenderOptions, TextSpan, VectorFont};
+///
+/// let spans = [
+///     TextSpan::new("Name: ", VectorFont::HersheyFont(HersheyFont::Romans)),
+///     TextSpan::new("山田", VectorFont::NewstrokeFont(())).with_scale(0.5),
+/// ];
+///
+/// let result = render_spans(&spans, &RenderOptions::default()).unwrap();
+/// # }
+/// ```
+///
+/// Stroke fonts have no bold weight, so [TextSpan::bold] spans are drawn twice instead,
+/// the second time shifted right by a unit of the font. On a plotter this overstrike
+/// makes the lines visibly thicker. Nor do they have small capitals, so
+/// [TextSpan::small_caps] spans draw lowercase letters as capitals, scaled down from the
+/// font's cap height to its x-height.
+///
+/// Superscripts and subscripts (see [ScriptPosition]) are drawn at
+/// [ScriptPosition::SCALE] of the span's size, moved off the baseline according to the
+/// span's font metrics. Footnote marks and chemical formulas can be written this way:
+///
+/// ```
+/// # #[cfg(feature = "hershey")] {
+/// use vector_text::{render_spans, HersheyFont, RenderOptions, ScriptPosition, TextSpan, VectorFont};
+///
+/// let font = VectorFont::HersheyFont(HersheyFont::Romans);
+/// let spans = [
+///     TextSpan::new("H", font),
+///     TextSpan::new("2", font).with_position(ScriptPosition::Subscript),
+///     TextSpan::new("O", font),
+/// ];
+///
+/// let result = render_spans(&spans, &RenderOptions::default()).unwrap();
+/// # }
+/// ```
+pub fn render_spans(
+    spans: &[TextSpan],
+    options: &RenderOptions,
+) -> Result<Vec<Point>, RenderError> {
+    let mut result = Vec::new();
+
+    let Some(first) = spans.first() else {
+        return Ok(result);
+    };
+
+    // Spans are put together along a baseline at y = 0 with y increasing downward, then
+    // moved, shadowed, and flipped if need be
+    let (shadow, origin, y_axis) = (options.shadow, options.origin, options.y_axis);
+    // The size of the text as a whole goes along with that of each span
+    let size = match options.scale.denominator {
+        0 => f32::INFINITY,
+        denominator => options.scale.numerator as f32 / denominator as f32,
+    };
+    let options = &RenderOptions {
+        scale: Scale::ONE,
+        shadow: None,
+        origin: Origin::BaselineLeft,
+        y_axis: YAxis::Down,
+        ..*options
+    };
+
+    let baseline = 0.0;
+    // Left edge of the next span
+    let mut x = 0.0;
+    // Byte offset of the span in all of the text, for errors
+    let mut start = 0;
+
+    for span in spans {
+        let metrics = span.font.metrics();
+        let span_scale = span.scale * size;
+
+        let (scale, span_baseline) = match span.position {
+            ScriptPosition::Normal => (span_scale, baseline),
+            ScriptPosition::Superscript => (
+                span_scale * ScriptPosition::SCALE,
+                baseline - metrics.cap_height as f32 * (1.0 - ScriptPosition::SCALE) * span_scale,
+            ),
+            ScriptPosition::Subscript => (
+                span_scale * ScriptPosition::SCALE,
+                baseline + metrics.descender as f32 / 2.0 * span_scale,
+            ),
+        };
+
+        let mut run = Run {
+            span,
+            options,
+            metrics,
+            baseline: span_baseline,
+            x,
+            start,
+        };
+
+        if span.small_caps {
+            // Small capitals are as tall as the lowercase letters they replace
+            let small = match metrics.cap_height {
+                0 => 1.0,
+                cap_height => metrics.x_height as f32 / cap_height as f32,
+            };
+
+            let mut rest = span.text;
+            while let Some(first) = rest.chars().next() {
+                let lowercase = first.is_lowercase();
+                let len = rest
+                    .find(|c: char| c.is_lowercase() != lowercase)
+                    .unwrap_or(rest.len());
+                let (text, after) = rest.split_at(len);
+
+                if lowercase {
+                    run.draw(&text.to_uppercase(), scale * small, &mut result)?;
+                } else {
+                    run.draw(text, scale, &mut result)?;
+                }
+
+                run.start += len;
+                rest = after;
+            }
+        } else {
+            run.draw(span.text, scale, &mut result)?;
+        }
+
+        if span.underline && run.x > x {
+            let y = span_baseline + (metrics.descender / 2).max(1) as f32 * scale;
+            let overflow = RenderError::AdvanceOverflow { offset: start };
+            let (Some(left), Some(right), Some(y)) = (round(x), round(run.x), round(y)) else {
+                return Err(overflow);
+            };
+
+            result.push(Point {
+                x: left,
+                y,
+                pen: false,
+            });
+            result.push(Point {
+                x: right,
+                y,
+                pen: true,
+            });
+        }
+
+        x = run.x;
+        start += span.text.len();
+    }
+
+    // The line reaches as high and as low as a line of the first span
+    let metrics = first.font.metrics();
+    let (top, bottom) = (
+        -(metrics.ascender as f32) * first.scale * size,
+        metrics.descender as f32 * first.scale * size,
+    );
+    let (dx, dy) = origin.locate(
+        round(x).unwrap_or(i16::MAX),
+        round(top).unwrap_or(i16::MIN),
+        round(bottom).unwrap_or(i16::MAX),
+        0,
+    );
+    for point in &mut result {
+        point.x = point.x.saturating_sub(dx);
+        point.y = point.y.saturating_sub(dy);
+    }
+    if let Some(shadow) = shadow {
+        shadow.apply(&mut result, 0);
+    }
+
+    y_axis.apply(&mut result);
+    Ok(result)
+}
+
+/// Text from a [TextSpan] being drawn by [render_spans]
+struct Run<'s, 'a> {
+    span: &'s TextSpan<'a>,
+    options: &'s RenderOptions,
+    metrics: FontMetrics,
+    /// Where the span's baseline is drawn
+    baseline: f32,
+    /// Left edge of the text
+    x: f32,
+    /// Byte offset of the text in all of the spans, for errors
+    start: usize,
+}
+
+impl Run<'_, '_> {
+    /// Draw some text from the span at the given scale, moving along past it
+    fn draw(&mut self, text: &str, scale: f32, out: &mut Vec<Point>) -> Result<(), RenderError> {
+        let font = self.span.font;
+        let mut points = Vec::new();
+        let glyphs = layout_glyphs(
+            text,
+            |_, c| font.glyph(c).map(|glyph| (glyph, 0)),
+            |_| self.options,
+            &self.metrics,
+            self.options,
+            &mut points,
+        )?;
+
+        // Move a point onto the baseline, scaling it around there
+        let place = |x_units: f32, y: i16, pen: bool, offset: usize| {
+            let (px, py) = (self.x + x_units * scale, self.baseline + y as f32 * scale);
+
+            match (round(px), round(py)) {
+                (Some(x), Some(y)) => Ok(Point { x, y, pen }),
+                _ => Err(RenderError::AdvanceOverflow {
+                    // Offsets are only meaningful in the span's own text
+                    offset: self.start + offset.min(text.len()),
+                }),
+            }
+        };
+
+        for glyph in &glyphs {
+            let glyph_points = &points[glyph.points.clone()];
+            for point in glyph_points {
+                out.push(place(point.x as f32, point.y, point.pen, glyph.offset)?);
+            }
+
+            // Bold text is drawn twice, the second time a unit of the font to the right
+            if self.span.bold {
+                for point in glyph_points {
+                    out.push(place(
+                        point.x as f32 + 1.0,
+                        point.y,
+                        point.pen,
+                        glyph.offset,
+                    )?);
+                }
+            }
+        }
+
+        let width = glyphs.iter().map(|g| g.x + g.advance).max().unwrap_or(0);
+        self.x += width as f32 * scale;
+        Ok(())
+    }
+}
+
+/// Round a coordinate to the nearest whole unit, if it fits in a [Point]
+pub(crate) fn round(value: f32) -> Option<i16> {
+    let rounded = if value < 0.0 {
+        value - 0.5
+    } else {
+        value + 0.5
+    } as i32;
+    i16::try_from(rounded).ok()
+}
@@ -0,0 +1,173 @@
+use alloc::format;
+use alloc::vec::Vec;
+
+use vector_text_core::{
+    BBox, Font, MinimalFont, Origin, Point, RenderError, RenderOptions, Scale, smooth,
+};
+
+use crate::{VectorFont, render_text_with_options};
+
+/// Draw every glyph of a font in a grid, `columns` wide, with the codepoint of each (in
+/// hexadecimal) written beneath it, to see at a glance which characters the font has and
+/// how it draws them.
+///
+/// The glyphs are drawn in order of codepoint, from the top left, each in a cell as wide
+/// as the widest of them. The points have y increasing downward.
+///
+/// ```
+/// # #[cfg(feature = "hershey")] {
+/// use vector_text::{HersheyFont, VectorFont, specimen};
+///
+/// let sheet = specimen(VectorFont::HersheyFont(HersheyFont::Romans), 16).unwrap();
+/// assert!(!sheet.is_empty());
+/// # }
+/// ```
+///
+/// Fonts with thousands of glyphs, such as NewStroke, may not fit in the range of a
+/// [Point], in which case [RenderError::AdvanceOverflow] gives the byte offset of the first
+/// glyph which didn't fit, in the string of every character the font has.
+pub fn specimen(font: VectorFont<'_>, columns: u16) -> Result<Vec<Point>, RenderError> {
+    let chars: Vec<char> = font.coverage().flatten().collect();
+    let metrics = Font::metrics(&font);
+    let columns = columns.max(1) as usize;
+
+    // Labels are about a third of the height of capitals
+    let label_options = RenderOptions {
+        origin: Origin::TopCenter,
+        scale: MinimalFont
+            .metrics()
+            .scale_to_cap_height((metrics.cap_height / 3).max(1) as u16),
+        ..Default::default()
+    };
+    let label_height = label_options
+        .scale
+        .apply(MinimalFont.metrics().line_height() as i32);
+
+    let widest = chars
+        .iter()
+        .filter_map(|&c| font.glyph(c).ok())
+        .map(|glyph| glyph.advance() as i32)
+        .max()
+        .unwrap_or(0);
+    let gap = metrics.average_advance as i32 / 2;
+    let width = widest.max(label_height * 2) + gap;
+    let height = metrics.line_height() as i32 + label_height + gap;
+
+    let options = RenderOptions {
+        origin: Origin::TopCenter,
+        ..Default::default()
+    };
+
+    let mut points = Vec::new();
+    let mut offset = 0;
+    for (i, &c) in chars.iter().enumerate() {
+        let overflow = RenderError::AdvanceOverflow { offset };
+        let to_i16 = |value: i32| i16::try_from(value).map_err(|_| overflow);
+
+        // Where the top of the middle of the cell is, checking that all of it fits
+        let (column, row) = ((i % columns) as i32, (i / columns) as i32);
+        to_i16((column + 1) * width)?;
+        to_i16((row + 1) * height)?;
+        let (x, y) = ((column * width + width / 2) as i16, (row * height) as i16);
+        let label_y = y + metrics.line_height();
+
+        let mut glyph = [0; 4];
+        let glyph = render_text_with_options(c.encode_utf8(&mut glyph), font, &options)?;
+        let label =
+            MinimalFont.render_text_with_options(&format!("{:04X}", c as u32), &label_options)?;
+
+        points.extend(glyph.into_iter().map(|p| p.translate(x, y)));
+        points.extend(label.into_iter().map(|p| p.translate(x, label_y)));
+
+        offset += c.len_utf8();
+    }
+
+    Ok(points)
+}
+
+/// Draw the same text at each of a ramp of sizes, one above another, to find the smallest
+/// size at which a font still reads well when drawn with a given pen.
+///
+/// Each row is labelled on the left with the height of capitals at its scale, and if
+/// `smoothing` is more than 0, the text is drawn again to the right of it,
+/// [smoothed](smooth) that many times, to see whether smoothing helps at that size. The
+/// points have y increasing downward.
+///
+/// ```
+/// # #[cfg(feature = "hershey")] {
+/// use vector_text::{HersheyFont, Scale, VectorFont, waterfall};
+///
+/// let scales = [0x40, 0x80, 0x100, 0x200].map(Scale::from_q8_8);
+/// let font = VectorFont::HersheyFont(HersheyFont::Romans);
+/// let sheet = waterfall("Handgloves", font, &scales, 2).unwrap();
+/// assert!(!sheet.is_empty());
+/// # }
+/// ```
+///
+/// If the sheet doesn't fit in the range of a [Point], [RenderError::AdvanceOverflow] is
+/// returned.
+pub fn waterfall(
+    text: &str,
+    font: VectorFont<'_>,
+    scales: &[Scale],
+    smoothing: u8,
+) -> Result<Vec<Point>, RenderError> {
+    let metrics = Font::metrics(&font);
+    let overflow = RenderError::AdvanceOverflow { offset: 0 };
+
+    // Labels are half the height of capitals at the font's own size
+    let label_options = RenderOptions {
+        scale: MinimalFont
+            .metrics()
+            .scale_to_cap_height((metrics.cap_height / 2).max(1) as u16),
+        ..Default::default()
+    };
+    let label_height = label_options
+        .scale
+        .apply(MinimalFont.metrics().line_height() as i32);
+    let gap = label_height / 2;
+
+    let mut rows = Vec::with_capacity(scales.len());
+    for &scale in scales {
+        let options = RenderOptions {
+            scale,
+            ..Default::default()
+        };
+        let label = format!("{}", scale.apply(metrics.cap_height as i32));
+        let label = MinimalFont.render_text_with_options(&label, &label_options)?;
+        let points = render_text_with_options(text, font, &options)?;
+        let height = scale.apply(metrics.line_height() as i32).max(label_height);
+        rows.push((label, points, height));
+    }
+
+    // Line up the text of every row, and the smoothed copies to the right of it all
+    let right = |points: &[Point]| BBox::of(points).map_or(0, |bounds| bounds.max.0 as i32);
+    let labels = rows.iter().map(|(label, ..)| right(label)).max();
+    let text_x = labels.unwrap_or(0) + gap;
+    let widest = rows.iter().map(|(_, points, _)| right(points)).max();
+    let smooth_x = text_x + widest.unwrap_or(0) + gap * 4;
+
+    let mut sheet = Vec::new();
+    let mut y = 0;
+    for (label, points, height) in rows {
+        let place = |points: Vec<Point>, x: i32, sheet: &mut Vec<Point>| {
+            let bounds = BBox::of(&points);
+            let (max_x, max_y) = bounds.map_or((0, 0), |b| (b.max.0 as i32, b.max.1 as i32));
+            i16::try_from(x + max_x).map_err(|_| overflow)?;
+            i16::try_from(y + max_y).map_err(|_| overflow)?;
+            sheet.extend(points.into_iter().map(|p| p.translate(x as i16, y as i16)));
+            Ok(())
+        };
+
+        let smoothed = (smoothing > 0).then(|| smooth(&points, smoothing));
+        place(label, 0, &mut sheet)?;
+        place(points, text_x, &mut sheet)?;
+        if let Some(smoothed) = smoothed {
+            place(smoothed, smooth_x, &mut sheet)?;
+        }
+
+        y += height + gap;
+    }
+
+    Ok(sheet)
+}
@@ -0,0 +1,142 @@
+use core::f32::consts::{FRAC_PI_2, TAU};
+
+use vector_text_core::RenderError;
+
+use crate::spans::round;
+use crate::{Layout, TextLayout};
+
+/// An Archimedean spiral to lay out the text of a [TextLayout] along, a favourite of pen
+/// plotter art.
+///
+/// ```
+/// # #[cfg(feature = "hershey")] {
+/// use vector_text::{HersheyFont, RenderOptions, Spiral, TextLayout, VectorFont};
+///
+/// let mut layout = TextLayout::new();
+/// layout.push_text(
+///     "The quick brown fox jumps over the lazy dog",
+///     VectorFont::HersheyFont(HersheyFont::Romans),
+///     RenderOptions::default(),
+/// );
+///
+/// let spiral = Spiral { start_radius: 100.0, pitch: 40.0 };
+/// let built = layout.build_spiral(&spiral).unwrap();
+///
+/// // The text starts at the top of the spiral, around the centre
+/// let first = &built.glyphs[0];
+/// assert!(first.x.abs() < 10 && first.y < -100);
+/// # }
+/// ```
+#[derive(Debug, Copy, Clone)]
+pub struct Spiral {
+    /// Distance from the centre to the baseline where the text starts
+    pub start_radius: f32,
+    /// Distance the spiral moves out with each turn, which should be more than the
+    /// height of the text for the turns not to overlap. If negative, the spiral winds
+    /// inward instead.
+    pub pitch: f32,
+}
+
+impl Spiral {
+    /// Distance of the spiral from the centre, `angle` radians from where it starts
+    fn radius(&self, angle: f32) -> f32 {
+        self.start_radius + self.pitch / TAU * angle
+    }
+
+    /// Length of the spiral from where it starts to `angle` radians further round
+    fn length(&self, angle: f32) -> f32 {
+        let growth = self.pitch / TAU;
+        if growth == 0.0 {
+            return self.start_radius * angle;
+        }
+
+        // Length from the centre to where the radius is `growth * turned`
+        let growth = growth.abs();
+        let from_centre = |turned: f32| {
+            growth / 2.0 * (turned * libm::sqrtf(1.0 + turned * turned) + libm::asinhf(turned))
+        };
+        let length =
+            from_centre(self.radius(angle) / growth) - from_centre(self.start_radius / growth);
+        // Winding inward, the radius shrinks as the length grows
+        length * self.pitch.signum()
+    }
+
+    /// How far round from the start the spiral is `length` along it, if it gets that far
+    /// before reaching the centre
+    fn angle_at(&self, length: f32) -> Option<f32> {
+        if self.pitch < 0.0 && length > self.length(self.start_radius / (-self.pitch / TAU)) {
+            return None;
+        }
+
+        // Newton's method, starting from the angle it would be on a circle
+        let mut angle = length / self.start_radius.abs().max(1.0);
+        for _ in 0..16 {
+            let growth = self.pitch / TAU;
+            let radius = self.radius(angle);
+            let slope = libm::sqrtf(radius * radius + growth * growth);
+            if slope == 0.0 {
+                break;
+            }
+            angle -= (self.length(angle) - length) / slope;
+        }
+        Some(angle)
+    }
+}
+
+impl TextLayout<'_> {
+    /// Lay out the text along a spiral, instead of in lines.
+    ///
+    /// The spiral is centred on the origin, and the text starts at its top and runs
+    /// clockwise (with y increasing downward), standing on its baseline and turned to
+    /// follow it. Lines after the first are drawn further in, by their distance below
+    /// it, so text is best kept to a single line. The spiral is scaled along with the text
+    /// by [RenderOptions::scale](crate::RenderOptions::scale), and
+    /// [Origin](crate::Origin) is not used,
+    /// and the `x` and `y` of each glyph are where the top left corner of it ends up. If
+    /// the spiral winds inward, the text past the centre is left out.
+    pub fn build_spiral(&self, spiral: &Spiral) -> Result<Layout, RenderError> {
+        let mut layout = self.build_with_width(None)?;
+
+        let Some(baseline) = self.font_at(0).map(|font| font.metrics().baseline) else {
+            return Ok(layout);
+        };
+
+        for i in 0..layout.glyphs.len() {
+            let glyph = &mut layout.glyphs[i];
+            let centre = glyph.x as f32 + glyph.advance as f32 / 2.0;
+
+            let Some(turned) = spiral.angle_at(centre) else {
+                layout.keep(i);
+                break;
+            };
+            let radius = spiral.radius(turned);
+            let growth = spiral.pitch / TAU;
+            let (sin, cos) = libm::sincosf(turned - FRAC_PI_2);
+
+            // Along the spiral, and away from its centre
+            let (tx, ty) = (growth * cos - radius * sin, growth * sin + radius * cos);
+            let length = libm::hypotf(tx, ty).max(f32::EPSILON);
+            let (tx, ty) = (tx / length, ty / length);
+            let (nx, ny) = (ty, -tx);
+
+            let overflow = RenderError::AdvanceOverflow {
+                offset: glyph.offset,
+            };
+            let place = |x: i16, y: i16| {
+                let (along, below) = (x as f32 - centre, y as f32 - baseline as f32);
+                let x = radius * cos + along * tx - below * nx;
+                let y = radius * sin + along * ty - below * ny;
+                round(x).zip(round(y)).ok_or(overflow)
+            };
+
+            for point in &mut layout.points[glyph.points.clone()] {
+                (point.x, point.y) = place(point.x, point.y)?;
+            }
+            (glyph.x, glyph.y) = place(glyph.x, glyph.y)?;
+        }
+
+        self.resize(&mut layout)?;
+        self.shadow_and_flip(&mut layout);
+        Ok(layout)
+    }
+}
@@ -0,0 +1,248 @@
+use alloc::vec::Vec;
+use core::ops::Range;
+
+use vector_text_core::{
+    FontMetrics, Glyph, Point, RenderError, RenderOptions, is_combining_mark, render_mixed_glyphs,
+};
+
+use crate::VectorFont;
+
+/// An ordered list of fonts used together to render text.
+///
+/// The text is split into [Run]s, each drawn with the first font in the stack that has
+/// glyphs for it, so a decorative font can be combined with a font with wider coverage.
+/// The baselines of all the fonts are lined up with that of the primary font:
+///
+/// ```
+/// # #[cfg(all(feature = "hershey", feature = "newstroke"))] {
+/// use vector_text::{FontStack, HersheyFont, VectorFont};
+///
+/// let stack = FontStack::new(VectorFont::HersheyFont(HersheyFont::Romans))
+///     .with_fallback(VectorFont::NewstrokeFont(()));
+///
+/// let result = stack.render_text("Grüße, Ω!");
+/// # }
+/// ```
+///
+/// A fallback font whose baseline is at another y has its glyphs moved to match, unless
+/// the baselines are too far apart to move between:
+///
+/// ```
+/// use vector_text::{CustomFont, FontMetrics, FontStack, PackedPoint, RenderError, VectorFont};
+///
+/// // A font which draws `character` as a line across at `y`
+/// let font = |baseline, character, y| {
+///     let mut font = CustomFont::new(FontMetrics {
+///         baseline,
+///         cap_height: 10,
+///         x_height: 6,
+///         ascender: 10,
+///         descender: 3,
+///         average_advance: 8,
+///     });
+///     font.add_glyph(character, 0, 8, &[
+///         PackedPoint { x: 0, y, pen: false },
+///         PackedPoint { x: 8, y, pen: true },
+///     ]);
+///     font
+/// };
+///
+/// // Both lines are drawn on the primary font's baseline
+/// let (primary, fallback) = (font(10, 'a', 10), font(0, 'b', 0));
+/// let stack = FontStack::new(VectorFont::Custom(&primary))
+///     .with_fallback(VectorFont::Custom(&fallback));
+/// let points = stack.try_render_text("ab").unwrap();
+/// assert_eq!(points[1].y, points[3].y);
+///
+/// let (primary, fallback) = (font(20000, 'a', 0), font(-20000, 'b', 0));
+/// let stack = FontStack::new(VectorFont::Custom(&primary))
+///     .with_fallback(VectorFont::Custom(&fallback));
+/// assert_eq!(stack.try_render_text("ab"), Err(RenderError::AdvanceOverflow { offset: 1 }));
+/// ```
+pub struct FontStack<'a> {
+    fonts: Vec<VectorFont<'a>>,
+}
+
+/// A part of some text drawn with a single font from a [FontStack].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Run {
+    /// Byte range of this run in the text
+    pub range: Range<usize>,
+    /// Index in the stack of the font used to draw this run
+    pub font: usize,
+}
+
+impl<'a> FontStack<'a> {
+    /// Create a stack containing only the given primary font.
+    pub fn new(primary: VectorFont<'a>) -> Self {
+        Self {
+            fonts: alloc::vec![primary],
+        }
+    }
+
+    /// Add a font to the end of the stack, to be used for characters
+    /// which none of the previous fonts can draw.
+    pub fn with_fallback(mut self, font: VectorFont<'a>) -> Self {
+        self.push(font);
+        self
+    }
+
+    /// Add a font to the end of the stack.
+    pub fn push(&mut self, font: VectorFont<'a>) {
+        self.fonts.push(font);
+    }
+
+    /// The fonts in this stack, in order of preference.
+    pub fn fonts(&self) -> &[VectorFont<'a>] {
+        &self.fonts
+    }
+
+    /// Check whether any font in this stack has a glyph for the given character.
+    pub fn supports_char(&self, character: char) -> bool {
+        self.fonts.iter().any(|font| font.supports_char(character))
+    }
+
+    /// Split some text into runs which can each be drawn with a single font.
+    ///
+    /// Letters and digits are drawn with the first font that has glyphs for them. Other
+    /// characters, like spaces and punctuation, stay in the current run if its font can
+    /// draw them, so that they match the text around them. Combining marks, and characters
+    /// no font can draw, always stay in the current run.
+    pub fn runs<'s>(&'s self, text: &'s str) -> impl Iterator<Item = Run> + 's {
+        let mut chars = text.char_indices().peekable();
+
+        core::iter::from_fn(move || {
+            let (start, first) = chars.next()?;
+            let mut font = self.font_for(None, first);
+            let mut end = start + first.len_utf8();
+
+            while let Some(&(offset, character)) = chars.peek() {
+                match (font, self.font_for(font, character)) {
+                    (Some(current), Some(next)) if current != next => break,
+                    // Characters before the first one with a font join its run
+                    (None, next) => font = next,
+                    _ => {}
+                }
+
+                end = offset + character.len_utf8();
+                chars.next();
+            }
+
+            Some(Run {
+                range: start..end,
+                font: font.unwrap_or(0),
+            })
+        })
+    }
+
+    /// Choose the font to draw a character with, given the font of the current run.
+    fn font_for(&self, current: Option<usize>, character: char) -> Option<usize> {
+        if let Some(i) = current
+            && (is_combining_mark(character)
+                || (!character.is_alphanumeric() && self.fonts[i].supports_char(character)))
+        {
+            return Some(i);
+        }
+
+        self.fonts
+            .iter()
+            .position(|font| font.supports_char(character))
+            .or(current)
+    }
+
+    /// Look up the glyph for a character, preferring the font at index `preferred`
+    /// but falling back to the first other font that has one.
+    ///
+    /// The glyph is returned with how far down to move it to line up its baseline with
+    /// the primary font, from `shifts` (see [FontStack::shifts]). If no font can draw it,
+    /// the error from the preferred font is returned.
+    fn glyph(
+        &self,
+        shifts: &[Option<i16>],
+        preferred: usize,
+        offset: usize,
+        character: char,
+    ) -> Result<(Glyph<'_>, i16), RenderError> {
+        let others = (0..self.fonts.len()).filter(|&i| i != preferred);
+        let mut first_error = None;
+
+        for i in core::iter::once(preferred).chain(others) {
+            match self.fonts[i].glyph(character) {
+                Ok(glyph) => {
+                    let shift = shifts[i].ok_or(RenderError::AdvanceOverflow { offset })?;
+                    return Ok((glyph, shift));
+                }
+                Err(e) => {
+                    first_error.get_or_insert(e);
+                }
+            }
+        }
+
+        Err(first_error.unwrap_or(RenderError::UnsupportedCharacter(character)))
+    }
+
+    /// How far down to move the glyphs of each font to line up their baselines with the
+    /// primary font, or `None` for fonts whose baselines are too far apart from it
+    fn shifts(&self) -> Vec<Option<i16>> {
+        let baseline = self.metrics().baseline as i32;
+        self.fonts
+            .iter()
+            .map(|font| i16::try_from(baseline - font.metrics().baseline as i32).ok())
+            .collect()
+    }
+
+    /// Metrics of the stack as a whole, taken from the primary font.
+    fn metrics(&self) -> FontMetrics {
+        self.fonts[0].metrics()
+    }
+
+    /// Render the given text string to a list of points.
+    ///
+    /// Characters which no font in the stack can draw are skipped. If the text is too wide
+    /// to represent, it is cut off after the last glyph that fits.
+    pub fn render_text(&self, text: &str) -> Vec<Point> {
+        let mut result = Vec::new();
+        // On overflow, keep whatever fit before it
+        let _ = self.render_runs(text, &RenderOptions::default(), &mut result);
+        result
+    }
+
+    /// Render the given text string to a list of points,
+    /// returning an error if any character cannot be drawn by any font in the stack.
+    pub fn try_render_text(&self, text: &str) -> Result<Vec<Point>, RenderError> {
+        self.render_text_with_options(text, &RenderOptions::strict())
+    }
+
+    /// Render the given text string to a list of points with the given rendering options.
+    pub fn render_text_with_options(
+        &self,
+        text: &str,
+        options: &RenderOptions,
+    ) -> Result<Vec<Point>, RenderError> {
+        let mut result = Vec::new();
+        self.render_runs(text, options, &mut result)?;
+        Ok(result)
+    }
+
+    /// Render each run of the text with its own font, appending the points to `out`.
+    fn render_runs(
+        &self,
+        text: &str,
+        options: &RenderOptions,
+        out: &mut Vec<Point>,
+    ) -> Result<(), RenderError> {
+        let runs: Vec<Run> = self.runs(text).collect();
+        let shifts = self.shifts();
+
+        render_mixed_glyphs(
+            text,
+            |offset, c| {
+                let run = runs.partition_point(|run| run.range.end <= offset);
+                self.glyph(&shifts, runs.get(run).map_or(0, |run| run.font), offset, c)
+            },
+            &self.metrics(),
+            options,
+            out,
+        )
+    }
+}
@@ -0,0 +1,272 @@
+use alloc::vec::Vec;
+use core::fmt;
+
+use vector_text_core::{PlacedGlyph, RenderError, RenderOptions, layout_glyphs};
+
+use crate::spans::round;
+use crate::{Layout, TextLayout, VectorFont};
+
+/// What a [TextBox] does with text which doesn't fit in it.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub enum Overflow {
+    /// Leave out the lines which don't fit.
+    #[default]
+    Clip,
+    /// Leave out the lines which don't fit, and end the last line which does with an
+    /// ellipsis (`…`, or `...` if the font has no such glyph).
+    Ellipsis,
+    /// Draw the text smaller, rewrapping it, until it fits. Text which still doesn't fit
+    /// at [TextBox::MIN_SCALE] of its size is clipped.
+    ShrinkToFit,
+    /// Return [TextBoxError::Overflow].
+    Error,
+}
+
+/// A fixed area to lay out text in, such as the screen of an oscilloscope.
+///
+/// Text is wrapped to the width of the box, and the lines which fit in its height are
+/// kept according to the [Overflow] policy. A line fits if it is a whole
+/// [FontMetrics::line_height](crate::FontMetrics::line_height) from the bottom of the
+/// box. A single glyph wider than the box is left as it is.
+///
+/// ```
+/// # #[cfg(feature = "hershey")] {
+/// use vector_text::{HersheyFont, Overflow, RenderOptions, TextBox, TextLayout, VectorFont};
+///
+/// let mut layout = TextLayout::new();
+/// layout.push_text(
+///     "The quick brown fox jumps over the lazy dog",
+///     VectorFont::HersheyFont(HersheyFont::Romans),
+///     RenderOptions::default(),
+/// );
+///
+/// let text_box = TextBox { width: 200, height: 40, overflow: Overflow::Ellipsis };
+/// let built = layout.build_in(&text_box).unwrap();
+/// assert_eq!(built.glyphs.last().unwrap().character, '…');
+///
+/// let text_box = TextBox { overflow: Overflow::Error, ..text_box };
+/// assert!(layout.build_in(&text_box).is_err());
+/// # }
+/// ```
+#[derive(Debug, Copy, Clone)]
+pub struct TextBox {
+    pub width: i16,
+    pub height: i16,
+    pub overflow: Overflow,
+}
+
+impl TextBox {
+    /// Smallest scale [Overflow::ShrinkToFit] draws text at
+    pub const MIN_SCALE: f32 = 1.0 / 16.0;
+}
+
+/// An error laying out text in a [TextBox].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum TextBoxError {
+    /// The text could not be rendered.
+    Render(RenderError),
+    /// The text doesn't fit in the box, and the policy is [Overflow::Error].
+    Overflow {
+        /// Byte offset in the text of the first character which did not fit
+        offset: usize,
+    },
+}
+
+impl From<RenderError> for TextBoxError {
+    fn from(error: RenderError) -> Self {
+        Self::Render(error)
+    }
+}
+
+impl fmt::Display for TextBoxError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Render(error) => error.fmt(f),
+            Self::Overflow { offset } => {
+                write!(
+                    f,
+                    "text does not fit in the box past byte offset {}",
+                    offset
+                )
+            }
+        }
+    }
+}
+
+impl core::error::Error for TextBoxError {}
+
+impl TextLayout<'_> {
+    /// Lay out the text in a box, instead of wrapping it to
+    /// [TextLayout::with_max_width].
+    pub fn build_in(&self, text_box: &TextBox) -> Result<Layout, TextBoxError> {
+        let mut layout = self.build_with_width(Some(text_box.width))?;
+
+        let Some(line_height) = self.font_at(0).map(|font| font.metrics().line_height()) else {
+            return Ok(layout);
+        };
+        let overflow = first_outside(&layout, text_box.height, line_height);
+
+        match (overflow, text_box.overflow) {
+            (None, _) => {}
+            (Some(kept), Overflow::Clip) => layout.keep(kept),
+            (Some(kept), Overflow::Ellipsis) => self.ellipsize(&mut layout, kept, text_box)?,
+            (Some(_), Overflow::ShrinkToFit) => {
+                layout = self.shrink(text_box, line_height)?;
+            }
+            (Some(kept), Overflow::Error) => {
+                return Err(TextBoxError::Overflow {
+                    offset: layout.glyphs[kept].offset,
+                });
+            }
+        }
+
+        self.finish(&mut layout, Some(text_box.width))?;
+        Ok(layout)
+    }
+
+    /// Cut the text back at the end of the last line which fits, and add an ellipsis
+    fn ellipsize(
+        &self,
+        layout: &mut Layout,
+        mut kept: usize,
+        text_box: &TextBox,
+    ) -> Result<(), TextBoxError> {
+        let cut = layout.glyphs[kept].offset;
+        let Some(last_line) = kept.checked_sub(1).map(|i| layout.glyphs[i].y) else {
+            // Not even the first line fits
+            layout.keep(0);
+            return Ok(());
+        };
+        let line_start = layout
+            .glyphs
+            .iter()
+            .find(|glyph| glyph.y == last_line)
+            .map_or(0, |glyph| glyph.x);
+
+        let font = self.font_at(cut.saturating_sub(1)).unwrap();
+        let (mut points, advance) = ellipsis(font)?;
+
+        // Make room for the ellipsis, and don't leave it after a space
+        while let Some(last) = kept.checked_sub(1).map(|i| &layout.glyphs[i])
+            && last.y == last_line
+            && (last.x as i32 + last.advance as i32 + advance as i32 > text_box.width as i32
+                || last.character.is_whitespace())
+        {
+            kept -= 1;
+        }
+        layout.keep(kept);
+
+        let x = match kept.checked_sub(1).map(|i| &layout.glyphs[i]) {
+            Some(last) if last.y == last_line => last.x + last.advance,
+            _ => line_start,
+        };
+        let metrics = self.font_at(0).unwrap().metrics();
+        let dy = last_line + metrics.baseline - font.metrics().baseline;
+
+        let start = layout.points.len();
+        for point in &mut points {
+            point.x = point.x.saturating_add(x);
+            point.y = point.y.saturating_add(dy);
+        }
+        layout.points.append(&mut points);
+        layout.glyphs.push(PlacedGlyph {
+            offset: cut,
+            character: '…',
+            x,
+            y: last_line,
+            advance,
+            points: start..layout.points.len(),
+            pen: layout.glyphs.last().map_or(0, |glyph| glyph.pen),
+        });
+
+        Ok(())
+    }
+
+    /// Find the largest scale the text fits in the box at, and lay it out at that scale
+    fn shrink(&self, text_box: &TextBox, line_height: i16) -> Result<Layout, TextBoxError> {
+        let at_scale = |scale: f32| {
+            let width = (text_box.width as f32 / scale).min(i16::MAX as f32) as i16;
+            let height = (text_box.height as f32 / scale).min(i16::MAX as f32) as i16;
+            let layout = self.build_with_width(Some(width))?;
+            let fits = first_outside(&layout, height, line_height).is_none();
+            Ok::<_, RenderError>((layout, height, fits))
+        };
+
+        // Search between a scale the text fits at and one it doesn't
+        let (mut low, mut high) = (TextBox::MIN_SCALE, 1.0);
+        let (mut layout, height, fits) = at_scale(low)?;
+        if fits {
+            for _ in 0..12 {
+                let middle = (low + high) / 2.0;
+                let (candidate, _, fits) = at_scale(middle)?;
+                if fits {
+                    (low, layout) = (middle, candidate);
+                } else {
+                    high = middle;
+                }
+            }
+        } else if let Some(kept) = first_outside(&layout, height, line_height) {
+            layout.keep(kept);
+        }
+
+        layout.scale(low);
+        Ok(layout)
+    }
+}
+
+/// Index of the first glyph of a layout on a line which doesn't fit in `height`, if any
+fn first_outside(layout: &Layout, height: i16, line_height: i16) -> Option<usize> {
+    layout
+        .glyphs
+        .iter()
+        .position(|glyph| glyph.y as i32 + line_height as i32 > height as i32)
+}
+
+/// The points and advance of an ellipsis in the given font, with the top of its line at
+/// 0
+fn ellipsis(font: VectorFont) -> Result<(Vec<crate::Point>, i16), RenderError> {
+    let text = if font.supports_char('…') {
+        "…"
+    } else {
+        "..."
+    };
+
+    let options = RenderOptions::default();
+    let mut points = Vec::new();
+    let glyphs = layout_glyphs(
+        text,
+        |_, c| font.glyph(c).map(|glyph| (glyph, 0)),
+        |_| &options,
+        &font.metrics(),
+        &options,
+        &mut points,
+    )?;
+    let advance = glyphs.iter().map(|g| g.x + g.advance).max().unwrap_or(0);
+
+    Ok((points, advance))
+}
+
+impl Layout {
+    /// Keep only the first `len` glyphs, and their points
+    pub(crate) fn keep(&mut self, len: usize) {
+        self.glyphs.truncate(len);
+        let end = self.glyphs.iter().map(|g| g.points.end).max().unwrap_or(0);
+        self.points.truncate(end);
+    }
+
+    /// Scale everything down around the origin
+    fn scale(&mut self, scale: f32) {
+        // Shrinking, so everything stays in range
+        let scaled = |value: i16| round(value as f32 * scale).unwrap_or(value);
+
+        for point in &mut self.points {
+            point.x = scaled(point.x);
+            point.y = scaled(point.y);
+        }
+        for glyph in &mut self.glyphs {
+            glyph.x = scaled(glyph.x);
+            glyph.y = scaled(glyph.y);
+            glyph.advance = scaled(glyph.advance);
+        }
+    }
+}